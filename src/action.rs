@@ -2,29 +2,100 @@ use std::path::PathBuf;
 
 use strum_macros::Display;
 
-use crate::ui::AppTab;
+use crate::model::students::StudentList;
+use crate::ui::{AppTab, TableColumn};
 
-#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, PartialEq, Display)]
 pub enum Action {
     Quit,
     EnterInsertMode,
     LeaveInsertMode,
+    EnterSearchMode,
+    LeaveSearchMode,
+    CloseWelcome,
+    ShowHelp,
+    HideHelp,
+    ShowLog,
+    HideLog,
+    OpenFilePicker,
+    CloseFilePicker,
+    OpenExportModal,
+    CloseExportModal,
+    OpenMaxPointsPopup,
+    CloseMaxPointsPopup,
+    ShowGradeStudents(u8),
+    CloseGradeStudents,
+    SelectStudent(String),
+    FilterGrade(Option<u8>), // shows only the students with this grade in the result tab
+    OpenRowMenu(String),
+    CloseRowMenu,
+    RowMenuChoice(Box<Action>), // chosen in the row menu
+    EditCell(TableColumn),      // of the selected student
+    OpenNotePopup(String),
+    CloseNotePopup,
+    CopyStudent(String),
+    Confirmed(Box<Action>),
+    CloseConfirmation,
+    OpenCurvePreview(Option<f64>), // target average grade, the configured one if not given
+    CloseCurvePreview,
     SwitchTab(AppTab),
+    ToggleSplitView,
+    ToggleFullWidth,
+    FocusNext,
+    FocusPrevious,
+    ToggleStudentPane,
+    ToggleColumn(TableColumn),
+    SwitchTheme(Option<String>), // None switches to the next builtin theme
     UpdateView,
     UpdateModel(ModelAction),
+    Undo,
+    Redo,
     LoadStudentList(PathBuf),
+    OpenRecent(Option<usize>), // the numbered recent file, or the list of them
+    StudentListLoaded(PathBuf, Result<StudentList, String>), // sent by the loading thread
     ExportTo(Option<PathBuf>),
+    ExportAs(String), // file extension, exported to the export directory
+    ExportDefault,    // in the default export format, or asks for the format
+    ExportResults(Option<PathBuf>), // xlsx sheet of the graded students, named after the course if not given
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Display)]
+impl Action {
+    // returns true for the actions which change the course or the scale,
+    // or open an editor for it. They are disabled in read-only mode.
+    pub fn changes_model(&self) -> bool {
+        match self {
+            Action::UpdateModel(_)
+            | Action::Undo
+            | Action::Redo
+            | Action::EditCell(_)
+            | Action::OpenNotePopup(_)
+            | Action::OpenMaxPointsPopup
+            | Action::OpenCurvePreview(_)
+            | Action::ExportTo(None) => true,
+            Action::RowMenuChoice(action) | Action::Confirmed(action) => action.changes_model(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Display)]
 pub enum ModelAction {
     IncrementThreshold(u8), // u8 repr grade for which the threshold should be changed
     DecrementThreshold(u8), // u8 repr grade for which the threshold should be changed
+    SetThreshold(u8, f64),  // grade and the new minimum points
     IncrementMaxPoints,
     DecrementMaxPoints,
-    SetMaxPoints(u16),
+    SetMaxPoints(f64),
     ToggleHalfPoints,
-    SetScale(u8), // u8 repr grade. See GradeScaleType::try_from()
+    SetScale(u8),                   // u8 repr grade. See GradeScaleType::try_from()
+    SetCustomScale([(u8, f64); 6]), // e.g. a named scale of the config
+    ResetScale,
+    DeleteStudent(String),
     IncrementStudentPoints(String),
     DecrementStudentPoints(String),
+    SetStudentPoints(String, f64),
+    RenameStudent(String, String),
+    SetStudentNotes(String, String),
+    ToggleStudentAbsent(String),
+    RenameCourse(String), // new name
 }
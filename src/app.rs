@@ -1,32 +1,86 @@
 use color_eyre::eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
 use layout::Flex;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Tabs};
+use ratatui::widgets::{Block, Paragraph, Tabs, Wrap};
 use ratatui::{text::Line, Frame};
+use std::collections::HashMap;
+use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 use strum::IntoEnumIterator;
-use tracing::debug;
-use tui_input::backend::crossterm::EventHandler;
-use tui_input::Input;
+use tracing::{debug, warn};
 
 use crate::action::{Action, ModelAction};
-use crate::config::AppConfig;
-use crate::model::scale::GradeScaleType;
+use crate::clipboard;
+use crate::commands::Commands;
+use crate::config::{load_theme, AppConfig, NamedScale};
+use crate::export::{self, ExportColumn};
+use crate::keymap::{Chord, Keymap, KeymapPreset, HINT_DELAY};
+use crate::locale::format_points;
+use crate::logging::log_path;
+use crate::model::exam::{ExamInfo, WeightedExam};
+use crate::model::scale::{Grade, GradeScaleType};
+use crate::model::students::StudentList;
 use crate::model::Model;
+use crate::recent::RecentFiles;
 use crate::tui::Tui;
-use crate::ui::report_tab::ExamChart;
+use crate::ui::box_plot::BoxPlot;
+use crate::ui::command_line::CommandLine;
+use crate::ui::confirm::ConfirmDialog;
+use crate::ui::cumulative_chart::CumulativeChart;
+use crate::ui::curve_preview::CurvePreview;
+use crate::ui::export_modal::ExportModal;
+use crate::ui::fail_warning::FailWarning;
+use crate::ui::file_picker::FilePicker;
+use crate::ui::grade_popup::GradeStudentsPopup;
+use crate::ui::grade_summary::GradeSummary;
+use crate::ui::help::{self, HelpPopup, KeyContext};
+use crate::ui::log_viewer::LogViewer;
+use crate::ui::note_popup::NotePopup;
+use crate::ui::notification::Toasts;
+use crate::ui::overlay::{Overlay, OverlayStack};
+use crate::ui::pass_gauge::PassGauge;
+use crate::ui::points_popup::MaxPointsPopup;
+use crate::ui::report_tab::{Comparison, ExamChart};
+use crate::ui::row_menu::RowMenu;
 use crate::ui::scale_tab::GradingScaleTable;
-use crate::ui::students_tab::ExamResultTable;
-use crate::ui::theme::{AppStyle, THEME};
-use crate::ui::AppTab;
+use crate::ui::stats_tab::StatsTab;
+use crate::ui::student_pane::StudentPane;
+use crate::ui::students_tab::{ExamResultTable, SortOrder};
+use crate::ui::theme::{AppStyle, ThemeVariant, THEME};
+use crate::ui::welcome::WelcomeScreen;
+use crate::ui::which_key::WhichKey;
+use crate::ui::{AppTab, TableColumn};
+
+/// Maximum time to wait for an event before the view is redrawn.
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// Interval in which a file loaded in the background is checked.
+const LOADING_POLL_RATE: Duration = Duration::from_millis(50);
+
+/// Smallest terminal size the layout works with.
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
+
+/// Time after which a single digit without a following key switches the tab.
+const COUNT_TIMEOUT: Duration = Duration::from_millis(400);
+/// Upper bound of a count prefix, so a typo does not freeze the app.
+const MAX_COUNT: usize = 999;
+/// Shown instead of applying a change in read-only mode.
+const READ_ONLY_HINT: &str = "Read-only mode, changes are disabled.";
+
+/// Max points, pass grade and the points of the present students.
+type ComparedPoints = (f64, Grade, Vec<f64>);
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum AppMode {
     Normal,
     Insert,
-    // Help,
+    Search,
     Exited,
 }
 
@@ -38,17 +92,38 @@ pub struct App {
     scale_tab: GradingScaleTable,
     results_tab: ExamResultTable,
     report_tab: ExamChart,
-    input_field: Input,
+    box_plot: BoxPlot,
+    stats_tab: StatsTab,
+    pass_gauge: PassGauge,
+    fail_warning: FailWarning,
+    grade_summary: GradeSummary,
+    cumulative_chart: CumulativeChart,
+    command_line: CommandLine,
+    tabs: Vec<AppTab>, // shown tabs in the order of the number keys
     selected_tab: AppTab,
+    focus: AppTab, // the tab which receives the keys, differs from the selected one in split view
+    overlays: OverlayStack,
+    toasts: Toasts,
+    split_view: bool,
+    full_width: bool,
+    student_pane: Option<StudentPane>, // shown next to the result table if some
+    hidden_columns: Vec<TableColumn>,
+    count: Option<(usize, Instant)>, // vim-style count prefix and the time of the last digit
+    keymap: Keymap,
+    keymap_preset: KeymapPreset,
+    recent_files: RecentFiles,
+    loading: Option<(PathBuf, Receiver<Result<StudentList, String>>)>, // file read in the background
+    watched: Option<Option<SystemTime>>, // last modification of the course file in watch mode
+    read_only: bool,
+    course_name: Option<(PathBuf, String)>, // overrides the name of this course file
+    export_columns: Option<Vec<ExportColumn>>, // overrides the columns of the config
+    named_scales: Vec<NamedScale>,          // the `[scales]` of the config
+    compared: Option<ComparedPoints>,       // what the scale comparisons were computed for
 }
 
 impl App {
     pub fn new() -> Self {
-        let config = if let Ok(config) = AppConfig::read_config() {
-            config
-        } else {
-            AppConfig::default()
-        };
+        let config = AppConfig::read_config().unwrap_or_default();
 
         Self {
             config,
@@ -58,8 +133,33 @@ impl App {
             scale_tab: GradingScaleTable::new(GradeScaleType::IHK),
             results_tab: ExamResultTable::new(),
             report_tab: ExamChart::default(),
-            input_field: Input::default(),
+            box_plot: BoxPlot::default(),
+            stats_tab: StatsTab::default(),
+            pass_gauge: PassGauge::default(),
+            fail_warning: FailWarning::default(),
+            grade_summary: GradeSummary::default(),
+            cumulative_chart: CumulativeChart::default(),
+            command_line: CommandLine::default(),
+            tabs: AppTab::iter().collect(),
             selected_tab: AppTab::default(),
+            focus: AppTab::default(),
+            overlays: OverlayStack::default(),
+            toasts: Toasts::default(),
+            split_view: false,
+            full_width: false,
+            student_pane: None,
+            hidden_columns: Vec::new(),
+            count: None,
+            keymap: Keymap::default(),
+            keymap_preset: KeymapPreset::default(),
+            loading: None,
+            watched: None,
+            read_only: false,
+            course_name: None,
+            export_columns: None,
+            named_scales: Vec::new(),
+            compared: None,
+            recent_files: RecentFiles::load(),
         }
     }
 
@@ -71,71 +171,600 @@ impl App {
         self
     }
 
-    pub fn with_points(mut self, points: u32) -> Self {
-        self.model.scale.set_max_points(points as f64);
+    pub fn with_points(mut self, points: f64) -> Self {
+        self.model.scale.set_max_points(points);
+        self
+    }
+
+    // the other exams of the course and the weight of the opened one in the average.
+    pub fn with_exams(mut self, weight: f64, exams: Vec<WeightedExam>) -> Self {
+        self.model.weight = weight;
+        for exam in exams {
+            self.model.add_exam(exam);
+        }
+        self
+    }
+
+    pub fn with_half_points(mut self, enabled: bool) -> Self {
+        self.model.scale.set_half_points(enabled);
         self
     }
 
+    // overrides the default scale of the config.
+    pub fn with_scale(mut self, scale_type: Option<GradeScaleType>) -> Self {
+        if let Some(scale_type) = scale_type {
+            self.model.scale.change_scale_type(scale_type);
+            self.update(Action::UpdateView);
+        }
+        self
+    }
+
+    // the course is read in the background like a file opened later.
     pub fn with_course(mut self, course_file_path: Option<PathBuf>) -> Self {
         if let Some(path_buf) = course_file_path {
-            if let Err(e) = self.model.load_student_data(path_buf.as_path()) {
-                debug!("{e}")
-            } else {
-                self.student_data_file_path = Some(path_buf);
-            }
+            self.update(Action::LoadStudentList(path_buf));
         };
         self
     }
 
+    // the opened course file, or the one which is still loading.
+    fn course_path(&self) -> Option<&PathBuf> {
+        self.student_data_file_path
+            .as_ref()
+            .or(self.loading.as_ref().map(|(path, _)| path))
+    }
+
+    // disables all changes, e.g. to show the results to students.
+    pub fn with_read_only(mut self, enabled: bool) -> Self {
+        self.read_only = enabled;
+        self
+    }
+
+    pub fn with_export_columns(mut self, columns: Option<Vec<ExportColumn>>) -> Self {
+        self.export_columns = columns;
+        self
+    }
+
+    // reloads the course file whenever it is changed by another program.
+    pub fn with_watch(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.watched = Some(self.course_modified());
+        }
+        self
+    }
+
+    fn course_modified(&self) -> Option<SystemTime> {
+        let path = self.course_path()?;
+        fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+
+    // overrides the name derived from the file name, also when the file is reloaded.
+    pub fn with_course_name(mut self, name: Option<String>) -> Self {
+        if let Some(name) = name {
+            self.model.set_class_name(&name);
+            self.course_name = self.course_path().cloned().map(|path| (path, name));
+        }
+        self
+    }
+
+    // the initial order of the result table.
+    pub fn with_sort(mut self, sort: Option<SortOrder>) -> Self {
+        self.results_tab.set_sort(sort);
+        self
+    }
+
+    pub fn with_exam(mut self, exam: ExamInfo) -> Self {
+        self.model.exam = exam;
+        self
+    }
+
     pub fn init(mut self) -> Self {
         self.model
             .scale
             .change_scale_type(self.config.get_default_scale());
+        self.load_theme(&self.config.get_theme());
+        self.model.pass_grade = self.config.get_pass_grade();
+        self.results_tab
+            .set_pass_grade(self.model.pass_grade.to_number());
+        self.tabs = self.config.get_tabs();
+        self.named_scales = self.config.get_scales();
+        self.full_width = self.config.get_full_width();
+        self.keymap_preset = self.config.get_keymap();
+        self.selected_tab = self.config.get_start_tab(self.course_path().is_some());
+        self.focus = self.selected_tab;
+        self.report_tab
+            .set_bin_width(self.config.get_histogram_bin_width());
+        self.report_tab
+            .set_target_average(self.config.get_target_average());
+        let (warning, critical) = self.config.get_pass_quota_thresholds();
+        self.pass_gauge.set_thresholds(warning, critical);
+        self.model.fail_limit = self.config.get_fail_limit();
+        self.fail_warning.set_limit(self.model.fail_limit);
+        self.hidden_columns = self.config.get_hidden_columns();
+        self.scale_tab.set_hidden_columns(&self.hidden_columns);
+        self.results_tab.set_hidden_columns(&self.hidden_columns);
+        if self.course_path().is_none() {
+            let welcome = WelcomeScreen::new(self.recent_files.existing());
+            self.overlays.push(Overlay::Welcome(welcome));
+        }
         self.update(Action::UpdateView);
         self
     }
 
+    fn remember_file(&mut self, path: &Path) {
+        self.recent_files.add(path);
+        if let Err(e) = self.recent_files.save() {
+            warn!("Could not save the recent files: {e}");
+        }
+    }
+
     fn update(&mut self, action: Action) {
+        self.dispatch(action, false);
+    }
+
+    // returns false if the theme could not be loaded.
+    fn load_theme(&mut self, name: &str) -> bool {
+        match load_theme(name) {
+            Ok(palette) => {
+                THEME.set(name, self.config.with_scale_colors(palette));
+                true
+            }
+            Err(e) => {
+                self.toasts.error(e.to_string());
+                false
+            }
+        }
+    }
+
+    // ask the user before the given action is executed.
+    fn ask_for_confirmation(&mut self, message: &str, action: Action) {
+        self.overlays
+            .push(Overlay::Confirm(ConfirmDialog::new(message, action)));
+    }
+
+    // `confirmed` is set if the user already agreed to a destructive action.
+    fn dispatch(&mut self, action: Action, confirmed: bool) {
         debug!("ACTION: {}", action);
+        if self.read_only && action.changes_model() {
+            self.overlays
+                .close(|overlay| matches!(overlay, Overlay::RowMenu(_)));
+            self.toasts.info(READ_ONLY_HINT);
+            return;
+        }
 
         match action {
+            Action::Confirmed(action) => {
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::Confirm(_)));
+                self.dispatch(*action, true);
+            }
+            Action::CloseConfirmation => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Confirm(_))),
+            Action::OpenCurvePreview(target) => {
+                let Some(target) = target.or(self.config.get_target_average()) else {
+                    self.toasts
+                        .error("No target average, use `:curve <avg>` or set target_average.");
+                    return;
+                };
+                match self.model.curve(target) {
+                    Some(curve) => {
+                        let preview = CurvePreview::new(
+                            &self.model.scale,
+                            curve,
+                            self.model.grade_average(),
+                            target,
+                        );
+                        self.overlays.push(Overlay::Curve(preview));
+                    }
+                    None => self.toasts.info("No graded students to curve."),
+                }
+            }
+            Action::CloseCurvePreview => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Curve(_))),
+            Action::Quit if !confirmed && self.model.has_unsaved_changes() => {
+                self.ask_for_confirmation("Quit without saving your changes?", Action::Quit)
+            }
             Action::Quit => self.exit(),
             Action::EnterInsertMode => self.enter_insert_mode(),
             Action::LeaveInsertMode => self.leave_insert_mode(),
+            Action::EnterSearchMode => self.mode = AppMode::Search,
+            Action::LeaveSearchMode => self.mode = AppMode::Normal,
+            Action::OpenRecent(None) => {
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::Welcome(_)));
+                let welcome = WelcomeScreen::new(self.recent_files.existing());
+                self.overlays.push(Overlay::Welcome(welcome));
+            }
+            Action::OpenRecent(Some(number)) => {
+                match self.recent_files.existing().get(number - 1) {
+                    Some(file) => {
+                        self.dispatch(Action::LoadStudentList(file.path.clone()), confirmed)
+                    }
+                    None => self.toasts.error(format!("No recent file {number}.")),
+                }
+            }
+            Action::CloseWelcome => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Welcome(_))),
+            Action::ShowHelp => self.overlays.push(Overlay::Help(HelpPopup::default())),
+            Action::HideHelp => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Help(_))),
+            Action::ShowLog => {
+                let path = log_path();
+                self.overlays.push(Overlay::Log(LogViewer::new(&path)));
+            }
+            Action::HideLog => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Log(_))),
+            Action::OpenFilePicker => {
+                // start in the directory of the current course file or the course dir.
+                let dir = self
+                    .student_data_file_path
+                    .as_ref()
+                    .and_then(|path| path.parent())
+                    .map(|dir| dir.to_path_buf())
+                    .unwrap_or_else(|| self.config.get_course_dir());
+                let mut file_picker = FilePicker::default();
+                file_picker.change_dir(&dir);
+                self.overlays.push(Overlay::FilePicker(file_picker));
+            }
+            Action::CloseFilePicker => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::FilePicker(_))),
+            Action::OpenExportModal => {
+                let mut export_modal = ExportModal::default();
+                export_modal.open(
+                    &self.config.get_export_dir(),
+                    &self.export_file_stem(),
+                    self.config.get_default_export_format(),
+                );
+                self.overlays.push(Overlay::Export(export_modal));
+            }
+            Action::ExportDefault => match self.config.get_default_export_format() {
+                Some(format) => self.dispatch(Action::ExportAs(format.to_string()), confirmed),
+                None => self.dispatch(Action::OpenExportModal, confirmed),
+            },
+            Action::CloseExportModal => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Export(_))),
+            Action::OpenMaxPointsPopup => {
+                let mut popup = MaxPointsPopup::default();
+                popup.open(self.model.scale.max_points());
+                self.overlays.push(Overlay::MaxPoints(popup));
+            }
+            Action::CloseMaxPointsPopup => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::MaxPoints(_))),
+            Action::ShowGradeStudents(grade) => {
+                let names = self.model.students_with_grade(grade);
+                self.overlays
+                    .push(Overlay::GradeStudents(GradeStudentsPopup::new(
+                        grade, names,
+                    )));
+            }
+            Action::SelectStudent(name) => {
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::GradeStudents(_)));
+                self.update(Action::SwitchTab(AppTab::Result));
+                self.results_tab.select_name(&name);
+            }
+            Action::FilterGrade(grade) => {
+                self.results_tab.set_grade_filter(grade);
+                if grade.is_some() {
+                    self.update(Action::SwitchTab(AppTab::Result));
+                }
+            }
+            Action::OpenRowMenu(name) => {
+                let absent = self
+                    .model
+                    .get_student_detail(&name)
+                    .is_some_and(|detail| detail.absent);
+                self.overlays
+                    .push(Overlay::RowMenu(RowMenu::new(&name, absent)));
+            }
+            Action::CloseRowMenu => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::RowMenu(_))),
+            Action::RowMenuChoice(action) => {
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::RowMenu(_)));
+                self.dispatch(*action, confirmed);
+            }
+            Action::EditCell(column) => self.results_tab.edit_cell(column),
+            Action::OpenNotePopup(name) => {
+                let notes = self
+                    .model
+                    .get_student_detail(&name)
+                    .map(|detail| detail.notes)
+                    .unwrap_or_default();
+                self.overlays
+                    .push(Overlay::Note(NotePopup::new(&name, &notes)));
+            }
+            Action::CloseNotePopup => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::Note(_))),
+            Action::CopyStudent(name) => {
+                let row = self
+                    .results_tab
+                    .selected_row()
+                    .filter(|row| row.cell(TableColumn::Name) == name);
+                match row.map(|row| clipboard::copy(&row.to_csv())) {
+                    Some(Ok(())) => self.toasts.info(format!("Copied {name}")),
+                    Some(Err(e)) => self.toasts.error(format!("Could not copy: {e}")),
+                    None => {}
+                }
+            }
+            Action::CloseGradeStudents => self
+                .overlays
+                .close(|overlay| matches!(overlay, Overlay::GradeStudents(_))),
+            Action::SwitchTab(selected_tab) if !self.tabs.contains(&selected_tab) => {
+                self.toasts
+                    .error(format!("The {selected_tab} tab is not configured."));
+            }
             Action::SwitchTab(selected_tab) => {
                 self.selected_tab = selected_tab;
+                self.focus = selected_tab;
                 self.update(Action::UpdateView);
             }
+            Action::SwitchTheme(name) => {
+                let name = name.unwrap_or_else(|| {
+                    let current = THEME.name().parse::<ThemeVariant>();
+                    current.map(|v| v.next()).unwrap_or_default().to_string()
+                });
+                if self.load_theme(&name) {
+                    self.toasts.info(format!("Theme: {name}"));
+                }
+            }
+            Action::ToggleColumn(column) => {
+                if let Some(index) = self.hidden_columns.iter().position(|c| *c == column) {
+                    self.hidden_columns.remove(index);
+                } else {
+                    self.hidden_columns.push(column);
+                }
+                self.scale_tab.set_hidden_columns(&self.hidden_columns);
+                self.results_tab.set_hidden_columns(&self.hidden_columns);
+            }
+            Action::ToggleSplitView => {
+                self.split_view = !self.split_view;
+                self.focus = self.selected_tab;
+            }
+            Action::ToggleFullWidth => self.full_width = !self.full_width,
+            Action::FocusNext => self.cycle_focus(1),
+            Action::FocusPrevious => self.cycle_focus(-1),
+            Action::ToggleStudentPane => {
+                self.student_pane = match self.student_pane {
+                    Some(_) => None,
+                    None => Some(StudentPane::default()),
+                };
+            }
             Action::UpdateView => {
-                let mut chart_data = [0u8; 6];
-                for (grade, count) in self.model.grade_distribution() {
-                    if (1..=6).contains(&grade) {
-                        chart_data[(grade - 1) as usize] = count as u8;
-                    }
+                let grades = chart_data(self.model.grade_distribution());
+                self.report_tab
+                    .set_data(&grades, self.model.grade_average());
+                // the builtin scales only change with the points, not with the edited thresholds.
+                let compared = (
+                    self.model.scale.max_points(),
+                    self.model.pass_grade,
+                    self.model.points(),
+                );
+                if self.compared.as_ref() != Some(&compared) {
+                    let comparisons = GradeScaleType::iter()
+                        .filter(|scale_type| !scale_type.is_custom())
+                        .filter_map(|scale_type| {
+                            let outcome = self.model.what_if(scale_type)?;
+                            Some(Comparison {
+                                scale_type,
+                                data: chart_data(outcome.distribution),
+                                avg: outcome.average,
+                                pass_rate: outcome.pass_rate,
+                            })
+                        })
+                        .collect();
+                    self.report_tab.set_comparisons(comparisons);
+                    self.compared = Some(compared);
                 }
+                let thresholds = self
+                    .model
+                    .scale
+                    .thresholds()
+                    .into_iter()
+                    .map(|(grade, min)| (grade.to_number(), min))
+                    .collect();
+                self.report_tab.set_points(
+                    self.model.points(),
+                    thresholds,
+                    self.model.scale.max_points(),
+                );
+                self.cumulative_chart
+                    .set_data(self.model.points(), self.model.scale.max_points());
+                self.box_plot
+                    .set_data(self.model.points_summary(), self.model.scale.max_points());
+                self.stats_tab
+                    .set_data(self.model.points_stats(), self.model.grade_stats());
+                self.stats_tab
+                    .set_ranking(self.model.ranking(), self.model.scale.max_points());
+                self.stats_tab.set_weighted_average(
+                    self.model.weighted_class_average(),
+                    self.model.exam_count(),
+                );
+                let (passed, total) = self.model.passed_count();
                 self.report_tab
-                    .set_data(&chart_data, self.model.grade_average());
+                    .set_pass_counts(passed, self.model.failed_count());
+                self.pass_gauge.set_data(passed, total);
+                self.fail_warning.set_data(total - passed, total);
+                self.grade_summary
+                    .set_data(&grades, self.model.grade_average(), passed, total);
+                if !self.model.get_class_name().is_empty() {
+                    self.results_tab.set_title(self.model.get_class_name());
+                }
                 self.results_tab.set_data(self.model.get_student_data());
                 self.scale_tab
                     .update(*self.model.scale.scale_type(), self.model.get_scale_data());
             }
+            // big files are read on a worker thread, so the view keeps being drawn.
             Action::LoadStudentList(path_buf) => {
-                self.model
-                    .load_student_data(path_buf.as_path())
-                    .expect(&format!(
-                        "Could not load student data from path '{}'",
-                        path_buf.display()
-                    ));
+                // relative paths are looked up in the course dir as well, the result is absolute.
+                let path_buf = match path_buf.is_relative() {
+                    true => self.config.find_course_file(&path_buf),
+                    false => path_buf,
+                };
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::FilePicker(_)));
+                let (sender, receiver) = mpsc::channel();
+                let path = path_buf.clone();
+                thread::spawn(move || {
+                    let result = StudentList::from_file(&path).map_err(|e| e.to_string());
+                    // nobody waits anymore if another file was opened meanwhile.
+                    let _ = sender.send(result);
+                });
+                self.loading = Some((path_buf, receiver));
+            }
+            Action::StudentListLoaded(path_buf, Err(e)) => self
+                .toasts
+                .error(format!("Could not load file '{}': {e}", path_buf.display())),
+            Action::StudentListLoaded(path_buf, Ok(student_list)) => {
+                self.model.set_student_list(student_list);
+                if let Some((_, name)) = self.course_name.as_ref().filter(|(p, _)| *p == path_buf) {
+                    self.model.set_class_name(&name.clone());
+                }
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::Welcome(_)));
+                self.remember_file(&path_buf);
+                self.toasts
+                    .info(format!("Loaded {}", self.model.get_class_name()));
+                self.student_data_file_path = Some(path_buf);
+                self.update(Action::UpdateView);
+            }
+            Action::UpdateModel(ModelAction::DeleteStudent(name)) if !confirmed => self
+                .ask_for_confirmation(
+                    &format!("Delete student '{name}'?"),
+                    Action::UpdateModel(ModelAction::DeleteStudent(name.clone())),
+                ),
+            Action::UpdateModel(ModelAction::ResetScale) if !confirmed => self
+                .ask_for_confirmation(
+                    "Reset all thresholds of the scale?",
+                    Action::UpdateModel(ModelAction::ResetScale),
+                ),
+            Action::Undo => {
+                if self.model.undo() {
+                    self.toasts
+                        .info(format!("Undone ({} steps)", self.model.undone_steps()));
+                } else {
+                    self.toasts.info("Nothing to undo.");
+                }
+                self.update(Action::UpdateView);
+            }
+            Action::Redo => {
+                if self.model.redo() {
+                    self.toasts
+                        .info(format!("Redone ({} steps left)", self.model.undone_steps()));
+                } else {
+                    self.toasts.info("Nothing to redo.");
+                }
+                self.update(Action::UpdateView);
             }
             Action::UpdateModel(act) => {
-                self.model.update(act);
+                self.overlays.close(|overlay| {
+                    matches!(
+                        overlay,
+                        Overlay::MaxPoints(_) | Overlay::Note(_) | Overlay::Curve(_)
+                    )
+                });
+                let result = self.model.update(act).map_err(|e| e.to_string());
+                // changes from an inline editor report back to it.
+                if self.is_editing() {
+                    match self.focus {
+                        AppTab::Scale => self.scale_tab.finish_editing(result),
+                        AppTab::Result => self.results_tab.finish_editing(result),
+                        AppTab::Report | AppTab::Stats => {}
+                    }
+                } else if let Err(e) = result {
+                    self.toasts.error(e);
+                }
                 self.update(Action::UpdateView);
             }
-            Action::ExportTo(_) => {
+            // relative paths of `:export-to` are in the export directory.
+            Action::ExportTo(Some(path)) if path.is_relative() => self.dispatch(
+                Action::ExportTo(Some(self.config.get_export_dir().join(path))),
+                confirmed,
+            ),
+            Action::ExportTo(Some(path)) if !confirmed && path.exists() => self
+                .ask_for_confirmation(
+                    &format!("Overwrite existing file '{}'?", path.display()),
+                    Action::ExportTo(Some(path.clone())),
+                ),
+            Action::ExportTo(Some(path)) => {
+                self.overlays
+                    .close(|overlay| matches!(overlay, Overlay::Export(_)));
+                match export::export(
+                    path.as_path(),
+                    &self.model.get_scale_data(),
+                    &self
+                        .export_columns
+                        .clone()
+                        .unwrap_or_else(|| self.config.get_export_columns()),
+                    &self.model.title(),
+                ) {
+                    Ok(()) => self.toasts.info(format!("Exported to {}", path.display())),
+                    Err(e) => self.toasts.error(format!("Export failed: {}", e.msg())),
+                };
+            }
+            Action::ExportAs(extension) => {
+                let file_name = format!("{}.{extension}", self.export_file_stem());
+                let path = self.config.get_export_dir().join(file_name);
+                self.dispatch(Action::ExportTo(Some(path)), confirmed);
+            }
+            Action::ExportResults(None) => {
+                let file_name = format!("{}_results.xlsx", self.export_file_stem());
+                let path = self.config.get_export_dir().join(file_name);
+                self.dispatch(Action::ExportResults(Some(path)), confirmed);
+            }
+            Action::ExportResults(Some(path)) if path.is_relative() => self.dispatch(
+                Action::ExportResults(Some(self.config.get_export_dir().join(path))),
+                confirmed,
+            ),
+            Action::ExportResults(Some(path))
+                if path.extension().and_then(|ext| ext.to_str()) != Some("xlsx") =>
+            {
+                self.toasts.error("Results are exported as xlsx.")
+            }
+            Action::ExportResults(Some(path)) if !confirmed && path.exists() => self
+                .ask_for_confirmation(
+                    &format!("Overwrite existing file '{}'?", path.display()),
+                    Action::ExportResults(Some(path.clone())),
+                ),
+            // the same sheet as `graca convert` writes, with the counts and notes below the students.
+            Action::ExportResults(Some(path)) => {
+                if self.student_data_file_path.is_none() {
+                    self.toasts.error("No student list opened.");
+                    return;
+                }
+                match export::export_results(
+                    path.as_path(),
+                    &self.model.get_student_data(),
+                    &self
+                        .export_columns
+                        .clone()
+                        .unwrap_or_else(|| self.config.get_export_columns()),
+                    &self.model.title(),
+                    self.model.pass_grade.to_number(),
+                    self.model.fail_limit,
+                ) {
+                    Ok(()) => self.toasts.info(format!("Exported to {}", path.display())),
+                    Err(e) => self.toasts.error(format!("Export failed: {}", e.msg())),
+                };
+            }
+            Action::ExportTo(None) => {
                 if let Some(file_path) = self.student_data_file_path.clone() {
-                    if let Err(e) = self.model.save_student_data(file_path.as_path()) {
-                        tracing::error!("{e}")
+                    match self.model.save_student_data(file_path.as_path()) {
+                        Ok(()) => self.toasts.info(format!("Saved {}", file_path.display())),
+                        Err(e) => self.toasts.error(format!("Could not save file: {e}")),
                     }
+                } else {
+                    self.toasts.error("No student list opened.");
                 }
             }
         }
@@ -156,6 +785,10 @@ impl App {
 
     fn draw(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            self.render_size_guard(area, frame.buffer_mut());
+            return;
+        }
 
         // main layout.
         let [header_area, main_area, help_area] = Layout::vertical([
@@ -169,49 +802,189 @@ impl App {
         self.render_header_bar(header_area, frame.buffer_mut());
 
         // MAIN AREA
-        let [table_area] = Layout::horizontal([Constraint::Max(80)])
+        // the content is clamped to a readable width unless full width is on.
+        let max_width = |width: u16| match self.full_width {
+            true => Constraint::Fill(width),
+            false => Constraint::Max(width),
+        };
+        let [table_area] = Layout::horizontal([max_width(80)])
             .margin(1)
             .flex(Flex::Center)
             .areas(main_area);
 
+        let split = self.visible_panes().len() > 1;
+        self.scale_tab
+            .set_focused(split && self.focus == AppTab::Scale);
+        self.results_tab
+            .set_focused(split && self.focus == AppTab::Result);
+
         match self.selected_tab {
+            AppTab::Scale | AppTab::Result if self.split_view => {
+                let [scale_area, result_area] = Layout::horizontal([max_width(60), max_width(80)])
+                    .margin(1)
+                    .spacing(1)
+                    .flex(Flex::Center)
+                    .areas(main_area);
+                self.scale_tab.render(scale_area, frame.buffer_mut());
+                self.results_tab.render(result_area, frame.buffer_mut());
+            }
             AppTab::Scale => self.scale_tab.render(table_area, frame.buffer_mut()),
-            AppTab::Result => self.results_tab.render(table_area, frame.buffer_mut()),
-            AppTab::Report => self.report_tab.render(table_area, frame.buffer_mut()),
+            AppTab::Result => match self.student_pane.as_mut() {
+                Some(pane) => {
+                    let [result_area, pane_area] =
+                        Layout::horizontal([max_width(80), Constraint::Length(36)])
+                            .margin(1)
+                            .spacing(1)
+                            .flex(Flex::Center)
+                            .areas(main_area);
+                    // follow the selection of the table.
+                    let detail = self
+                        .results_tab
+                        .selected_name()
+                        .and_then(|name| self.model.get_student_detail(name));
+                    pane.set_detail(detail);
+                    self.results_tab.render(result_area, frame.buffer_mut());
+                    pane.render(pane_area, frame.buffer_mut());
+                }
+                None => self.results_tab.render(table_area, frame.buffer_mut()),
+            },
+            AppTab::Report => {
+                // the warning takes a line above the report only if the fail limit is exceeded.
+                let warning_height = match self.fail_warning.message() {
+                    Some(_) => 1,
+                    None => 0,
+                };
+                let [warning_area, gauge_area, chart_area, summary_area, box_plot_area] =
+                    Layout::vertical([
+                        Constraint::Length(warning_height),
+                        Constraint::Length(3),
+                        Constraint::Fill(1),
+                        Constraint::Length(3),
+                        Constraint::Length(5),
+                    ])
+                    .areas(table_area);
+                let [gauge_area, cumulative_area] =
+                    Layout::horizontal([Constraint::Percentage(40), Constraint::Fill(1)])
+                        .spacing(1)
+                        .areas(gauge_area);
+                self.fail_warning.render(warning_area, frame.buffer_mut());
+                self.pass_gauge.render(gauge_area, frame.buffer_mut());
+                self.cumulative_chart
+                    .render(cumulative_area, frame.buffer_mut());
+                self.report_tab.render(chart_area, frame.buffer_mut());
+                self.grade_summary.render(summary_area, frame.buffer_mut());
+                self.box_plot.render(box_plot_area, frame.buffer_mut());
+            }
+            AppTab::Stats => self.stats_tab.render(table_area, frame.buffer_mut()),
         }
 
         // BOTTOM
-        App::render_help_bar(help_area, frame.buffer_mut());
+        let (mode_label, mode_style) = self.mode_indicator();
+        let [mode_area, help_area] = Layout::horizontal([
+            Constraint::Length(mode_label.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(help_area);
+        Span::styled(mode_label, mode_style).render(mode_area, frame.buffer_mut());
+        if self.mode == AppMode::Insert {
+            let error_area = Rect {
+                y: help_area.y.saturating_sub(1),
+                ..help_area
+            };
+            self.command_line
+                .render_error(error_area, frame.buffer_mut());
+            self.command_line.render(help_area, frame.buffer_mut());
+            self.render_help_bar(help_area, frame.buffer_mut());
+        } else if self.mode == AppMode::Search {
+            self.render_search_bar(help_area, frame.buffer_mut());
+            self.render_help_bar(help_area, frame.buffer_mut());
+        } else if !self.toasts.is_empty() {
+            self.toasts.render(help_area, frame.buffer_mut());
+        } else {
+            self.render_help_bar(help_area, frame.buffer_mut());
+        }
+
+        // POPUP
+        self.overlays.render(main_area, frame.buffer_mut());
+        if self.keymap.show_hints() {
+            let which_key = WhichKey {
+                prefix: self.keymap.pending(),
+                next_keys: self.keymap.next_keys(),
+            };
+            which_key.render(main_area, frame.buffer_mut());
+        }
+    }
+
+    fn render_size_guard(&self, area: Rect, buf: &mut Buffer) {
+        let text = vec![
+            Line::from("Terminal too small").style(THEME.block_title()),
+            Line::from(format!("need at least {MIN_WIDTH}x{MIN_HEIGHT}")).style(THEME.text()),
+            Line::from(format!("current {}x{}", area.width, area.height))
+                .style(THEME.text().italic()),
+        ];
+        let [text_area] = Layout::vertical([Constraint::Length(text.len() as u16)])
+            .flex(Flex::Center)
+            .areas(area);
+        Block::default().style(THEME.block()).render(area, buf);
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(text_area, buf);
     }
 
     fn render_header_bar(&self, area: Rect, buf: &mut Buffer) {
         Block::default().style(THEME.top_bar()).render(area, buf);
 
-        let scale_identifier_text = format!(" {} ", self.model.scale.scale_type().text());
-        let point_identifier_text = format!(" {} PTs ", self.model.scale.max_points());
+        let named_scale = self.active_named_scale();
+        let scale_identifier_text = match named_scale {
+            Some(scale) => format!(" {} ", scale.name.to_uppercase()),
+            None => format!(" {} ", self.model.scale.scale_type().text()),
+        };
+        let point_identifier_text =
+            format!(" {} PTs ", format_points(self.model.scale.max_points()));
         let half_identifier_text = match self.model.scale.is_using_half_points() {
             true => ".",
             false => "",
         };
+        let course_identifier_text = match self.model.title().as_str() {
+            "" => String::new(),
+            title => format!(" {title} "),
+        };
+        let undo_identifier_text = match self.model.undone_steps() {
+            0 => String::new(),
+            steps => format!(" ↶{steps} "),
+        };
 
         let [identifier_area, tabs_area, version_area] = Layout::horizontal([
             Constraint::Min(
                 (scale_identifier_text.len()
                     + point_identifier_text.len()
-                    + half_identifier_text.len()) as u16,
+                    + half_identifier_text.len()
+                    + course_identifier_text.chars().count()
+                    + undo_identifier_text.chars().count()) as u16,
             ),
             Constraint::Percentage(100),
             Constraint::Length(7),
         ])
         .areas(area);
 
-        let scale_identifier = Span::from(scale_identifier_text)
-            .style(THEME.indicator(Some(self.model.scale.scale_type())));
+        let scale_style = match named_scale.and_then(|scale| scale.color) {
+            Some(color) => THEME.indicator(None).bg(color),
+            None => THEME.indicator(Some(self.model.scale.scale_type())),
+        };
+        let scale_identifier = Span::from(scale_identifier_text).style(scale_style);
         let point_identifier = Span::from(point_identifier_text).style(THEME.tag(false));
         let half_identifier = Span::from(half_identifier_text).style(THEME.indicator(None));
+        let course_identifier = Span::from(course_identifier_text).style(THEME.tag(false));
+        let undo_identifier = Span::from(undo_identifier_text).style(THEME.tag(true));
 
-        let identifier =
-            Line::default().spans([scale_identifier, point_identifier, half_identifier]);
+        let identifier = Line::default().spans([
+            scale_identifier,
+            point_identifier,
+            half_identifier,
+            course_identifier,
+            undo_identifier,
+        ]);
 
         let version = Span::from(format!(" {} ", env!("CARGO_PKG_NAME").to_uppercase()))
             .style(THEME.indicator(None));
@@ -222,12 +995,19 @@ impl App {
     }
 
     fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
-        let [tabs_area] = Layout::horizontal([Constraint::Length(37)])
+        let titles: Vec<String> = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{tab} [{}]", index + 1))
+            .collect();
+        // titles with padding and divider.
+        let width = titles.iter().map(|title| title.len() as u16 + 3).sum();
+        let [tabs_area] = Layout::horizontal([Constraint::Length(width)])
             .flex(Flex::Center)
             .areas(area);
 
-        let titles = AppTab::iter().map(|tab| tab.to_string());
-        let selected_tab_index = self.selected_tab as usize;
+        let selected_tab_index = self.tabs.iter().position(|tab| *tab == self.selected_tab);
         Tabs::new(titles)
             .select(selected_tab_index)
             .highlight_style(THEME.tab(true))
@@ -236,48 +1016,231 @@ impl App {
             .render(tabs_area, buf);
     }
 
-    fn render_help_bar(area: Rect, buf: &mut Buffer) {
-        let mut spans: Vec<Span> = GradeScaleType::iter()
-            .flat_map(|scale_type| {
+    // the vim-like segment in front of the bottom bar, which shows the live keys.
+    fn mode_indicator(&self) -> (&'static str, Style) {
+        let style = Style::default().fg(THEME.background_color(true)).bold();
+        match self.mode {
+            AppMode::Normal if self.is_editing() => (" EDIT ", THEME.indicator(None).bold()),
+            AppMode::Normal | AppMode::Exited => (" NORMAL ", style.bg(THEME.accent_color())),
+            AppMode::Insert => (" INSERT ", THEME.indicator(None).bold()),
+            AppMode::Search => (" SEARCH ", style.bg(Color::Yellow)),
+        }
+    }
+
+    fn render_search_bar(&self, area: Rect, buf: &mut Buffer) {
+        let matches = self.results_tab.search_matches().len();
+        Line::from(vec![
+            Span::from("/").style(THEME.indicator(None)),
+            Span::from(format!(" {}", self.results_tab.search_query())).style(THEME.text()),
+            Span::from(format!("   [{matches} matches]")).style(THEME.text().italic()),
+        ])
+        .style(THEME.bottom_bar())
+        .render(area, buf);
+    }
+
+    // the keybindings which apply to the current mode and tab.
+    fn key_context(&self) -> KeyContext {
+        if let Some(context) = self.overlays.key_context() {
+            return context;
+        }
+        match self.mode {
+            AppMode::Normal if self.keymap.is_pending() => KeyContext::Chord,
+            AppMode::Insert => KeyContext::Insert,
+            AppMode::Search => KeyContext::Search,
+            _ if self.is_editing() => KeyContext::Edit,
+            _ => match self.focus {
+                AppTab::Scale => KeyContext::Scale,
+                AppTab::Result => KeyContext::Result,
+                AppTab::Report => KeyContext::Report,
+                AppTab::Stats => KeyContext::General,
+            },
+        }
+    }
+
+    fn render_help_bar(&self, area: Rect, buf: &mut Buffer) {
+        let context = self.key_context();
+        let mut hints = help::hints(context).to_vec();
+        if matches!(
+            context,
+            KeyContext::Scale | KeyContext::Result | KeyContext::Report
+        ) {
+            hints.extend(help::hints(KeyContext::General));
+        }
+        // a loading file, the pending count prefix and key sequence come first.
+        let loading = self.loading.as_ref().map(|(path, _)| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            Span::styled(format!(" ⏳ loading {name}… "), THEME.tag(true))
+        });
+        let watching = self
+            .watched
+            .map(|_| Span::styled(" 👁 watching ", THEME.tag(false)));
+        let read_only = self
+            .read_only
+            .then(|| Span::styled(" 🔒 read-only ", THEME.tag(false)));
+        let count = self
+            .count
+            .map(|(count, _)| Span::styled(format!(" {count}× "), THEME.tag(true)));
+        let chord = match self.keymap.is_pending() {
+            true => Some(Span::styled(
+                format!(" {} … ", self.keymap.pending()),
+                THEME.tag(true),
+            )),
+            false => None,
+        };
+        let spans: Vec<Span> = loading
+            .into_iter()
+            .chain(watching)
+            .chain(read_only)
+            .chain(count)
+            .chain(chord)
+            .chain(hints.into_iter().flat_map(|(key, description)| {
                 [
-                    Span::styled(
-                        format!(" {} ", scale_type.key_binding()),
-                        THEME.indicator(Some(&scale_type)),
-                    ),
-                    Span::styled(
-                        format!(" {} ", scale_type.text()),
-                        THEME.indicator(Some(&scale_type)).reversed(),
-                    ),
+                    Span::styled(format!(" {key} "), THEME.indicator(None)),
+                    Span::styled(format!(" {description} "), THEME.indicator(None).reversed()),
                 ]
-            })
+            }))
             .collect();
 
-        spans.push(Span::styled(" Q ", THEME.indicator(None)));
+        let line = Line::from(spans);
+        // the command line and the search bar use the left side of the bar.
+        match self.mode {
+            AppMode::Insert | AppMode::Search if self.overlays.is_empty() => {
+                line.right_aligned().render(area, buf)
+            }
+            _ => line.centered().style(THEME.bottom_bar()).render(area, buf),
+        }
+    }
 
-        spans.push(Span::styled(" Quit ", THEME.indicator(None).reversed()));
+    // apply the student list once the loading thread is done.
+    fn poll_loading(&mut self) {
+        let Some((path, receiver)) = &self.loading else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("the loading thread stopped".to_string()),
+        };
+        let path = path.clone();
+        self.loading = None;
+        self.update(Action::StudentListLoaded(path, result));
+    }
 
-        Line::from(spans)
-            .centered()
-            .style(THEME.bottom_bar())
-            .render(area, buf);
+    // start loading the watched file again once it was modified.
+    fn poll_watched(&mut self) {
+        let Some(last_modified) = self.watched else {
+            return;
+        };
+        let modified = self.course_modified();
+        if modified == last_modified || self.loading.is_some() {
+            return;
+        }
+        self.watched = Some(modified);
+        if let Some(path) = self.student_data_file_path.clone() {
+            debug!("{} changed, reloading", path.display());
+            self.update(Action::LoadStudentList(path));
+        }
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
+        // poll with a timeout, so toasts expire without a key press.
+        self.toasts.tick();
+        self.poll_loading();
+        self.poll_watched();
+        let timeout = match self.count {
+            Some((_, last_digit)) => COUNT_TIMEOUT.saturating_sub(last_digit.elapsed()),
+            None if self.loading.is_some() => LOADING_POLL_RATE,
+            // redraw once the hints of a pending key sequence are due.
+            None if self.keymap.is_pending() && !self.keymap.show_hints() => HINT_DELAY,
+            None => TICK_RATE,
+        };
+        if !event::poll(timeout)? {
+            self.expire_count();
+            return Ok(());
+        }
+
         match event::read()? {
             // it's important to check that the event is a key press event as
             // crossterm also emits key release and repeat events on Windows.
             Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                if let Some(action) = self.handle_key_event(key_event) {
+                if self.push_count_digit(key_event) {
+                    return Ok(());
+                }
+                let count = self.count.take().map(|(count, _)| count).unwrap_or(1);
+                self.handle_repeated_key_event(key_event, count);
+            }
+            Event::Mouse(mouse_event) => {
+                if let Some(action) = self.handle_mouse_event(mouse_event) {
                     self.update(action);
                 }
             }
+            // the next loop iteration redraws with the new size.
+            Event::Resize(width, height) => debug!("Resized to {width}x{height}"),
             _ => {}
         };
         Ok(())
     }
 
+    // digits are collected as count prefix, if no modal or editor is open.
+    fn push_count_digit(&mut self, key_event: KeyEvent) -> bool {
+        let KeyCode::Char(c @ '0'..='9') = key_event.code else {
+            return false;
+        };
+        if key_event.modifiers != KeyModifiers::NONE
+            || self.mode != AppMode::Normal
+            || !self.overlays.is_empty()
+            || self.is_editing()
+        {
+            return false;
+        }
+        let digit = c as usize - '0' as usize;
+        let count = match self.count {
+            Some((count, _)) => count * 10 + digit,
+            None if digit == 0 => return false,
+            None => digit,
+        };
+        self.count = Some((count.min(MAX_COUNT), Instant::now()));
+        true
+    }
+
+    // a single digit not followed by another key switches the tab as before.
+    fn expire_count(&mut self) {
+        let Some((count, last_digit)) = self.count else {
+            return;
+        };
+        if last_digit.elapsed() < COUNT_TIMEOUT {
+            return;
+        }
+        self.count = None;
+        if let Some(&tab) = count.checked_sub(1).and_then(|index| self.tabs.get(index)) {
+            self.update(Action::SwitchTab(tab));
+        }
+    }
+
+    // repeat the key `count` times, but stop once it opened a modal or changed the mode.
+    fn handle_repeated_key_event(&mut self, key_event: KeyEvent, count: usize) {
+        for _ in 0..count {
+            if let Some(action) = self.handle_key_event(key_event) {
+                self.update(action);
+            }
+            // the inline editors are opened by the tabs themselves.
+            if self.read_only && self.is_editing() {
+                self.scale_tab.cancel_editing();
+                self.results_tab.cancel_editing();
+                self.toasts.info(READ_ONLY_HINT);
+            }
+            if self.mode != AppMode::Normal
+                || !self.overlays.is_empty()
+                || self.is_editing()
+                || self.keymap.is_pending()
+            {
+                break;
+            }
+        }
+    }
+
     fn leave_insert_mode(&mut self) {
-        self.input_field.reset();
+        self.command_line.reset();
         self.mode = AppMode::Normal;
     }
 
@@ -285,28 +1248,127 @@ impl App {
         self.mode = AppMode::Insert;
     }
 
+    // default file name of an export without extension.
+    fn export_file_stem(&self) -> String {
+        let parts: Vec<&str> = [
+            Some(self.model.get_class_name()),
+            self.model.exam.name.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|part| !part.is_empty())
+        .collect();
+        match parts.is_empty() {
+            true => "grading_scale".to_string(),
+            false => parts.join("_"),
+        }
+    }
+
+    // only the scale table reacts to the mouse so far.
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) -> Option<Action> {
+        if self.mode != AppMode::Normal
+            || !self.overlays.is_empty()
+            || !self.visible_panes().contains(&AppTab::Scale)
+        {
+            return None;
+        }
+        self.scale_tab.handle_mouse_event(mouse_event)
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Action> {
         // Terminate with CTRL+C
-        if key_event.modifiers == KeyModifiers::CONTROL {
-            if key_event.code == KeyCode::Char('c') {
-                debug!("Should exit");
-                self.exit();
+        if key_event.modifiers == KeyModifiers::CONTROL && key_event.code == KeyCode::Char('c') {
+            debug!("Should exit");
+            self.exit();
+        }
+
+        // an open modal gets all keys.
+        if !self.overlays.is_empty() {
+            return self.overlays.handle_event(key_event);
+        }
+
+        if self.mode == AppMode::Normal
+            && !self.is_editing()
+            && (key_event.modifiers - KeyModifiers::SHIFT).is_empty()
+        {
+            if key_event.code == KeyCode::Esc && self.keymap.is_pending() {
+                self.keymap.cancel();
+                return None;
+            }
+            match self.keymap.push(key_event.code) {
+                Chord::Pending => return None,
+                Chord::Matched(action) => return Some(action),
+                // hand the keys of a started sequence over one by one, e.g. `gg`.
+                Chord::Unmatched(mut keys) => {
+                    keys.pop();
+                    for key in keys {
+                        if let Some(action) = self.handle_mode_key_event(KeyEvent::from(key)) {
+                            self.update(action);
+                        }
+                    }
+                }
             }
         }
+        self.handle_mode_key_event(key_event)
+    }
 
+    fn handle_mode_key_event(&mut self, key_event: KeyEvent) -> Option<Action> {
+        // the keys to move around depend on the preset, only the tables have them.
+        let key_event = match self.keymap_preset.translate(key_event) {
+            _ if self.mode != AppMode::Normal || self.is_editing() => key_event,
+            Some(key_event) => key_event,
+            None if matches!(self.focus, AppTab::Report | AppTab::Stats) => key_event,
+            None => return None,
+        };
         match self.mode {
             AppMode::Insert => match key_event.code {
                 KeyCode::Esc => Some(Action::LeaveInsertMode),
-                KeyCode::Enter => Some(Action::LeaveInsertMode),
+                KeyCode::Enter => match Commands::parse(self.command_line.value()) {
+                    Ok(command) => {
+                        self.command_line.push_history();
+                        self.leave_insert_mode();
+                        Some(command.into())
+                    }
+                    // keep the input, so it can be corrected.
+                    Err(e) => {
+                        self.command_line.set_error(e.to_string());
+                        None
+                    }
+                },
                 _ => {
-                    self.input_field.handle_event(&Event::Key(key_event));
+                    self.command_line.handle_event(key_event);
                     None
                 }
             },
+            AppMode::Search => self.results_tab.handle_search_event(key_event),
+            AppMode::Normal if self.is_editing() => match self.focus {
+                AppTab::Scale => self.scale_tab.handle_event(key_event),
+                AppTab::Result => self.results_tab.handle_event(key_event),
+                AppTab::Report | AppTab::Stats => None,
+            },
+            AppMode::Normal if key_event.modifiers == KeyModifiers::CONTROL => {
+                match key_event.code {
+                    KeyCode::Char('z') => Some(Action::Undo),
+                    KeyCode::Char('t') => Some(Action::SwitchTheme(None)),
+                    KeyCode::Char('r') | KeyCode::Char('y') => Some(Action::Redo),
+                    _ => match self.focus {
+                        AppTab::Scale => self.scale_tab.handle_event(key_event),
+                        AppTab::Result => self.results_tab.handle_event(key_event),
+                        AppTab::Report | AppTab::Stats => None,
+                    },
+                }
+            }
+            AppMode::Normal if key_event.modifiers == KeyModifiers::ALT => match self.focus {
+                AppTab::Scale => self.scale_tab.handle_event(key_event),
+                _ => None,
+            },
             AppMode::Normal => match key_event.code {
-                KeyCode::F(1) | KeyCode::Char('1') => Some(Action::SwitchTab(AppTab::Scale)),
-                KeyCode::F(2) | KeyCode::Char('2') => Some(Action::SwitchTab(AppTab::Result)),
-                KeyCode::F(3) | KeyCode::Char('3') => Some(Action::SwitchTab(AppTab::Report)),
+                KeyCode::F(1) | KeyCode::Char('?') => Some(Action::ShowHelp),
+                KeyCode::F(12) => Some(Action::ShowLog),
+                KeyCode::F(n @ 2..=4) => self
+                    .tabs
+                    .get(n as usize - 1)
+                    .map(|&tab| Action::SwitchTab(tab)),
                 KeyCode::Char(':') => Some(Action::EnterInsertMode),
                 KeyCode::Char('I') => Some(Action::UpdateModel(ModelAction::SetScale(1))),
                 KeyCode::Char('T') => Some(Action::UpdateModel(ModelAction::SetScale(2))),
@@ -315,20 +1377,100 @@ impl App {
 
                 KeyCode::Char('.') => Some(Action::UpdateModel(ModelAction::ToggleHalfPoints)),
 
+                KeyCode::Char('u') => Some(Action::Undo),
+                KeyCode::Char('v') => Some(Action::ToggleSplitView),
+                KeyCode::Char('f') => Some(Action::ToggleFullWidth),
+                KeyCode::Tab => Some(Action::FocusNext),
+                KeyCode::BackTab => Some(Action::FocusPrevious),
                 KeyCode::Char('q') => Some(Action::Quit),
-                KeyCode::Char('e') => Some(Action::ExportTo(None)),
+                KeyCode::Char('e') => Some(Action::ExportDefault),
+                KeyCode::Char('E') => Some(Action::OpenExportModal),
+                KeyCode::Char('p') => Some(Action::OpenMaxPointsPopup),
+                KeyCode::Char('w') => Some(Action::ExportTo(None)),
+                KeyCode::Char('o') => Some(Action::OpenFilePicker),
+                KeyCode::Char(c) if self.scale_for_key(c).is_some() => self
+                    .scale_for_key(c)
+                    .map(|values| Action::UpdateModel(ModelAction::SetCustomScale(values))),
 
-                _ => match self.selected_tab {
+                _ => match self.focus {
                     AppTab::Scale => self.scale_tab.handle_event(key_event),
                     AppTab::Result => self.results_tab.handle_event(key_event),
-                    AppTab::Report => None,
+                    AppTab::Report => self.report_tab.handle_event(key_event),
+                    AppTab::Stats => None,
                 },
             },
             _ => None,
         }
     }
 
+    // the boundaries of the named scale of the config with this key.
+    fn scale_for_key(&self, key: char) -> Option<[(u8, f64); 6]> {
+        self.named_scales
+            .iter()
+            .find(|scale| scale.key == Some(key))
+            .map(|scale| scale.scale_type.values())
+    }
+
+    // the named scale of the config, which matches the current custom scale.
+    fn active_named_scale(&self) -> Option<&NamedScale> {
+        let scale_type = self.model.scale.scale_type();
+        match scale_type.is_custom() {
+            true => self
+                .named_scales
+                .iter()
+                .find(|scale| scale.scale_type == *scale_type),
+            false => None,
+        }
+    }
+
+    // the tabs which are shown side by side.
+    fn visible_panes(&self) -> Vec<AppTab> {
+        match self.selected_tab {
+            AppTab::Scale | AppTab::Result if self.split_view => {
+                vec![AppTab::Scale, AppTab::Result]
+            }
+            tab => vec![tab],
+        }
+    }
+
+    // move the focus by `step` panes and wrap around.
+    fn cycle_focus(&mut self, step: isize) {
+        let panes = self.visible_panes();
+        let index = panes
+            .iter()
+            .position(|pane| *pane == self.focus)
+            .unwrap_or(0);
+        let next = (index as isize + step).rem_euclid(panes.len() as isize);
+        self.focus = panes[next as usize];
+    }
+
+    // returns true if an inline editor of the focused tab is open.
+    fn is_editing(&self) -> bool {
+        match self.focus {
+            AppTab::Scale => self.scale_tab.is_editing(),
+            AppTab::Result => self.results_tab.is_editing(),
+            AppTab::Report | AppTab::Stats => false,
+        }
+    }
+
     fn exit(&mut self) {
         self.mode = AppMode::Exited;
     }
 }
+
+// count of students per grade, starting with the best grade.
+fn chart_data(distribution: HashMap<u8, usize>) -> [u8; 6] {
+    let mut data = [0u8; 6];
+    for (grade, count) in distribution {
+        if (1..=6).contains(&grade) {
+            data[(grade - 1) as usize] = count as u8;
+        }
+    }
+    data
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
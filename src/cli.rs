@@ -1,10 +1,27 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub use clap::Parser;
+use clap::Subcommand;
+
+use crate::export::ExportColumn;
+use crate::model::exam::{ExamDate, ExamInfo};
+use crate::model::scale::{parse_max_points, GradingError};
+use crate::ui::students_tab::SortOrder;
 
 #[derive(Parser, Debug)]
-#[command(version, about = "simple grade point calculator")]
+#[command(
+    version,
+    about = "simple grade point calculator",
+    args_conflicts_with_subcommands = true
+)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// config file to use instead of the one in the config directory.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
     #[arg(
         help = "Path to the courses student list to be opened.",
         required = false
@@ -13,14 +30,204 @@ pub struct Args {
 
     #[arg(
         long,
+        global = true,
         help = "Sets the course name. By default, the name is selected based on the courses file name."
     )]
     pub course_name: Option<String>,
 
-    /// max reachable points for the exam.
-    #[arg(short, long, default_value_t = 100)]
-    pub points: u32,
+    /// initial order of the result table: name, points or grade, with `:desc` for descending.
+    #[arg(long, value_name = "COLUMN[:desc]")]
+    pub sort: Option<SortOrder>,
+
+    /// name of the exam, shown in the header and stored in the exports.
+    #[arg(long, global = true)]
+    pub exam_name: Option<String>,
+
+    /// date of the exam like 2025-03-24 or 24.03.2025.
+    #[arg(long, global = true)]
+    pub exam_date: Option<ExamDate>,
+
+    /// max reachable points for the exam, half points like 47.5 are allowed.
+    /// With several exams one value per exam like `100,50,80`, a single value counts for all.
+    #[arg(short, long, global = true, default_value = "100", value_delimiter = ',', value_parser = points_arg)]
+    pub points: Vec<f64>,
+
+    /// course files of other exams, their grades are averaged with the opened course
+    /// in the results and the stats.
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub exams: Vec<PathBuf>,
+
+    /// weight of each exam in the average grade, e.g. `2,1,1`. All exams count once if not given.
+    #[arg(short, long, global = true, value_delimiter = ',')]
+    pub weights: Vec<f64>,
+
+    /// starts with half points enabled, like pressing `.` in the app.
+    #[arg(long, global = true)]
+    pub half_points: bool,
+
+    /// grading scale (IHK, TECHNIKER, LINEAR) or a path to a custom scale toml.
+    /// The default scale of the config is used if not given.
+    #[arg(short, long, global = true)]
+    pub scale: Option<String>,
+
+    /// disables all changes, e.g. to show the results to students.
+    #[arg(long, global = true)]
+    pub read_only: bool,
+
+    /// log level, overrides the GRACA_LOGLEVEL environment variable.
+    #[arg(long, global = true, value_parser = ["off", "error", "warn", "info", "debug", "trace"])]
+    pub log_level: Option<String>,
+
+    /// file to write the log to instead of graca.log in the data directory.
+    #[arg(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// columns of the exports like grade,label,pct or percentile,notes, instead of export_columns of the config.
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub columns: Option<Vec<ExportColumn>>,
+
+    /// prints the output of scale, grade and stats as json.
+    #[arg(long, global = true)]
+    pub json: bool,
+}
+
+impl Args {
+    pub fn exam(&self) -> ExamInfo {
+        ExamInfo {
+            name: self.exam_name.clone(),
+            date: self.exam_date,
+        }
+    }
+
+    // the max points of the first exam, which is the opened course.
+    pub fn max_points(&self) -> f64 {
+        self.points_of(0)
+    }
+
+    // the max points of the exam at the index, a single value counts for all exams.
+    pub fn points_of(&self, index: usize) -> f64 {
+        match self.points.as_slice() {
+            [points] => *points,
+            points => points.get(index).copied().unwrap_or(100.0),
+        }
+    }
+
+    // all course and roster files of the arguments, e.g. to look them up in the course dir.
+    pub fn course_files_mut(&mut self) -> Vec<&mut PathBuf> {
+        let inputs: Vec<&mut PathBuf> = match &mut self.command {
+            Some(Command::Grade { input, .. }) => input.iter_mut().collect(),
+            Some(
+                Command::Stats { input }
+                | Command::Watch { input }
+                | Command::Convert { input, .. },
+            ) => vec![input],
+            Some(Command::Merge { inputs, .. }) => inputs.iter_mut().collect(),
+            Some(Command::New { from, .. }) => from.iter_mut().collect(),
+            _ => Vec::new(),
+        };
+        self.course
+            .iter_mut()
+            .chain(inputs)
+            .chain(self.exams.iter_mut())
+            .collect()
+    }
+
+    // the course file given as argument or to a command, its directory can hold a `.graca.toml`.
+    pub fn course_file(&self) -> Option<&Path> {
+        match &self.command {
+            Some(Command::Grade { input, .. }) => input.as_deref(),
+            Some(
+                Command::Stats { input }
+                | Command::Watch { input }
+                | Command::Convert { input, .. },
+            ) => Some(input),
+            _ => self.course.as_deref(),
+        }
+    }
+}
+
+fn points_arg(value: &str) -> Result<f64, String> {
+    parse_max_points(value).map_err(|e| match e {
+        GradingError::NotANumber(_) => e.to_string(),
+        _ => "expected a positive number of (half) points".to_string(),
+    })
+}
 
-    #[arg(short, long, default_value_t = String::from("IHK"))]
-    pub scale: String,
+/// Commands which run without the terminal interface.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Prints the grading scale for the given points and scale and exits.
+    Scale,
+    /// Creates the student list of a new course in the course directory.
+    New {
+        /// name of the course, which is used as file name.
+        class: String,
+        /// number of placeholder students.
+        #[arg(long, conflicts_with = "from")]
+        students: Option<usize>,
+        /// text file with one student name per line.
+        #[arg(long)]
+        from: Option<PathBuf>,
+        /// opens the new course afterwards.
+        #[arg(long)]
+        open: bool,
+    },
+    /// Adds percentage and grade to a csv file with name and points of each student.
+    Grade {
+        /// csv file with the columns name and points.
+        #[arg(required_unless_present = "stdin", conflicts_with = "stdin")]
+        input: Option<PathBuf>,
+        /// reads one `points` or `name;points` per line from stdin and prints the graded lines.
+        #[arg(long)]
+        stdin: bool,
+        /// file for the graded list, printed to stdout if not given.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Prints averages, median, mode, spread, pass rate and grade distribution of a course file.
+    Stats {
+        /// csv file with the columns name and points.
+        input: PathBuf,
+    },
+    /// Opens a course file and reloads it whenever it changes, e.g. while the
+    /// points are entered in a spreadsheet.
+    Watch {
+        /// csv file with the columns name and points.
+        input: PathBuf,
+    },
+    /// Converts a course between the graca csv file, a roster with one name
+    /// per line (txt) and a sheet with the graded results (xlsx).
+    Convert {
+        /// the course file (csv), roster (txt) or sheet with a name and a points column (xlsx)
+        /// to convert.
+        input: PathBuf,
+        /// the converted file, its format is taken from the extension.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Merges the results of several exams of a course into one gradebook csv with the
+    /// weighted average grade of each student, students are matched by name.
+    Merge {
+        /// csv files with the columns name and points, one per exam.
+        #[arg(required = true, num_args = 2..)]
+        inputs: Vec<PathBuf>,
+        /// file for the gradebook, printed to stdout if not given.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// prints the grade trend of the class and each student over the exams instead,
+        /// in the order of the files.
+        #[arg(short, long)]
+        trend: bool,
+    },
+    /// Checks the config file and prints the configuration which is used.
+    CheckConfig,
+    /// Writes a commented config file with all keys and their defaults.
+    InitConfig {
+        /// replaces an existing config file.
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Prints the man page in roff format, e.g. for packaging.
+    #[command(hide = true)]
+    Man,
 }
@@ -0,0 +1,292 @@
+use core::fmt;
+use std::{fs, path::PathBuf};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    action::{Action, ModelAction},
+    config::available_themes,
+    export::resolve_path,
+    model::scale::parse_max_points,
+    ui::TableColumn,
+};
+
+/// Names of all known commands, used for completion.
+pub const COMMAND_NAMES: [&str; 12] = [
+    "curve",
+    "export-results",
+    "export-to",
+    "help",
+    "open",
+    "quit",
+    "recent",
+    "rename-course",
+    "save",
+    "set-points",
+    "theme",
+    "toggle-column",
+];
+
+/// Commands which can be entered in the command line after pressing `:`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Commands {
+    Quit,
+    Help,
+    Save,
+    Open(PathBuf),
+    Recent(Option<usize>), // number of the file in the list, shows the list if not given
+    ExportTo(PathBuf),
+    ExportResults(PathBuf),
+    SetPoints(f64),
+    Theme(Option<String>),
+    ToggleColumn(TableColumn),
+    RenameCourse(String),
+    Curve(Option<f64>), // target average grade
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    Empty,
+    UnknownCommand(String),
+    MissingArgument(&'static str),
+    InvalidArgument(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::Empty => write!(f, "No command given."),
+            CommandError::UnknownCommand(name) => write!(f, "Unknown command '{name}'."),
+            CommandError::MissingArgument(arg) => write!(f, "Missing argument <{arg}>."),
+            CommandError::InvalidArgument(arg) => write!(f, "Invalid argument '{arg}'."),
+        }
+    }
+}
+
+impl Commands {
+    pub fn parse(input: &str) -> Result<Commands, CommandError> {
+        let input = input.trim();
+        let (name, arg) = match input.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, Some(arg.trim())),
+            None => (input, None),
+        };
+
+        match name {
+            "" => Err(CommandError::Empty),
+            "q" | "quit" => Ok(Commands::Quit),
+            "help" => Ok(Commands::Help),
+            "w" | "save" => Ok(Commands::Save),
+            "open" => Ok(Commands::Open(Commands::relative_path_arg(arg)?)),
+            "recent" => match arg {
+                None => Ok(Commands::Recent(None)),
+                Some(arg) => match arg.parse::<usize>() {
+                    Ok(number) if number > 0 => Ok(Commands::Recent(Some(number))),
+                    _ => Err(CommandError::InvalidArgument(arg.to_string())),
+                },
+            },
+            "export-to" => Ok(Commands::ExportTo(Commands::relative_path_arg(arg)?)),
+            "export-results" => Ok(Commands::ExportResults(Commands::relative_path_arg(arg)?)),
+            "set-points" => {
+                let arg = arg.ok_or(CommandError::MissingArgument("points"))?;
+                parse_max_points(arg)
+                    .map(Commands::SetPoints)
+                    .map_err(|_| CommandError::InvalidArgument(arg.to_string()))
+            }
+            "theme" => Ok(Commands::Theme(arg.map(|name| name.to_string()))),
+            "toggle-column" => {
+                let arg = arg.ok_or(CommandError::MissingArgument("column"))?;
+                arg.parse::<TableColumn>()
+                    .map(Commands::ToggleColumn)
+                    .map_err(|_| CommandError::InvalidArgument(arg.to_string()))
+            }
+            "rename-course" => {
+                let arg = arg.ok_or(CommandError::MissingArgument("name"))?;
+                Ok(Commands::RenameCourse(arg.to_string()))
+            }
+            "curve" => match arg {
+                None => Ok(Commands::Curve(None)),
+                Some(arg) => match arg.replace(',', ".").parse::<f64>() {
+                    Ok(target) if (1.0..=6.0).contains(&target) => {
+                        Ok(Commands::Curve(Some(target)))
+                    }
+                    _ => Err(CommandError::InvalidArgument(arg.to_string())),
+                },
+            },
+            _ => Err(CommandError::UnknownCommand(name.to_string())),
+        }
+    }
+
+    fn path_arg(arg: Option<&str>) -> Result<PathBuf, CommandError> {
+        let arg = arg.ok_or(CommandError::MissingArgument("path"))?;
+        resolve_path(arg).ok_or_else(|| CommandError::InvalidArgument(arg.to_string()))
+    }
+
+    // relative paths are kept, the app resolves them in the course or export directory.
+    fn relative_path_arg(arg: Option<&str>) -> Result<PathBuf, CommandError> {
+        let arg = arg.ok_or(CommandError::MissingArgument("path"))?;
+        match arg.starts_with('~') {
+            true => Commands::path_arg(Some(arg)),
+            false => Ok(PathBuf::from(arg)),
+        }
+    }
+}
+
+impl From<Commands> for Action {
+    fn from(command: Commands) -> Self {
+        match command {
+            Commands::Quit => Action::Quit,
+            Commands::Help => Action::ShowHelp,
+            Commands::Save => Action::ExportTo(None),
+            Commands::Open(path) => Action::LoadStudentList(path),
+            Commands::Recent(number) => Action::OpenRecent(number),
+            Commands::ExportTo(path) => Action::ExportTo(Some(path)),
+            Commands::ExportResults(path) => Action::ExportResults(Some(path)),
+            Commands::SetPoints(points) => Action::UpdateModel(ModelAction::SetMaxPoints(points)),
+            Commands::Theme(name) => Action::SwitchTheme(name),
+            Commands::ToggleColumn(column) => Action::ToggleColumn(column),
+            Commands::RenameCourse(name) => Action::UpdateModel(ModelAction::RenameCourse(name)),
+            Commands::Curve(target) => Action::OpenCurvePreview(target),
+        }
+    }
+}
+
+/// Returns all possible completions for the given command line input.
+/// Every completion is the full new input value.
+pub fn complete(input: &str) -> Vec<String> {
+    match input.split_once(' ') {
+        None => COMMAND_NAMES
+            .iter()
+            .filter(|name| name.starts_with(input))
+            .map(|name| name.to_string())
+            .collect(),
+        Some((name @ ("open" | "export-to" | "export-results"), arg)) => complete_path(arg)
+            .into_iter()
+            .map(|path| format!("{name} {path}"))
+            .collect(),
+        Some(("theme", arg)) => available_themes()
+            .into_iter()
+            .filter(|name| name.starts_with(arg))
+            .map(|name| format!("theme {name}"))
+            .collect(),
+        Some(("toggle-column", arg)) => TableColumn::iter()
+            .map(|column| column.to_string())
+            .filter(|column| column.starts_with(arg))
+            .map(|column| format!("toggle-column {column}"))
+            .collect(),
+        Some(_) => Vec::new(),
+    }
+}
+
+// list the directory entries matching the last component of the given path.
+fn complete_path(partial: &str) -> Vec<String> {
+    let (dir, prefix) = match partial.rfind('/') {
+        Some(idx) => partial.split_at(idx + 1),
+        None => ("", partial),
+    };
+
+    let Some(search_dir) = resolve_path(if dir.is_empty() { "." } else { dir }) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(search_dir) else {
+        return Vec::new();
+    };
+
+    let mut completions: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            if !name.starts_with(prefix) || (prefix.is_empty() && name.starts_with('.')) {
+                return None;
+            }
+            let suffix = if entry.path().is_dir() { "/" } else { "" };
+            Some(format!("{dir}{name}{suffix}"))
+        })
+        .collect();
+    completions.sort();
+    completions
+}
+
+/// Returns the longest common prefix of all given values.
+pub fn common_prefix(values: &[String]) -> Option<String> {
+    let first = values.first()?;
+    let mut prefix = first.as_str();
+    for value in values.iter().skip(1) {
+        while !value.starts_with(prefix) {
+            prefix = &prefix[..prefix.char_indices().last()?.0];
+        }
+    }
+    Some(prefix.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Commands::parse("quit"), Ok(Commands::Quit));
+        assert_eq!(
+            Commands::parse(" set-points 40 "),
+            Ok(Commands::SetPoints(40.0))
+        );
+        assert_eq!(
+            Commands::parse("set-points 47.5"),
+            Ok(Commands::SetPoints(47.5))
+        );
+        assert_eq!(
+            Commands::parse("set-points"),
+            Err(CommandError::MissingArgument("points"))
+        );
+        assert_eq!(
+            Commands::parse("set-points abc"),
+            Err(CommandError::InvalidArgument("abc".into()))
+        );
+        assert_eq!(
+            Commands::parse("foo"),
+            Err(CommandError::UnknownCommand("foo".into()))
+        );
+        assert_eq!(
+            Commands::parse("theme light"),
+            Ok(Commands::Theme(Some("light".into())))
+        );
+        assert_eq!(
+            Commands::parse("rename-course IT 23 b"),
+            Ok(Commands::RenameCourse("IT 23 b".into()))
+        );
+        assert_eq!(Commands::parse("recent 2"), Ok(Commands::Recent(Some(2))));
+        assert_eq!(Commands::parse("curve 2,5"), Ok(Commands::Curve(Some(2.5))));
+        assert_eq!(
+            Commands::parse("curve 7"),
+            Err(CommandError::InvalidArgument("7".into()))
+        );
+        assert_eq!(
+            Commands::parse("recent 0"),
+            Err(CommandError::InvalidArgument("0".into()))
+        );
+        assert!(matches!(
+            Commands::parse("export-to /tmp/scale.csv"),
+            Ok(Commands::ExportTo(_))
+        ));
+        assert_eq!(
+            Commands::parse("export-results 10a.xlsx"),
+            Ok(Commands::ExportResults("10a.xlsx".into()))
+        );
+    }
+
+    #[test]
+    fn test_complete_command_names() {
+        assert_eq!(complete("se"), vec!["set-points".to_string()]);
+        assert_eq!(
+            complete("s"),
+            vec!["save".to_string(), "set-points".to_string()]
+        );
+        assert!(complete("set-points 4").is_empty());
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        let values = vec!["save".to_string(), "set-points".to_string()];
+        assert_eq!(common_prefix(&values), Some("s".to_string()));
+        assert_eq!(common_prefix(&[]), None);
+    }
+}
@@ -1,12 +1,26 @@
-use std::{fs, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 use color_eyre::eyre;
 use directories::{ProjectDirs, UserDirs};
 use lazy_static::lazy_static;
+use ratatui::style::Color;
 use serde::Deserialize;
+use strum::IntoEnumIterator;
 use tracing::warn;
 
-use crate::model::scale::GradeScaleType;
+use crate::export::{resolve_path, ExportColumn, EXPORT_FORMATS};
+use crate::keymap::KeymapPreset;
+use crate::locale::{Locale, Precision};
+use crate::model::scale::{round_dp, Grade, GradeFormat, GradeScaleType, PASS_GRADE};
+use crate::model::FAIL_LIMIT;
+use crate::ui::report_tab::DEFAULT_BIN_WIDTH;
+use crate::ui::theme::{Palette, ThemeVariant};
+use crate::ui::{AppTab, TableColumn};
 
 lazy_static! {
     pub static ref PROJECT_NAME: String = env!("CARGO_CRATE_NAME").to_uppercase().to_string();
@@ -22,47 +36,898 @@ lazy_static! {
     pub static ref LOG_FILE: String = format!("{}.log", env!("CARGO_PKG_NAME"));
 }
 
+/// Config file given on the command line, which replaces the one in the config dir.
+static CONFIG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn set_config_file(path: PathBuf) {
+    let _ = CONFIG_FILE.set(path);
+}
+
+/// Name of the config in the working directory or next to a course file,
+/// whose keys override the ones of the user config.
+pub const LOCAL_CONFIG_FILE: &str = ".graca.toml";
+
+/// Directory of the course file given on the command line.
+static COURSE_FILE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn set_course_file(path: &Path) {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let _ = COURSE_FILE_DIR.set(dir);
+}
+
+// the existing local configs of the working directory and the course file's directory,
+// the last one has the highest priority.
+pub fn local_config_files() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in std::env::current_dir()
+        .ok()
+        .iter()
+        .chain(COURSE_FILE_DIR.get())
+    {
+        if let Ok(dir) = dir.canonicalize() {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs.into_iter()
+        .map(|dir| dir.join(LOCAL_CONFIG_FILE))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Keys of config.toml, keep in sync with `AppConfig`. Used to report unknown keys.
+const CONFIG_KEYS: [&str; 23] = [
+    "export_path",
+    "default_export_format",
+    "default_scale",
+    "export_columns",
+    "theme",
+    "hidden_columns",
+    "histogram_bin_width",
+    "pass_quota_warning",
+    "pass_quota_critical",
+    "target_average",
+    "tabs",
+    "default_tab",
+    "course_tab",
+    "full_width",
+    "keymap",
+    "course_dir",
+    "scales",
+    "locale",
+    "grade_format",
+    "precision",
+    "pass_grade",
+    "fail_limit",
+    "scale_colors",
+];
+
+/// Keys whose `GRACA_*` values are toml like `true`, `3` or `["label"]`,
+/// the values of all other keys are strings.
+const TOML_KEYS: [&str; 13] = [
+    "export_columns",
+    "hidden_columns",
+    "histogram_bin_width",
+    "pass_quota_warning",
+    "pass_quota_critical",
+    "target_average",
+    "tabs",
+    "full_width",
+    "scales",
+    "precision",
+    "pass_grade",
+    "fail_limit",
+    "scale_colors",
+];
+
+/// Commented config.toml with all keys and their defaults, written by `graca init-config`.
+/// Every key is commented out, so the file behaves like no config at all.
+pub const DEFAULT_CONFIG: &str = r#"# Configuration of graca, remove the `#` in front of a key to change its value.
+# `graca check-config` shows problems and the values which are used.
+# A `.graca.toml` in the working directory or next to a course file overrides
+# the keys of this file, e.g. to pin the scale of a course.
+# Environment variables like GRACA_DEFAULT_SCALE=LINEAR or GRACA_THEME=light override both,
+# the command line arguments override everything.
+
+# Directory of the exports, relative paths of `:export-to` and `:export-results` are put there.
+# The documents directory is used if not set.
+# export_path = "~/Documents/grades"
+
+# Format `e` exports to without asking, one of "csv", "toml" or "xlsx".
+# `E` always asks for the format.
+# default_export_format = "xlsx"
+
+# Scale used on start: "IHK", "TECHNIKER", "LINEAR" or the name of a scale in [scales].
+# default_scale = "IHK"
+
+# Columns of the scale exports: grade, label, min, max and pct.
+# "percentile" and "notes" add the percentile rank and the notes of the students
+# to the result sheet.
+# Overridden by `--columns` on the command line.
+# export_columns = ["grade", "min", "max", "pct"]
+
+# "dark", "light" or the name of a theme file in the themes directory next to this file.
+# theme = "dark"
+
+# Table columns which are not shown: grade, label, min, max, pct, name, points, percentage,
+# percentile, the rank of the student in the class, and average, the weighted grade of all
+# exams given with --exams.
+# hidden_columns = ["label", "percentile"]
+
+# Width of the bins of the points histogram on the report tab.
+# histogram_bin_width = 5.0
+
+# The pass gauge is yellow below the warning and red below the critical pass quota.
+# pass_quota_warning = 0.8
+# pass_quota_critical = 0.6
+
+# Expected average grade, marked in the grade chart and the default target of `:curve`.
+# target_average = 3.0
+
+# Shown tabs and their order, from "scale", "result", "report" and "stats".
+# tabs = ["scale", "result", "report", "stats"]
+
+# Tab shown on start, the first of tabs if not set.
+# default_tab = "scale"
+
+# Tab shown if a course is opened on the command line, the default_tab if not set.
+# course_tab = "result"
+
+# Use the whole terminal width for tables and charts.
+# full_width = false
+
+# Keys to move around in the tabs: "default" for the arrows, Home, End, j/k, gg and G,
+# "vim" for h and l in addition, "emacs" for Ctrl+n/p/f/b, Alt+< and Alt+> in addition.
+# keymap = "default"
+
+# Directory in which `graca new` creates the course files and the file picker starts.
+# Relative course files which are not in the working directory are looked up there.
+# The working directory is used if not set.
+# course_dir = "~/Documents/courses"
+
+# Format of dates and decimal numbers in the header, the tables and the exports:
+# "en" for 1.5 and 2025-03-24, "de" for 1,5 and 24.03.2025. The numbers of the csv and
+# toml exports always use a `.`, the columns a `,`.
+# locale = "en"
+
+# Grades in the tables and exports: "numeric" (2), "verbal" (gut) or "combined" (2 (gut)).
+# The verbal grades are in the language of the locale.
+# grade_format = "numeric"
+
+# Maximum number of decimals shown for the percentages (of the percent value, 0 shows 92%),
+# the points and the average grade in the tables, the header and the exports.
+# precision = { percentage = 0, points = 2, average = 2 }
+
+# Worst grade which still counts as passed, used by the failing students in the result
+# table, the pass rate, the pass gauge and the exports.
+# pass_grade = 4
+
+# Share of failed students above which the report tab and the exports show a warning,
+# as many regulations require a justification if more than a third of the students fail.
+# fail_limit = 0.333
+
+# Colors of the scales in the header and the scale tab, replacing the ones of the theme.
+# [scale_colors]
+# ihk = "yellow"
+# techniker = "blue"
+# linear = "green"
+# custom = "lightred"
+
+# Custom scales with the minimum share of the points for each grade.
+# They can be selected with their key or as default_scale.
+# [scales.school]
+# boundaries = { 1 = 0.92, 2 = 0.81, 3 = 0.67, 4 = 0.5, 5 = 0.3, 6 = 0.0 }
+# color = "magenta"
+# key = "S"
+"#;
+
+/// A problem in the config file found by `graca check-config`.
+#[derive(Debug, PartialEq)]
+pub struct ConfigIssue {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// The `default_scale`, either a builtin scale or the name of one in `[scales]`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum DefaultScale {
+    Builtin(GradeScaleType),
+    Named(String),
+}
+
+impl Default for DefaultScale {
+    fn default() -> Self {
+        DefaultScale::Builtin(GradeScaleType::default())
+    }
+}
+
+/// A custom scale in the `[scales]` table, e.g.
+/// `[scales.school]` with `boundaries = { 1 = 0.9, 2 = 0.8, ... }`, `color = "magenta"` and `key = "S"`.
+#[derive(Debug, Clone, Deserialize)]
+struct ScaleDefinition {
+    boundaries: BTreeMap<String, f64>, // grade -> minimum share of the points
+    color: Option<Color>,
+    key: Option<char>,
+}
+
+/// The `[scale_colors]` table, which replaces the scale colors of the theme.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ScaleColorOverrides {
+    ihk: Option<Color>,
+    techniker: Option<Color>,
+    linear: Option<Color>,
+    custom: Option<Color>,
+}
+
+/// A valid scale of the `[scales]` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedScale {
+    pub name: String,
+    pub scale_type: GradeScaleType,
+    pub color: Option<Color>,
+    pub key: Option<char>, // switches to the scale like `I` or `T`
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct AppConfig {
     export_path: Option<PathBuf>,
-    default_scale: GradeScaleType,
+    default_export_format: Option<String>, // exports with `e` without asking
+    #[serde(default)]
+    default_scale: DefaultScale,
+    export_columns: Option<Vec<ExportColumn>>,
+    theme: Option<String>,
+    hidden_columns: Option<Vec<TableColumn>>,
+    histogram_bin_width: Option<f64>,
+    pass_quota_warning: Option<f64>,
+    pass_quota_critical: Option<f64>,
+    target_average: Option<f64>,
+    tabs: Option<Vec<AppTab>>,
+    default_tab: Option<AppTab>,
+    course_tab: Option<AppTab>, // replaces the default tab if a course is opened on start
+    full_width: Option<bool>,
+    keymap: Option<KeymapPreset>,
+    course_dir: Option<PathBuf>, // where new course files are created
+    scales: Option<BTreeMap<String, ScaleDefinition>>,
+    locale: Option<Locale>,
+    grade_format: Option<GradeFormat>,
+    precision: Option<Precision>,
+    pass_grade: Option<u8>, // worst grade which still counts as passed
+    fail_limit: Option<f64>,
+    scale_colors: Option<ScaleColorOverrides>,
 }
 
 impl AppConfig {
     pub fn new() -> AppConfig {
         Self {
-            export_path: if let Ok(path) = get_document_dir() {
-                Some(path)
-            } else {
-                None
-            },
-            default_scale: GradeScaleType::IHK,
+            export_path: None,
+            default_export_format: None,
+            default_scale: DefaultScale::default(),
+            export_columns: None,
+            theme: None,
+            hidden_columns: None,
+            histogram_bin_width: None,
+            pass_quota_warning: None,
+            pass_quota_critical: None,
+            target_average: None,
+            tabs: None,
+            default_tab: None,
+            course_tab: None,
+            full_width: None,
+            keymap: None,
+            course_dir: None,
+            scales: None,
+            locale: None,
+            grade_format: None,
+            precision: None,
+            pass_grade: None,
+            fail_limit: None,
+            scale_colors: None,
         }
     }
 
+    // the user config with the keys of the local `.graca.toml` files and the environment on top.
     pub fn read_config() -> eyre::Result<AppConfig> {
-        let config_path = if let Ok(config_dir) = get_config_dir() {
-            config_dir.join("config.toml")
-        } else {
-            return Err(eyre::eyre!("Unable to find config dir."));
+        let mut table = match CONFIG_FILE.get() {
+            Some(path) => Some(read_table(path)?),
+            None => {
+                let config_path = match get_config_file() {
+                    Ok(path) => path,
+                    Err(_) => return Err(eyre::eyre!("Unable to find config dir.")),
+                };
+                match config_path.exists() {
+                    true => Some(read_table(&config_path)?),
+                    false => None,
+                }
+            }
+        };
+        for path in local_config_files() {
+            merge_tables(table.get_or_insert_default(), read_table(&path)?);
+        }
+        let overrides = env_overrides(|name| std::env::var(name).ok())?;
+        if !overrides.is_empty() {
+            merge_tables(table.get_or_insert_default(), overrides);
+        }
+
+        match table {
+            Some(table) => Ok(toml::Value::Table(table).try_into()?),
+            None => Ok(AppConfig::new()),
+        }
+    }
+
+    // parses the config and collects all problems instead of stopping at the first one.
+    pub fn check(content: &str) -> (Option<AppConfig>, Vec<ConfigIssue>) {
+        let mut issues = Vec::new();
+        let table: toml::Table = match toml::from_str(content) {
+            Ok(table) => table,
+            Err(e) => {
+                issues.push(ConfigIssue::from_toml(content, &e));
+                return (None, issues);
+            }
         };
+        for key in table
+            .keys()
+            .filter(|key| !CONFIG_KEYS.contains(&key.as_str()))
+        {
+            issues.push(ConfigIssue {
+                line: line_of_key(content, key),
+                message: format!("unknown key '{key}'"),
+            });
+        }
 
-        if !config_path.exists() {
-            return Ok(AppConfig::new());
+        let config: AppConfig = match toml::from_str(content) {
+            Ok(config) => config,
+            Err(e) => {
+                issues.push(ConfigIssue::from_toml(content, &e));
+                return (None, issues);
+            }
+        };
+        for (name, definition) in config.scales.iter().flatten() {
+            if let Err(e) = GradeScaleType::from_shares(&definition.boundaries) {
+                issues.push(ConfigIssue {
+                    line: line_of_table(content, &format!("scales.{name}")),
+                    message: format!("invalid scale '{name}': {e}"),
+                });
+            }
+        }
+        if let DefaultScale::Named(name) = &config.default_scale {
+            if config.named_scale(name).is_none() {
+                issues.push(ConfigIssue {
+                    line: line_of_key(content, "default_scale"),
+                    message: format!("unknown scale '{name}'"),
+                });
+            }
+        }
+        if let DefaultScale::Builtin(GradeScaleType::Custom(values)) = config.default_scale {
+            let decreasing = values.windows(2).all(|pair| pair[0].1 > pair[1].1);
+            let grades = values.iter().map(|(grade, _)| *grade).eq(1..=6);
+            if !decreasing || !grades {
+                issues.push(ConfigIssue {
+                    line: line_of_key(content, "default_scale"),
+                    message: "the custom scale needs the grades 1 to 6 with decreasing shares"
+                        .to_string(),
+                });
+            }
+        }
+        for (key, path) in [
+            ("export_path", &config.export_path),
+            ("course_dir", &config.course_dir),
+        ] {
+            let Some(path) = path else { continue };
+            if !resolve_path(&path.to_string_lossy()).is_some_and(|path| path.is_dir()) {
+                issues.push(ConfigIssue {
+                    line: line_of_key(content, key),
+                    message: format!("'{}' is no directory", path.display()),
+                });
+            }
+        }
+        if let Some(format) = &config.default_export_format {
+            if !EXPORT_FORMATS.contains(&format.as_str()) {
+                issues.push(ConfigIssue {
+                    line: line_of_key(content, "default_export_format"),
+                    message: format!(
+                        "unknown export format '{format}', expected one of {}",
+                        EXPORT_FORMATS.join(", ")
+                    ),
+                });
+            }
+        }
+        for (key, tab) in [
+            ("default_tab", config.default_tab),
+            ("course_tab", config.course_tab),
+        ] {
+            if let Some(tab) = tab.filter(|tab| !config.get_tabs().contains(tab)) {
+                issues.push(ConfigIssue {
+                    line: line_of_key(content, key),
+                    message: format!("'{}' is not in tabs", format!("{tab:?}").to_lowercase()),
+                });
+            }
         }
+        if let Some(grade) = config
+            .pass_grade
+            .filter(|grade| Grade::try_from(*grade).is_err())
+        {
+            issues.push(ConfigIssue {
+                line: line_of_key(content, "pass_grade"),
+                message: format!("the pass grade {grade} is no grade from 1 to 6"),
+            });
+        }
+        if let Some(limit) = config
+            .fail_limit
+            .filter(|limit| !(0.0..=1.0).contains(limit))
+        {
+            issues.push(ConfigIssue {
+                line: line_of_key(content, "fail_limit"),
+                message: format!("the fail limit {limit} is no share from 0 to 1"),
+            });
+        }
+        if let Some(theme) = &config.theme {
+            if !available_themes().contains(theme) {
+                issues.push(ConfigIssue {
+                    line: line_of_key(content, "theme"),
+                    message: format!("unknown theme '{theme}'"),
+                });
+            }
+        }
+        (Some(config), issues)
+    }
+
+    // the values which are used, including the defaults for keys which are not set.
+    pub fn effective(&self) -> Vec<(&'static str, String)> {
+        let list = |values: Vec<String>| format!("[{}]", values.join(", "));
+        let optional = |value: Option<String>| value.unwrap_or("-".to_string());
+        let (warning, critical) = self.get_pass_quota_thresholds();
+        vec![
+            (
+                "export_path",
+                optional(self.export_path.as_ref().map(|p| p.display().to_string())),
+            ),
+            (
+                "default_export_format",
+                optional(self.get_default_export_format().map(|f| f.to_string())),
+            ),
+            (
+                "default_scale",
+                match &self.default_scale {
+                    DefaultScale::Builtin(scale_type) => scale_type.text().to_string(),
+                    DefaultScale::Named(name) => name.clone(),
+                },
+            ),
+            (
+                "export_columns",
+                list(
+                    self.get_export_columns()
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect(),
+                ),
+            ),
+            ("theme", self.get_theme()),
+            (
+                "hidden_columns",
+                list(
+                    self.get_hidden_columns()
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect(),
+                ),
+            ),
+            (
+                "histogram_bin_width",
+                self.get_histogram_bin_width().to_string(),
+            ),
+            ("pass_quota_warning", warning.to_string()),
+            ("pass_quota_critical", critical.to_string()),
+            (
+                "target_average",
+                optional(self.target_average.map(|avg| avg.to_string())),
+            ),
+            (
+                "tabs",
+                list(
+                    self.get_tabs()
+                        .iter()
+                        .map(|tab| format!("{tab:?}").to_lowercase())
+                        .collect(),
+                ),
+            ),
+            (
+                "default_tab",
+                format!("{:?}", self.get_start_tab(false)).to_lowercase(),
+            ),
+            (
+                "course_tab",
+                format!("{:?}", self.get_start_tab(true)).to_lowercase(),
+            ),
+            ("full_width", self.get_full_width().to_string()),
+            ("keymap", self.get_keymap().to_string()),
+            ("course_dir", self.get_course_dir().display().to_string()),
+            (
+                "scales",
+                list(
+                    self.get_scales()
+                        .into_iter()
+                        .map(|scale| scale.name)
+                        .collect(),
+                ),
+            ),
+            ("locale", self.get_locale().to_string()),
+            ("grade_format", self.get_grade_format().to_string()),
+            ("precision", {
+                let precision = self.get_precision();
+                format!(
+                    "percentage {}, points {}, average {}",
+                    precision.percentage, precision.points, precision.average
+                )
+            }),
+            ("pass_grade", self.get_pass_grade().to_string()),
+            ("fail_limit", round_dp(self.get_fail_limit(), 3).to_string()),
+        ]
+    }
 
-        let content = fs::read_to_string(config_path)?;
-        let config: AppConfig = toml::from_str(&content)?;
-        Ok(config)
+    // the directory of the course files, the working directory if not configured.
+    pub fn get_course_dir(&self) -> PathBuf {
+        self.course_dir
+            .as_ref()
+            .and_then(|dir| resolve_path(&dir.to_string_lossy()))
+            .unwrap_or(PathBuf::from("."))
     }
 
-    pub fn get_export_path(&self) -> &Option<PathBuf> {
-        &self.export_path
+    // a relative course file which is not in the working directory is looked up in the
+    // course directory. The returned path is absolute.
+    pub fn find_course_file(&self, path: &Path) -> PathBuf {
+        let in_course_dir = self.get_course_dir().join(path);
+        let path = match path.is_relative() && !path.exists() && in_course_dir.exists() {
+            true => in_course_dir,
+            false => path.to_path_buf(),
+        };
+        std::path::absolute(&path).unwrap_or(path)
+    }
+
+    // the directory of the exports, the documents directory if not configured.
+    pub fn get_export_dir(&self) -> PathBuf {
+        self.export_path
+            .as_ref()
+            .and_then(|dir| resolve_path(&dir.to_string_lossy()))
+            .or_else(|| get_document_dir().ok())
+            .unwrap_or(PathBuf::from("."))
+    }
+
+    // the format `e` exports to without opening the export modal, if it is supported.
+    pub fn get_default_export_format(&self) -> Option<&str> {
+        let format = self.default_export_format.as_deref()?;
+        match EXPORT_FORMATS.contains(&format) {
+            true => Some(format),
+            false => {
+                warn!("Unknown default export format '{format}'");
+                None
+            }
+        }
     }
 
+    // a named default scale which is not defined falls back to the builtin default.
     pub fn get_default_scale(&self) -> GradeScaleType {
-        self.default_scale.clone()
+        match &self.default_scale {
+            DefaultScale::Builtin(scale_type) => *scale_type,
+            DefaultScale::Named(name) => match self.named_scale(name) {
+                Some(scale) => scale.scale_type,
+                None => {
+                    warn!("Unknown default scale '{name}'");
+                    GradeScaleType::default()
+                }
+            },
+        }
+    }
+
+    // return the valid scales of the `[scales]` table, ordered by name.
+    pub fn get_scales(&self) -> Vec<NamedScale> {
+        self.scales
+            .iter()
+            .flatten()
+            .filter_map(|(name, definition)| {
+                match GradeScaleType::from_shares(&definition.boundaries) {
+                    Ok(scale_type) => Some(NamedScale {
+                        name: name.clone(),
+                        scale_type,
+                        color: definition.color,
+                        key: definition.key,
+                    }),
+                    Err(e) => {
+                        warn!("Invalid scale '{name}': {e}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    // the name of a scale of the `[scales]` table with these boundaries, otherwise the builtin name.
+    pub fn scale_name(&self, scale_type: &GradeScaleType) -> String {
+        match self
+            .get_scales()
+            .into_iter()
+            .find(|scale| scale_type.is_custom() && scale.scale_type == *scale_type)
+        {
+            Some(scale) => scale.name,
+            None => scale_type.text().to_string(),
+        }
+    }
+
+    // the scale of the `[scales]` table with this name, ignoring the case.
+    pub fn named_scale(&self, name: &str) -> Option<NamedScale> {
+        self.get_scales()
+            .into_iter()
+            .find(|scale| scale.name.eq_ignore_ascii_case(name))
+    }
+
+    // the palette of a theme with the scale colors of the config.
+    pub fn with_scale_colors(&self, mut palette: Palette) -> Palette {
+        let Some(overrides) = &self.scale_colors else {
+            return palette;
+        };
+        let colors = &mut palette.scale_colors;
+        colors.ihk = overrides.ihk.unwrap_or(colors.ihk);
+        colors.techniker = overrides.techniker.unwrap_or(colors.techniker);
+        colors.linear = overrides.linear.unwrap_or(colors.linear);
+        colors.custom = overrides.custom.unwrap_or(colors.custom);
+        palette
+    }
+
+    // return the name of the theme, which is either a builtin or a theme file.
+    pub fn get_theme(&self) -> String {
+        self.theme
+            .clone()
+            .unwrap_or(ThemeVariant::default().to_string())
     }
+
+    // return the table columns which should not be shown.
+    pub fn get_hidden_columns(&self) -> Vec<TableColumn> {
+        self.hidden_columns
+            .clone()
+            .unwrap_or_else(TableColumn::default_hidden)
+    }
+
+    // return the bin width of the points histogram on the report tab.
+    pub fn get_histogram_bin_width(&self) -> f64 {
+        self.histogram_bin_width.unwrap_or(DEFAULT_BIN_WIDTH)
+    }
+
+    // return the pass quotas below which the pass gauge shows a warning or critical color.
+    pub fn get_pass_quota_thresholds(&self) -> (f64, f64) {
+        (
+            self.pass_quota_warning.unwrap_or(0.8),
+            self.pass_quota_critical.unwrap_or(0.6),
+        )
+    }
+
+    // return the expected average grade, which is marked in the grade chart.
+    pub fn get_target_average(&self) -> Option<f64> {
+        self.target_average
+    }
+
+    // return the tabs in the configured order without duplicates, all tabs if none are given.
+    pub fn get_tabs(&self) -> Vec<AppTab> {
+        let mut tabs: Vec<AppTab> = Vec::new();
+        for tab in self.tabs.iter().flatten() {
+            if !tabs.contains(tab) {
+                tabs.push(*tab);
+            }
+        }
+        match tabs.is_empty() {
+            true => AppTab::iter().collect(),
+            false => tabs,
+        }
+    }
+
+    // return the locale of dates and decimal numbers.
+    pub fn get_locale(&self) -> Locale {
+        self.locale.unwrap_or_default()
+    }
+
+    // return how grades are written in the tables and exports.
+    pub fn get_grade_format(&self) -> GradeFormat {
+        self.grade_format.unwrap_or_default()
+    }
+
+    // return the decimals shown for percentages, points and averages.
+    pub fn get_precision(&self) -> Precision {
+        self.precision.unwrap_or_default()
+    }
+
+    // return the tab shown on start, `with_course` if a course was opened on the command line.
+    // Tabs which are not shown fall back to the first tab.
+    pub fn get_start_tab(&self, with_course: bool) -> AppTab {
+        let tabs = self.get_tabs();
+        let tab = match with_course {
+            true => self.course_tab.or(self.default_tab),
+            false => self.default_tab,
+        };
+        tab.filter(|tab| tabs.contains(tab)).unwrap_or(tabs[0])
+    }
+
+    // return the worst grade which still counts as passed, 4 if not configured or invalid.
+    pub fn get_pass_grade(&self) -> Grade {
+        let Some(grade) = self.pass_grade else {
+            return PASS_GRADE;
+        };
+        Grade::try_from(grade).unwrap_or_else(|_| {
+            warn!("Invalid pass grade {grade}");
+            PASS_GRADE
+        })
+    }
+
+    // return the share of failed students above which a warning is shown.
+    pub fn get_fail_limit(&self) -> f64 {
+        self.fail_limit.unwrap_or(FAIL_LIMIT)
+    }
+
+    // return true if tables and charts should use the whole terminal width.
+    pub fn get_full_width(&self) -> bool {
+        self.full_width.unwrap_or(false)
+    }
+
+    // return the keys to move around in the tabs.
+    pub fn get_keymap(&self) -> KeymapPreset {
+        self.keymap.unwrap_or_default()
+    }
+
+    // return the columns the exporters should write.
+    pub fn get_export_columns(&self) -> Vec<ExportColumn> {
+        self.export_columns
+            .clone()
+            .unwrap_or_else(ExportColumn::defaults)
+    }
+}
+
+impl ConfigIssue {
+    fn from_toml(content: &str, error: &toml::de::Error) -> Self {
+        Self {
+            line: error
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() + 1),
+            message: error.message().to_string(),
+        }
+    }
+}
+
+// the line of a `[table]` header, starting at 1.
+fn line_of_table(content: &str, table: &str) -> Option<usize> {
+    let header = format!("[{table}]");
+    content
+        .lines()
+        .position(|line| line.trim() == header)
+        .map(|index| index + 1)
+}
+
+fn read_table(path: &Path) -> eyre::Result<toml::Table> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| eyre::eyre!("Unable to read config '{}': {e}", path.display()))?;
+    toml::from_str(&content).map_err(|e| eyre::eyre!("Invalid config '{}': {e}", path.display()))
+}
+
+// the name of the environment variable which overrides a config key, e.g. `GRACA_DEFAULT_SCALE`.
+pub fn env_name(key: &str) -> String {
+    format!("{}_{}", PROJECT_NAME.as_str(), key.to_uppercase())
+}
+
+// the names of the set `GRACA_*` variables which override config keys.
+pub fn env_variables() -> Vec<String> {
+    CONFIG_KEYS
+        .iter()
+        .map(|key| env_name(key))
+        .filter(|name| std::env::var_os(name).is_some())
+        .collect()
+}
+
+// the config keys set by `GRACA_*` variables, e.g. `GRACA_THEME=light` or `GRACA_PASS_GRADE=3`.
+// Fails with the first value which does not fit its key.
+fn env_overrides(var: impl Fn(&str) -> Option<String>) -> eyre::Result<toml::Table> {
+    let mut table = toml::Table::new();
+    for key in CONFIG_KEYS {
+        let Some(raw) = var(&env_name(key)) else {
+            continue;
+        };
+        let value = env_value(key, raw).map_err(|e| eyre::eyre!("${}: {e}", env_name(key)))?;
+        table.insert(key.to_string(), value);
+    }
+    Ok(table)
+}
+
+// the value of a key as it would be written in the config, e.g. a course dir `2025` stays a string.
+fn env_value(key: &str, raw: String) -> Result<toml::Value, String> {
+    let value = match TOML_KEYS.contains(&key) {
+        true => toml::from_str::<toml::Table>(&format!("value = {raw}"))
+            .ok()
+            .and_then(|mut parsed| parsed.remove("value"))
+            .ok_or_else(|| format!("'{raw}' is no valid value"))?,
+        false => toml::Value::String(raw),
+    };
+    let table = toml::Table::from_iter([(key.to_string(), value.clone())]);
+    toml::Value::Table(table)
+        .try_into::<AppConfig>()
+        .map_err(|e| e.message().trim().to_string())?;
+    Ok(value)
+}
+
+/// Fails if a `GRACA_*` variable has a value which does not fit its config key,
+/// instead of using the defaults for the whole config.
+pub fn check_env() -> eyre::Result<()> {
+    env_overrides(|name| std::env::var(name).ok()).map(|_| ())
+}
+
+// keys of `overrides` replace the ones of `base`, tables like `[scales]` are merged.
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(value)) => {
+                merge_tables(base, value)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+// the line of a top level `key = value`, starting at 1.
+fn line_of_key(content: &str, key: &str) -> Option<usize> {
+    content
+        .lines()
+        .position(|line| {
+            line.trim_start()
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map(|index| index + 1)
+}
+
+/// Loads the palette of a builtin theme or a theme file `themes/<name>.toml` in the config dir.
+pub fn load_theme(name: &str) -> eyre::Result<Palette> {
+    if let Ok(variant) = name.parse::<ThemeVariant>() {
+        return Ok(variant.palette());
+    }
+
+    let theme_path = get_config_dir()?
+        .join("themes")
+        .join(format!("{name}.toml"));
+    let content = fs::read_to_string(&theme_path)
+        .map_err(|e| eyre::eyre!("Unable to read theme '{}': {e}", theme_path.display()))?;
+    let palette: Palette = toml::from_str(&content)?;
+    Ok(palette)
+}
+
+/// Returns the builtin scale with this name or a scale of the `[scales]` config table,
+/// otherwise the custom scale in the toml file at `name`.
+pub fn load_scale(name: &str) -> eyre::Result<GradeScaleType> {
+    if let Some(scale_type) = GradeScaleType::from_name(name) {
+        return Ok(scale_type);
+    }
+    let config = AppConfig::read_config().unwrap_or_default();
+    if let Some(scale) = config.named_scale(name) {
+        return Ok(scale.scale_type);
+    }
+    let content = fs::read_to_string(name)
+        .map_err(|e| eyre::eyre!("'{name}' is no known scale and no readable file: {e}"))?;
+    GradeScaleType::from_toml(&content).map_err(|e| eyre::eyre!("Invalid scale '{name}': {e}"))
+}
+
+/// Returns the names of the builtin themes and all theme files in the config dir.
+pub fn available_themes() -> Vec<String> {
+    let mut names: Vec<String> = ThemeVariant::iter().map(|v| v.to_string()).collect();
+    if let Ok(entries) = get_config_dir().and_then(|dir| Ok(fs::read_dir(dir.join("themes"))?)) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            match path.extension()?.to_str()? {
+                "toml" => Some(path.file_stem()?.to_str()?.to_string()),
+                _ => None,
+            }
+        }));
+    }
+    names
 }
 
 fn project_directory() -> Option<ProjectDirs> {
@@ -81,6 +946,15 @@ pub fn get_data_dir() -> PathBuf {
     directory
 }
 
+// the config file given on the command line or the config.toml in the config dir,
+// which might not exist yet.
+pub fn get_config_file() -> eyre::Result<PathBuf> {
+    match CONFIG_FILE.get() {
+        Some(path) => Ok(path.clone()),
+        None => Ok(get_config_dir()?.join("config.toml")),
+    }
+}
+
 pub fn get_config_dir() -> eyre::Result<PathBuf> {
     let directory = if let Some(s) = CONFIG_FOLDER.clone() {
         s
@@ -105,3 +979,137 @@ pub fn get_document_dir() -> eyre::Result<PathBuf> {
 
     Ok(directory)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let content =
+            "default_scale = \"IHK\"\ncolour = \"red\"\ncourse_dir = \"/does/not/exist\"\n";
+        let (config, issues) = AppConfig::check(content);
+        assert!(config.is_some());
+        assert_eq!(
+            issues,
+            vec![
+                ConfigIssue {
+                    line: Some(2),
+                    message: "unknown key 'colour'".to_string()
+                },
+                ConfigIssue {
+                    line: Some(3),
+                    message: "'/does/not/exist' is no directory".to_string()
+                },
+            ]
+        );
+
+        let content = "\ndefault_scale = \"school\"\n\n[scales.school]\nboundaries = { 1 = 0.9, 2 = 0.8, 3 = 0.6, 4 = 0.5, 5 = 0.2, 6 = 0 }\nkey = \"S\"\n";
+        let (config, issues) = AppConfig::check(content);
+        assert!(issues.is_empty());
+        let config = config.unwrap();
+        assert_eq!(config.get_scales()[0].key, Some('S'));
+        assert_eq!(config.get_default_scale().values()[0], (1, 0.9),);
+
+        let (_, issues) = AppConfig::check("\ndefault_scale = \"FOO\"\n");
+        assert_eq!(
+            issues,
+            vec![ConfigIssue {
+                line: Some(2),
+                message: "unknown scale 'FOO'".to_string()
+            }]
+        );
+
+        let (_, issues) = AppConfig::check("[scales.bad]\nboundaries = { 1 = 0.5 }\n");
+        assert_eq!(issues[0].line, Some(1));
+
+        let (_, issues) = AppConfig::check(DEFAULT_CONFIG);
+        assert!(issues.is_empty());
+
+        let (config, issues) = AppConfig::check(
+            "tabs = [\"result\", \"report\"]\ndefault_tab = \"scale\"\ncourse_tab = \"report\"\n",
+        );
+        assert_eq!(issues[0].line, Some(2));
+        let config = config.unwrap();
+        assert_eq!(config.get_start_tab(false), AppTab::Result);
+        assert_eq!(config.get_start_tab(true), AppTab::Report);
+
+        let (config, issues) = AppConfig::check("pass_grade = 7\n");
+        assert_eq!(issues[0].line, Some(1));
+        assert_eq!(config.unwrap().get_pass_grade(), PASS_GRADE);
+
+        let (config, issues) = AppConfig::check("default_export_format = \"pdf\"\n");
+        assert_eq!(issues[0].line, Some(1));
+        assert_eq!(config.unwrap().get_default_export_format(), None);
+    }
+
+    #[test]
+    fn test_export_dir() {
+        // the same without a config file and with one which doesn't set the path.
+        let documents = get_document_dir().unwrap_or(PathBuf::from("."));
+        assert_eq!(AppConfig::new().get_export_dir(), documents);
+        let config: AppConfig = toml::from_str("theme = \"light\"\n").unwrap();
+        assert_eq!(config.get_export_dir(), documents);
+
+        let config: AppConfig = toml::from_str("export_path = \"/tmp/grades\"\n").unwrap();
+        assert_eq!(config.get_export_dir(), PathBuf::from("/tmp/grades"));
+    }
+
+    #[test]
+    fn test_merge_tables() {
+        let mut base: toml::Table =
+            toml::from_str("theme = \"dark\"\nfull_width = true\n[scales.a]\nkey = \"A\"\n")
+                .unwrap();
+        let local: toml::Table =
+            toml::from_str("theme = \"light\"\n[scales.b]\nkey = \"B\"\n").unwrap();
+        merge_tables(&mut base, local);
+
+        assert_eq!(base["theme"].as_str(), Some("light"));
+        assert_eq!(base["full_width"].as_bool(), Some(true));
+        let scales = base["scales"].as_table().unwrap();
+        assert_eq!(scales.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let env = |name: &str| match name {
+            "GRACA_THEME" => Some("light".to_string()),
+            "GRACA_PASS_GRADE" => Some("3".to_string()),
+            "GRACA_HIDDEN_COLUMNS" => Some("[\"label\", \"pct\"]".to_string()),
+            _ => None,
+        };
+        let config: AppConfig = toml::Value::Table(env_overrides(env).unwrap())
+            .try_into()
+            .unwrap();
+        assert_eq!(config.get_theme(), "light");
+        assert_eq!(config.get_pass_grade(), Grade::Satisfactory);
+        assert_eq!(
+            config.get_hidden_columns(),
+            vec![TableColumn::Label, TableColumn::Pct]
+        );
+
+        // strings which look like numbers stay strings.
+        let env = |name: &str| (name == "GRACA_COURSE_DIR").then(|| "2025".to_string());
+        let config: AppConfig = toml::Value::Table(env_overrides(env).unwrap())
+            .try_into()
+            .unwrap();
+        assert_eq!(config.course_dir, Some(PathBuf::from("2025")));
+
+        let env = |name: &str| (name == "GRACA_PASS_GRADE").then(|| "four".to_string());
+        let e = env_overrides(env).unwrap_err();
+        assert!(e.to_string().starts_with("$GRACA_PASS_GRADE: "));
+        let env = |name: &str| (name == "GRACA_DEFAULT_TAB").then(|| "nope".to_string());
+        assert!(env_overrides(env).is_err());
+    }
+
+    #[test]
+    fn test_scale_colors() {
+        let theme: Palette =
+            toml::from_str("[scale_colors]\nihk = \"red\"\nlinear = \"cyan\"\n").unwrap();
+        let config: AppConfig = toml::from_str("[scale_colors]\nlinear = \"white\"\n").unwrap();
+        let colors = config.with_scale_colors(theme).scale_colors;
+        assert_eq!(colors.ihk, Color::Red);
+        assert_eq!(colors.techniker, Color::Blue);
+        assert_eq!(colors.linear, Color::White);
+    }
+}
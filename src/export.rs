@@ -8,9 +8,16 @@ use std::{
 
 use csv::Error as CsvError;
 use directories::UserDirs;
-use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use rust_xlsxwriter::{Color, DocProperties, Format, Workbook, XlsxError};
+use serde::Deserialize;
+use strum_macros::{Display, EnumIter, EnumString};
 
+use crate::locale::{locale, precision};
+use crate::model::scale::round_dp;
+use crate::model::stats::Statistics;
 use crate::ui::scale_tab::GradingScaleTableRowData;
+use crate::ui::students_tab::ExamResultTableRowData;
+use crate::ui::TableColumn;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExportError {
@@ -69,8 +76,79 @@ impl From<toml::ser::Error> for ExportError {
     }
 }
 
+/// Columns which can be written by the exporters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Display, EnumIter, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ExportColumn {
+    Grade,
+    Label,
+    Min,
+    Max,
+    Pct,
+    // of the students, only in the result sheet.
+    Percentile,
+    Notes,
+}
+
+impl ExportColumn {
+    // the columns exported if nothing else is configured.
+    pub fn defaults() -> Vec<ExportColumn> {
+        vec![
+            ExportColumn::Grade,
+            ExportColumn::Min,
+            ExportColumn::Max,
+            ExportColumn::Pct,
+        ]
+    }
+
+    // return the header text of a column
+    pub fn header(&self) -> &'static str {
+        match self {
+            ExportColumn::Grade => "Note",
+            ExportColumn::Label => "Bezeichnung",
+            ExportColumn::Min => "min",
+            ExportColumn::Max => "max",
+            ExportColumn::Pct => "%",
+            ExportColumn::Percentile => "Perzentil",
+            ExportColumn::Notes => "Notizen",
+        }
+    }
+
+    fn is_result_column(&self) -> bool {
+        matches!(self, ExportColumn::Percentile | ExportColumn::Notes)
+    }
+
+    // the columns which belong to the rows of the scale.
+    pub fn scale_columns(columns: &[ExportColumn]) -> Vec<ExportColumn> {
+        columns
+            .iter()
+            .filter(|column| !column.is_result_column())
+            .copied()
+            .collect()
+    }
+
+    // the optional columns of the result sheet, in the given order.
+    pub fn result_columns(columns: &[ExportColumn]) -> Vec<ExportColumn> {
+        columns
+            .iter()
+            .filter(|column| column.is_result_column())
+            .copied()
+            .collect()
+    }
+}
+
+/// File formats supported by the exporters.
+pub const EXPORT_FORMATS: [&str; 3] = ["csv", "toml", "xlsx"];
+
 pub trait Exporter {
-    fn export(path: &Path, data: &Vec<GradingScaleTableRowData>) -> Result<(), ExportError>;
+    // `title` names the exam, if the format can store it.
+    fn export(
+        path: &Path,
+        data: &[GradingScaleTableRowData],
+        columns: &[ExportColumn],
+        title: &str,
+    ) -> Result<(), ExportError>;
 }
 
 pub struct CsvExporter;
@@ -78,11 +156,17 @@ pub struct TomlExporter;
 pub struct XlsxExporter;
 
 impl Exporter for CsvExporter {
-    fn export(path: &Path, data: &Vec<GradingScaleTableRowData>) -> Result<(), ExportError> {
+    fn export(
+        path: &Path,
+        data: &[GradingScaleTableRowData],
+        columns: &[ExportColumn],
+        _title: &str,
+    ) -> Result<(), ExportError> {
+        // the locale only changes the displayed numbers, not the format of the file.
         let mut wtr = csv::Writer::from_path(path)?;
 
-        for data_row in data.into_iter() {
-            wtr.serialize(data_row.as_str_array())?
+        for data_row in data.iter() {
+            wtr.serialize(data_row.as_plain_columns(columns))?
         }
         wtr.flush()?;
 
@@ -92,10 +176,25 @@ impl Exporter for CsvExporter {
 }
 
 impl Exporter for TomlExporter {
-    fn export(path: &Path, data: &Vec<GradingScaleTableRowData>) -> Result<(), ExportError> {
+    fn export(
+        path: &Path,
+        data: &[GradingScaleTableRowData],
+        columns: &[ExportColumn],
+        _title: &str,
+    ) -> Result<(), ExportError> {
+        // the grade is always used as key, so it is not repeated in the value.
+        let value_columns: Vec<ExportColumn> = columns
+            .iter()
+            .filter(|&&col| col != ExportColumn::Grade)
+            .copied()
+            .collect();
+
         let mut dict: HashMap<String, String> = HashMap::new();
-        for [grade, min, max, pct] in data.iter().map(|row| row.as_str_array()) {
-            dict.insert(grade, format!("({},{},{})", min, max, pct));
+        for row in data.iter() {
+            dict.insert(
+                row.grade().to_string(),
+                format!("({})", row.as_plain_columns(&value_columns).join(",")),
+            );
         }
 
         let toml_string = toml::to_string_pretty(&dict)?;
@@ -105,9 +204,15 @@ impl Exporter for TomlExporter {
 }
 
 impl Exporter for XlsxExporter {
-    fn export(path: &Path, data: &Vec<GradingScaleTableRowData>) -> Result<(), ExportError> {
+    fn export(
+        path: &Path,
+        data: &[GradingScaleTableRowData],
+        columns: &[ExportColumn],
+        title: &str,
+    ) -> Result<(), ExportError> {
         // Create a new Excel file object.
         let mut workbook = Workbook::new();
+        workbook.set_properties(&DocProperties::new().set_title(title));
 
         // Add a bold format to use to highlight cells.
         let bold = Format::new().set_bold();
@@ -115,18 +220,16 @@ impl Exporter for XlsxExporter {
         // Add a worksheet to the workbook.
         let worksheet = workbook.add_worksheet();
 
-        // Write a string to cell (0, 0) = A1.
-        worksheet.write_with_format(0, 0, "Note", &bold)?;
-        worksheet.write_with_format(0, 1, "min", &bold)?;
-        worksheet.write_with_format(0, 2, "max", &bold)?;
-        worksheet.write_with_format(0, 3, "%", &bold)?;
+        // Write the header row starting at cell (0, 0) = A1.
+        for (col, column) in columns.iter().enumerate() {
+            worksheet.write_with_format(0, col as u16, column.header(), &bold)?;
+        }
 
-        for (idx, row_data) in data.iter().map(|row| row.as_str_array()).enumerate() {
+        for (idx, row_data) in data.iter().map(|row| row.as_columns(columns)).enumerate() {
             let idx = idx as u32;
-            worksheet.write(idx + 1, 0, row_data[0].clone())?;
-            worksheet.write(idx + 1, 1, row_data[1].clone())?;
-            worksheet.write(idx + 1, 2, row_data[2].clone())?;
-            worksheet.write(idx + 1, 3, row_data[3].clone())?;
+            for (col, value) in row_data.into_iter().enumerate() {
+                worksheet.write(idx + 1, col as u16, value)?;
+            }
         }
 
         workbook.save(path)?;
@@ -135,17 +238,166 @@ impl Exporter for XlsxExporter {
     }
 }
 
-pub fn export(path: &Path, data: &Vec<GradingScaleTableRowData>) -> Result<(), ExportError> {
+pub fn export(
+    path: &Path,
+    data: &[GradingScaleTableRowData],
+    columns: &[ExportColumn],
+    title: &str,
+) -> Result<(), ExportError> {
+    let columns = &ExportColumn::scale_columns(columns);
     match path.extension().and_then(|ext| ext.to_str()) {
-        Some("csv") => Ok(CsvExporter::export(path, data)?),
-        Some("toml") => Ok(TomlExporter::export(path, data)?),
-        Some("xlsx") => Ok(XlsxExporter::export(path, data)?),
+        Some("csv") => Ok(CsvExporter::export(path, data, columns, title)?),
+        Some("toml") => Ok(TomlExporter::export(path, data, columns, title)?),
+        Some("xlsx") => Ok(XlsxExporter::export(path, data, columns, title)?),
         _ => Err(ExportError {
             details: "File type not supported.".to_string(),
         }),
     }
 }
 
+/// Writes the graded students to an xlsx sheet, absent students have no grade.
+/// The last column tells if the student passed with `pass_grade` or better,
+/// the rows below the students count the passed and failed students
+/// and describe the shape of the point distribution. A note follows if the share
+/// of failed students is above `fail_limit`. The percentile rank and the notes of
+/// the students are written after the last column if `columns` contains them.
+pub fn export_results(
+    path: &Path,
+    rows: &[ExamResultTableRowData],
+    columns: &[ExportColumn],
+    title: &str,
+    pass_grade: u8,
+    fail_limit: f64,
+) -> Result<(), ExportError> {
+    let mut workbook = Workbook::new();
+    workbook.set_properties(&DocProperties::new().set_title(title));
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet();
+
+    let optional = ExportColumn::result_columns(columns);
+    let columns = [
+        TableColumn::Name,
+        TableColumn::Points,
+        TableColumn::Percentage,
+        TableColumn::Grade,
+    ];
+    for (col, header) in ["Name", "Punkte", "%", "Note", "Bestanden"]
+        .into_iter()
+        .chain(optional.iter().map(ExportColumn::header))
+        .enumerate()
+    {
+        worksheet.write_with_format(0, col as u16, header, &bold)?;
+    }
+    for (idx, row) in rows.iter().enumerate() {
+        let idx = idx as u32 + 1;
+        for (col, column) in columns.iter().enumerate() {
+            // numbers are written as numbers so the sheet can calculate with them.
+            match row.value(*column) {
+                Some(number) => worksheet.write(idx, col as u16, number)?,
+                None => worksheet.write(idx, col as u16, row.cell(*column))?,
+            };
+        }
+        if !row.is_absent() {
+            worksheet.write(idx, columns.len() as u16, row.is_passed(pass_grade))?;
+        }
+        for (offset, column) in optional.iter().enumerate() {
+            let col = (columns.len() + 1 + offset) as u16;
+            match column {
+                ExportColumn::Percentile => {
+                    if let Some(share) = row.value(TableColumn::Percentile) {
+                        worksheet.write(idx, col, share)?;
+                    }
+                }
+                _ if !row.notes().is_empty() => {
+                    worksheet.write(idx, col, row.notes())?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let passed = rows.iter().filter(|row| row.is_passed(pass_grade)).count();
+    let failed = rows.iter().filter(|row| !row.is_absent()).count() - passed;
+    let first = rows.len() as u32 + 2;
+    for (idx, (label, count)) in [("Bestanden", passed), ("Nicht bestanden", failed)]
+        .into_iter()
+        .enumerate()
+    {
+        worksheet.write_with_format(first + idx as u32, 0, label, &bold)?;
+        worksheet.write(first + idx as u32, 1, count as f64)?;
+    }
+    if passed + failed > 0 {
+        let rate = round_dp(
+            passed as f64 / (passed + failed) as f64,
+            precision().percentage + 2,
+        );
+        worksheet.write_with_format(first + 2, 0, "Quote", &bold)?;
+        worksheet.write(first + 2, 1, rate)?;
+    }
+
+    // the shape of the point distribution with its interpretation next to it.
+    let points: Vec<f64> = rows
+        .iter()
+        .filter(|row| !row.is_absent())
+        .filter_map(|row| row.value(TableColumn::Points))
+        .collect();
+    if let Some(stats) = Statistics::from_values(&points) {
+        let decimals = precision().average;
+        for (idx, (label, value)) in [("Schiefe", stats.skewness), ("Wölbung", stats.kurtosis)]
+            .into_iter()
+            .enumerate()
+        {
+            worksheet.write_with_format(first + 3 + idx as u32, 0, label, &bold)?;
+            worksheet.write(first + 3 + idx as u32, 1, round_dp(value, decimals))?;
+        }
+        worksheet.write(first + 3, 2, stats.shape())?;
+    }
+    if passed + failed > 0 && failed as f64 / (passed + failed) as f64 > fail_limit {
+        let limit = round_dp(fail_limit * 100.0, precision().percentage);
+        worksheet.write_with_format(first + 5, 0, "Hinweis", &bold)?;
+        worksheet.write_with_format(
+            first + 5,
+            1,
+            format!(
+                "Mehr als {}% nicht bestanden, eine Begründung kann erforderlich sein.",
+                locale().format_number(limit)
+            ),
+            &Format::new().set_font_color(Color::Red),
+        )?;
+    }
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+/// Formats the scale as a text table with aligned columns, e.g. for the terminal.
+pub fn format_table(data: &[GradingScaleTableRowData], columns: &[ExportColumn]) -> String {
+    let columns = &ExportColumn::scale_columns(columns);
+    let rows: Vec<Vec<String>> =
+        std::iter::once(columns.iter().map(|c| c.header().into()).collect())
+            .chain(data.iter().map(|row| row.as_columns(columns)))
+            .collect();
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect();
+            format!("{}\n", cells.join("  ").trim_end())
+        })
+        .collect()
+}
+
 pub fn resolve_path(user_input: &str) -> Option<PathBuf> {
     let path = PathBuf::from(user_input);
 
@@ -160,7 +412,7 @@ pub fn resolve_path(user_input: &str) -> Option<PathBuf> {
 
 // Takes a string and appends it to the home directory
 fn expand_home(path: &PathBuf) -> Option<PathBuf> {
-    if let Some(mut home_path) = UserDirs::new().and_then(|u| Some(u.home_dir().to_path_buf())) {
+    if let Some(mut home_path) = UserDirs::new().map(|u| u.home_dir().to_path_buf()) {
         home_path.push(path);
         Some(home_path)
     } else {
@@ -195,13 +447,68 @@ mod tests {
     #[test]
     fn test_export() {
         let data = vec![];
-        assert_eq!(export(&PathBuf::from("test.csv"), &data), Ok(()));
-        assert_eq!(export(&PathBuf::from("test.xlsx"), &data), Ok(()));
+        let columns = ExportColumn::defaults();
+        let dir = std::env::temp_dir().join("graca_test_export");
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(export(&dir.join("test.csv"), &data, &columns, ""), Ok(()));
+        assert_eq!(export(&dir.join("test.xlsx"), &data, &columns, ""), Ok(()));
         assert_eq!(
-            export(&PathBuf::from("test.txt"), &data),
+            export(&dir.join("test.txt"), &data, &columns, ""),
             Err(ExportError {
                 details: "File type not supported.".to_string(),
             })
-        )
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_plain_columns() {
+        let row = GradingScaleTableRowData::new(2, 40.5, 45.5, 0.81);
+        let columns = [ExportColumn::Min, ExportColumn::Max, ExportColumn::Pct];
+        assert_eq!(row.as_plain_columns(&columns), ["40.5", "45.5", "81%"]);
+    }
+
+    #[test]
+    fn test_export_results() {
+        use calamine::{open_workbook, Data, Reader, Xlsx};
+
+        let path = std::env::temp_dir().join("graca_test_export_results.xlsx");
+        let rows = [
+            ExamResultTableRowData::new("Anna", 46.0, 0.92, 1).with_percentile(Some(0.75)),
+            ExamResultTableRowData::new("Ben", 0.0, 0.0, 6).with_absent(true),
+        ];
+        let columns = [ExportColumn::Notes, ExportColumn::Percentile];
+        export_results(&path, &rows, &columns, "", 4, 1.0 / 3.0).unwrap();
+
+        let mut workbook: Xlsx<_> = open_workbook(&path).unwrap();
+        let sheet = workbook.worksheet_range_at(0).unwrap().unwrap();
+        assert_eq!(sheet.get((0, 5)), Some(&Data::String("Notizen".into())));
+        assert_eq!(sheet.get((0, 6)), Some(&Data::String("Perzentil".into())));
+        assert_eq!(sheet.get((1, 6)), Some(&Data::Float(0.75)));
+        // absent students have no rank.
+        assert!(matches!(sheet.get((2, 6)), None | Some(Data::Empty)));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_format_table() {
+        let data = vec![
+            GradingScaleTableRowData::new(1, 37.0, 40.0, 0.92),
+            GradingScaleTableRowData::new(2, 32.5, 36.5, 0.81),
+        ];
+        assert_eq!(
+            format_table(&data, &ExportColumn::defaults()),
+            "Note  min   max   %\n1     37    40    92%\n2     32.5  36.5  81%\n"
+        );
+    }
+
+    #[test]
+    fn test_export_columns() {
+        let row = GradingScaleTableRowData::new(2, 81.0, 91.0, 0.81);
+        assert_eq!(
+            row.as_columns(&[ExportColumn::Grade, ExportColumn::Label, ExportColumn::Pct]),
+            vec!["2".to_string(), "Good".to_string(), "81%".to_string()]
+        );
+        assert_eq!("pct".parse::<ExportColumn>().unwrap(), ExportColumn::Pct);
     }
 }
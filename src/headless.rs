@@ -0,0 +1,1168 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde::Serialize;
+
+use crate::cli::{Args, Command};
+use crate::config::{
+    check_env, env_variables, get_config_file, load_scale, local_config_files, AppConfig,
+    ConfigIssue, DEFAULT_CONFIG,
+};
+use crate::export::{export_results, format_table, ExportColumn};
+use crate::locale::{format_average, format_percentage, format_points, format_share};
+use crate::man;
+use crate::model::exam::WeightedExam;
+use crate::model::scale::GradingScale;
+use crate::model::stats::{weighted_average, Statistics};
+use crate::model::students::StudentList;
+use crate::model::Model;
+use crate::ui::scale_tab::GradingScaleTableRowData;
+use crate::ui::students_tab::ExamResultTableRowData;
+use crate::ui::TableColumn;
+
+/// Failures of the headless commands, each with its own exit code for scripts.
+#[derive(Debug)]
+pub enum HeadlessError {
+    InvalidArgs(String),  // e.g. an unknown scale, exit code 2 like clap
+    InvalidInput(String), // unreadable or invalid data, exit code 3
+    Io(io::Error),        // writing the output failed, exit code 1
+}
+
+impl HeadlessError {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            // the reader of a pipe like `graca scale | head` is gone, which is no failure.
+            HeadlessError::Io(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+            HeadlessError::Io(_) => ExitCode::from(1),
+            HeadlessError::InvalidArgs(_) => ExitCode::from(2),
+            HeadlessError::InvalidInput(_) => ExitCode::from(3),
+        }
+    }
+}
+
+impl std::fmt::Display for HeadlessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeadlessError::InvalidArgs(msg) | HeadlessError::InvalidInput(msg) => {
+                write!(f, "{msg}")
+            }
+            HeadlessError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for HeadlessError {
+    fn from(e: io::Error) -> Self {
+        HeadlessError::Io(e)
+    }
+}
+
+impl From<csv::Error> for HeadlessError {
+    fn from(e: csv::Error) -> Self {
+        HeadlessError::Io(e.into())
+    }
+}
+
+/// Runs a command without the terminal interface. Returns the path of the
+/// course which should be opened afterwards, if any, e.g. for `watch`.
+pub fn run(command: &Command, args: &Args) -> Result<Option<PathBuf>, HeadlessError> {
+    match command {
+        Command::Scale => print_scale(args)?,
+        Command::New {
+            class,
+            students,
+            from,
+            open,
+        } => {
+            let path = new_course(class, *students, from.as_deref())?;
+            return Ok(open.then_some(path));
+        }
+        Command::Grade {
+            input: Some(input),
+            output,
+            ..
+        } => grade_file(args, input, output.as_deref())?,
+        Command::Grade { input: None, .. } => grade_stdin(args)?,
+        Command::Stats { input } => print_stats(args, input)?,
+        Command::Convert { input, output } => convert(args, input, output)?,
+        Command::Watch { input } => return Ok(Some(input.clone())),
+        Command::CheckConfig => check_config()?,
+        Command::InitConfig { force } => init_config(*force)?,
+        Command::Merge {
+            inputs,
+            output,
+            trend,
+        } => merge(args, inputs, *trend, output.as_deref())?,
+        Command::Man => write!(io::stdout(), "{}", man::render())?,
+    }
+    Ok(None)
+}
+
+fn unreadable(path: &Path, e: io::Error) -> HeadlessError {
+    HeadlessError::InvalidInput(format!("Unable to read '{}': {e}", path.display()))
+}
+
+// a model with the scale and points given on the command line.
+fn model_from_args(args: &Args, config: &AppConfig) -> Result<Model, HeadlessError> {
+    let scale_type = match &args.scale {
+        Some(name) => load_scale(name).map_err(|e| HeadlessError::InvalidArgs(e.to_string()))?,
+        None => config.get_default_scale(),
+    };
+    let mut model = Model::new();
+    model.scale.change_scale_type(scale_type);
+    model.scale.set_max_points(args.max_points());
+    model.scale.set_half_points(args.half_points);
+    model.exam = args.exam();
+    model.pass_grade = config.get_pass_grade();
+    model.fail_limit = config.get_fail_limit();
+    Ok(model)
+}
+
+// the model with the students of the course file, named as given on the command line
+// and averaged with the other exams of `--exams`.
+fn load_course(args: &Args, config: &AppConfig, input: &Path) -> Result<Model, HeadlessError> {
+    let mut model = load_exam(args, config, input, 0)?;
+    model.weight = args.weights.first().copied().unwrap_or(1.0);
+    for exam in other_exams(args, config)? {
+        model.add_exam(exam);
+    }
+    Ok(model)
+}
+
+// the model of one of several exams, graded with its own max points of `--points`.
+fn load_exam(
+    args: &Args,
+    config: &AppConfig,
+    input: &Path,
+    index: usize,
+) -> Result<Model, HeadlessError> {
+    let mut model = model_from_args(args, config)?;
+    model.scale.set_max_points(args.points_of(index));
+    model
+        .load_student_data(input)
+        .map_err(|e| unreadable(input, e))?;
+    if let Some(name) = &args.course_name {
+        model.set_class_name(name);
+    }
+    if let Some((name, points)) = model.points_out_of_range() {
+        return Err(HeadlessError::InvalidInput(format!(
+            "{points} points of {name} are out of range in '{}'",
+            input.display()
+        )));
+    }
+    Ok(model)
+}
+
+// fails unless there are max points and weights for each of the exams.
+fn check_exams(args: &Args, count: usize) -> Result<(), HeadlessError> {
+    if args.points.len() != 1 && args.points.len() != count {
+        return Err(HeadlessError::InvalidArgs(format!(
+            "expected {count} max points, one per exam"
+        )));
+    }
+    if !args.weights.is_empty() && args.weights.len() != count {
+        return Err(HeadlessError::InvalidArgs(format!(
+            "expected {count} weights, one per exam"
+        )));
+    }
+    if args.weights.iter().any(|weight| *weight < 0.0) {
+        return Err(HeadlessError::InvalidArgs(
+            "weights must not be negative".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The grades of the exams of `--exams`, which are averaged with the opened course.
+pub fn other_exams(args: &Args, config: &AppConfig) -> Result<Vec<WeightedExam>, HeadlessError> {
+    check_exams(args, args.exams.len() + 1)?;
+    let mut exams = Vec::new();
+    for (index, input) in args.exams.iter().enumerate() {
+        let mut model = load_exam(args, config, input, index + 1)?;
+        model.weight = args.weights.get(index + 1).copied().unwrap_or(1.0);
+        let name = input.file_stem().unwrap_or_default().to_string_lossy();
+        exams.push(model.to_weighted_exam(&name));
+    }
+    Ok(exams)
+}
+
+// the columns of `--columns`, else the ones of the config.
+fn export_columns(args: &Args, config: &AppConfig) -> Vec<ExportColumn> {
+    args.columns
+        .clone()
+        .unwrap_or_else(|| config.get_export_columns())
+}
+
+/// The scale of `scale --json`.
+#[derive(Serialize)]
+struct ScaleSummary {
+    scale: String,
+    max_points: f64,
+    half_points: bool,
+    grades: Vec<GradingScaleTableRowData>,
+}
+
+// writes the value as one line of json, NaN like the average of no students is null.
+fn write_json(mut writer: impl Write, value: &impl Serialize) -> Result<(), HeadlessError> {
+    serde_json::to_writer(&mut writer, value).map_err(io::Error::from)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Prints the grading scale to stdout, for a quick look without starting the app.
+pub fn print_scale(args: &Args) -> Result<(), HeadlessError> {
+    let config = AppConfig::read_config().unwrap_or_default();
+    let model = model_from_args(args, &config)?;
+
+    if args.json {
+        let summary = ScaleSummary {
+            scale: config.scale_name(model.scale.scale_type()),
+            max_points: model.scale.max_points(),
+            half_points: model.scale.is_using_half_points(),
+            grades: model.get_scale_data(),
+        };
+        return write_json(io::stdout(), &summary);
+    }
+
+    let mut out = io::stdout().lock();
+    writeln!(
+        out,
+        "{} · {} points",
+        config.scale_name(model.scale.scale_type()),
+        format_points(model.scale.max_points())
+    )?;
+    write!(
+        out,
+        "{}",
+        format_table(&model.get_scale_data(), &export_columns(args, &config))
+    )?;
+    Ok(())
+}
+
+/// Grades the students of a csv file, so it can be used in scripts.
+pub fn grade_file(args: &Args, input: &Path, output: Option<&Path>) -> Result<(), HeadlessError> {
+    let config = AppConfig::read_config().unwrap_or_default();
+    let model = load_course(args, &config, input)?;
+
+    let rows = model.get_student_data();
+
+    let writer: Box<dyn io::Write> = match output {
+        Some(path) => Box::new(fs::File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    if args.json {
+        return write_json(writer, &rows);
+    }
+
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(["name", "points", "percentage", "grade"])?;
+    for row in rows {
+        writer.write_record(
+            [
+                TableColumn::Name,
+                TableColumn::Points,
+                TableColumn::Percentage,
+                TableColumn::Grade,
+            ]
+            .map(|column| row.cell(column)),
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Grades the points read from stdin line by line, to be used in pipes.
+/// With `--json` each line is printed as a json object.
+pub fn grade_stdin(args: &Args) -> Result<(), HeadlessError> {
+    let config = AppConfig::read_config().unwrap_or_default();
+    let model = model_from_args(args, &config)?;
+    let mut out = io::stdout().lock();
+    for (number, line) in io::stdin().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let graded = GradedLine::parse(&model.scale, &line)
+            .map_err(|e| HeadlessError::InvalidInput(format!("line {}: {e}", number + 1)))?;
+        match args.json {
+            true => write_json(&mut out, &graded)?,
+            false => writeln!(out, "{graded}")?,
+        }
+    }
+    Ok(())
+}
+
+// a line of `points` or `name;points` from stdin with its grade.
+#[derive(Debug, PartialEq, Serialize)]
+struct GradedLine<'a> {
+    #[serde(skip)]
+    line: &'a str,
+    name: Option<&'a str>,
+    points: f64,
+    percentage: f64,
+    grade: u8,
+}
+
+impl<'a> GradedLine<'a> {
+    fn parse(scale: &GradingScale, line: &'a str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, value) = match line.rsplit_once(';') {
+            Some((name, value)) => (Some(name.trim()), value.trim()),
+            None => (None, line),
+        };
+        let points: f64 = value
+            .replace(',', ".")
+            .parse()
+            .map_err(|_| format!("'{value}' are no points"))?;
+        // NaN is no number in any range.
+        let grade = scale
+            .grade_for_points(points)
+            .filter(|_| (0.0..=scale.max_points()).contains(&points))
+            .ok_or(format!("{points} points are out of range"))?;
+        Ok(Self {
+            line,
+            name,
+            points,
+            percentage: GradingScale::percentage_for_points(points, scale.max_points()),
+            grade: grade.to_number(),
+        })
+    }
+}
+
+// appends percentage and grade to the input line.
+impl std::fmt::Display for GradedLine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{};{};{}",
+            self.line,
+            format_share(self.percentage),
+            self.grade
+        )
+    }
+}
+
+/// Prints the statistics of a course file, for a quick check without the report tab.
+pub fn print_stats(args: &Args, input: &Path) -> Result<(), HeadlessError> {
+    let config = AppConfig::read_config().unwrap_or_default();
+    let model = load_course(args, &config, input)?;
+    let scale_name = config.scale_name(model.scale.scale_type());
+    match args.json {
+        true => write_json(io::stdout(), &StatsSummary::new(&model, &scale_name))?,
+        false => write!(io::stdout(), "{}", format_stats(&model, &scale_name))?,
+    }
+    Ok(())
+}
+
+fn format_stats(model: &Model, scale_name: &str) -> String {
+    let mut out = format!(
+        "{} · {scale_name} · {} points\n",
+        model.title(),
+        format_points(model.scale.max_points())
+    );
+    let (passed, total) = model.passed_count();
+    let absent = model.get_student_data().len() - total;
+    out += &format!("students        {total} ({absent} absent)\n");
+    let (Some(points), Some(grades)) = (model.points_stats(), model.grade_stats()) else {
+        return out;
+    };
+    let join = |values: &[f64], format: fn(f64) -> String| {
+        let values: Vec<String> = values.iter().map(|&value| format(value)).collect();
+        values.join(", ")
+    };
+    out += &format!("average grade   {}\n", format_average(grades.mean));
+    if let Some(average) = model.weighted_class_average() {
+        out += &format!(
+            "weighted grade  {} over {} exams\n",
+            format_average(average),
+            model.exam_count()
+        );
+    }
+    out += &format!("median grade    {}\n", format_average(grades.median));
+    out += &format!("mode grade      {}\n", join(&grades.mode, format_average));
+    out += &format!("std dev grade   {}\n", format_average(grades.std_dev));
+    out += &format!("average points  {}\n", format_points(points.mean));
+    out += &format!("median points   {}\n", format_points(points.median));
+    out += &format!("mode points     {}\n", join(&points.mode, format_points));
+    out += &format!("std dev points  {}\n", format_points(points.std_dev));
+    out += &format!(
+        "range points    {} – {} ({})\n",
+        format_points(points.min),
+        format_points(points.max),
+        format_points(points.range())
+    );
+    out += &format!(
+        "iqr points      {} – {} ({})\n",
+        format_points(points.q1),
+        format_points(points.q3),
+        format_points(points.iqr())
+    );
+    out += &format!("skewness        {}\n", format_average(points.skewness));
+    out += &format!("kurtosis        {}\n", format_average(points.kurtosis));
+    out += &format!("shape           {}\n", points.shape());
+    out += &format!(
+        "pass rate       {} ({passed} of {total})\n",
+        format_percentage(model.pass_rate().unwrap_or_default())
+    );
+    out += &format!("failed          {}\n", model.failed_count());
+    if model.fail_limit_exceeded() {
+        out += &format!(
+            "warning         more than {} failed, a justification may be required\n",
+            format_percentage(model.fail_limit)
+        );
+    }
+
+    let distribution = model.grade_distribution();
+    out += "\ngrade  count\n";
+    for grade in 1..=6 {
+        let count = distribution.get(&grade).copied().unwrap_or_default();
+        let line = format!("{grade}      {count:<5} {}", "█".repeat(count));
+        out += line.trim_end();
+        out += "\n";
+    }
+    out
+}
+
+/// The statistics of `stats --json`, the averages are null if no student is present.
+#[derive(Serialize)]
+struct StatsSummary {
+    course: String,
+    exam: Option<String>,
+    date: Option<String>,
+    scale: String,
+    max_points: f64,
+    students: usize,
+    absent: usize,
+    average_grade: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weighted_average_grade: Option<f64>,
+    median_grade: Option<f64>,
+    mode_grade: Option<Vec<f64>>,
+    std_dev_grade: Option<f64>,
+    average_points: Option<f64>,
+    median_points: Option<f64>,
+    mode_points: Option<Vec<f64>>,
+    std_dev_points: Option<f64>,
+    lowest_points: Option<f64>,
+    highest_points: Option<f64>,
+    q1_points: Option<f64>,
+    q3_points: Option<f64>,
+    iqr_points: Option<f64>,
+    skewness_points: Option<f64>,
+    kurtosis_points: Option<f64>,
+    shape: Option<String>,
+    pass_grade: u8,
+    passed: usize,
+    failed: usize,
+    pass_rate: Option<f64>,
+    fail_limit_exceeded: bool,
+    distribution: Vec<GradeCount>,
+}
+
+#[derive(Serialize)]
+struct GradeCount {
+    grade: u8,
+    count: usize,
+}
+
+impl StatsSummary {
+    fn new(model: &Model, scale_name: &str) -> Self {
+        let (passed, total) = model.passed_count();
+        let point_stats = model.points_stats();
+        let grade_stats = model.grade_stats();
+        let points = |value: fn(&Statistics) -> f64| point_stats.as_ref().map(value);
+        let grades = |value: fn(&Statistics) -> f64| grade_stats.as_ref().map(value);
+        let distribution = model.grade_distribution();
+        Self {
+            course: model.get_class_name().to_string(),
+            exam: model.exam.name.clone(),
+            date: model.exam.date.map(|date| date.to_string()),
+            scale: scale_name.to_string(),
+            max_points: model.scale.max_points(),
+            students: total,
+            absent: model.get_student_data().len() - total,
+            average_grade: grades(|stats| stats.mean),
+            weighted_average_grade: model.weighted_class_average(),
+            median_grade: grades(|stats| stats.median),
+            mode_grade: grade_stats.as_ref().map(|stats| stats.mode.clone()),
+            std_dev_grade: grades(|stats| stats.std_dev),
+            average_points: points(|stats| stats.mean),
+            median_points: points(|stats| stats.median),
+            mode_points: point_stats.as_ref().map(|stats| stats.mode.clone()),
+            std_dev_points: points(|stats| stats.std_dev),
+            lowest_points: points(|stats| stats.min),
+            highest_points: points(|stats| stats.max),
+            q1_points: points(|stats| stats.q1),
+            q3_points: points(|stats| stats.q3),
+            iqr_points: points(Statistics::iqr),
+            skewness_points: points(|stats| stats.skewness),
+            kurtosis_points: points(|stats| stats.kurtosis),
+            shape: point_stats.as_ref().map(Statistics::shape),
+            pass_grade: model.pass_grade.to_number(),
+            passed,
+            failed: model.failed_count(),
+            pass_rate: model.pass_rate(),
+            fail_limit_exceeded: model.fail_limit_exceeded(),
+            distribution: (1..=6)
+                .map(|grade| GradeCount {
+                    grade,
+                    count: distribution.get(&grade).copied().unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Converts a course file or roster into another format, given by the extensions.
+pub fn convert(args: &Args, input: &Path, output: &Path) -> Result<(), HeadlessError> {
+    let config = AppConfig::read_config().unwrap_or_default();
+    let mut model = model_from_args(args, &config)?;
+    match extension(input).as_deref() {
+        Some("csv" | "xlsx") => model = load_course(args, &config, input)?,
+        Some("txt") => {
+            let content = fs::read_to_string(input).map_err(|e| unreadable(input, e))?;
+            let course = input.file_stem().unwrap_or_default().to_string_lossy();
+            model.set_student_list(StudentList::new(&course, &student_names(&content)));
+        }
+        _ => return Err(unsupported(input)),
+    }
+
+    match extension(output).as_deref() {
+        Some("csv") => model.save_student_data(output)?,
+        Some("txt") => {
+            let names: String = model
+                .get_student_data()
+                .iter()
+                .map(|row| row.cell(TableColumn::Name) + "\n")
+                .collect();
+            fs::write(output, names)?;
+        }
+        Some("xlsx") => export_results(
+            output,
+            &model.get_student_data(),
+            &export_columns(args, &config),
+            &model.title(),
+            model.pass_grade.to_number(),
+            model.fail_limit,
+        )
+        .map_err(|e| HeadlessError::Io(io::Error::other(e.msg())))?,
+        _ => return Err(unsupported(output)),
+    }
+    writeln!(
+        io::stdout(),
+        "Converted {} to {}",
+        input.display(),
+        output.display()
+    )?;
+    Ok(())
+}
+
+fn extension(path: &Path) -> Option<String> {
+    Some(path.extension()?.to_str()?.to_lowercase())
+}
+
+fn unsupported(path: &Path) -> HeadlessError {
+    HeadlessError::InvalidArgs(format!(
+        "'{}' has no supported format (csv, txt, xlsx).",
+        path.display()
+    ))
+}
+
+/// Name, weight and results of an exam of the gradebook.
+type Exam = (String, f64, Vec<ExamResultTableRowData>);
+
+/// Grade of each exam, none if the student is missing or absent.
+type Trend = Vec<Option<f64>>;
+
+/// Writes the points and grades of several exams side by side, one row per student,
+/// with the weighted average grade and the grade trend in the last columns and the
+/// class averages below. With `trend` only the trends are written as text.
+/// Students which are missing in some of the exams are reported on stderr.
+pub fn merge(
+    args: &Args,
+    inputs: &[PathBuf],
+    trend: bool,
+    output: Option<&Path>,
+) -> Result<(), HeadlessError> {
+    check_exams(args, inputs.len())?;
+    let config = AppConfig::read_config().unwrap_or_default();
+    let mut exams = Vec::new();
+    for (index, input) in inputs.iter().enumerate() {
+        let model = load_exam(args, &config, input, index)?;
+        let exam = input.file_stem().unwrap_or_default().to_string_lossy();
+        let weight = args.weights.get(index).copied().unwrap_or(1.0);
+        exams.push((exam.to_string(), weight, model.get_student_data()));
+    }
+
+    let (records, mismatches) = gradebook(&exams);
+    for mismatch in mismatches {
+        eprintln!("warning: {mismatch}");
+    }
+    let mut writer: Box<dyn io::Write> = match output {
+        Some(path) => Box::new(fs::File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    if trend {
+        write!(writer, "{}", format_trends(&exams))?;
+        return Ok(());
+    }
+    let mut writer = csv::Writer::from_writer(writer);
+    for record in records {
+        writer.write_record(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// the header, a row per student in the order they first appear and the averages,
+// and the missing students.
+fn gradebook(exams: &[Exam]) -> (Vec<Vec<String>>, Vec<String>) {
+    let mut header = vec!["name".to_string()];
+    for (exam, _, _) in exams {
+        header.push(format!("{exam} points"));
+        header.push(format!("{exam} grade"));
+    }
+    header.extend(["average".to_string(), "trend".to_string()]);
+    let mut records = vec![header];
+    let mut mismatches = Vec::new();
+    let mut averages = Vec::new();
+    let (class_trend, trends) = grade_trends(exams);
+    for (name, trend) in trends {
+        let mut record = vec![name.clone()];
+        let mut grades = Vec::new();
+        for ((exam, weight, rows), grade) in exams.iter().zip(&trend) {
+            match rows.iter().find(|row| row.cell(TableColumn::Name) == name) {
+                Some(row) => {
+                    record.extend([row.cell(TableColumn::Points), row.cell(TableColumn::Grade)]);
+                    // absent students have no grade in this exam.
+                    grades.extend(grade.map(|grade| (grade, *weight)));
+                }
+                None => {
+                    mismatches.push(format!("{name} is missing in {exam}"));
+                    record.extend([String::new(), String::new()]);
+                }
+            }
+        }
+        let average = weighted_average(&grades);
+        averages.extend(average);
+        record.push(average.map(format_average).unwrap_or_default());
+        record.push(format_trend(&trend));
+        records.push(record);
+    }
+
+    let mut footer = vec!["average".to_string()];
+    for ((_, _, rows), grade) in exams.iter().zip(&class_trend) {
+        let points: Vec<f64> = rows
+            .iter()
+            .filter(|row| !row.is_absent())
+            .filter_map(|row| row.value(TableColumn::Points))
+            .collect();
+        let mean = Statistics::from_values(&points).map(|stats| stats.mean);
+        footer.push(mean.map(format_points).unwrap_or_default());
+        footer.push(grade.map(format_average).unwrap_or_default());
+    }
+    let mean = Statistics::from_values(&averages).map(|stats| stats.mean);
+    footer.push(mean.map(format_average).unwrap_or_default());
+    footer.push(format_trend(&class_trend));
+    records.push(footer);
+    (records, mismatches)
+}
+
+// the average grade of the class per exam, and the grade per exam of each student
+// in the order they first appear. Missing and absent students have no grade.
+fn grade_trends(exams: &[Exam]) -> (Trend, Vec<(String, Trend)>) {
+    let mut names: Vec<String> = Vec::new();
+    for (_, _, rows) in exams {
+        for name in rows.iter().map(|row| row.cell(TableColumn::Name)) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    let trends = names
+        .into_iter()
+        .map(|name| {
+            let grades = exams
+                .iter()
+                .map(|(_, _, rows)| {
+                    rows.iter()
+                        .find(|row| row.cell(TableColumn::Name) == name)
+                        .and_then(|row| row.grade())
+                        .map(f64::from)
+                })
+                .collect();
+            (name, grades)
+        })
+        .collect();
+    let class = exams
+        .iter()
+        .map(|(_, _, rows)| {
+            let grades: Vec<f64> = rows
+                .iter()
+                .filter_map(|row| row.grade())
+                .map(f64::from)
+                .collect();
+            Statistics::from_values(&grades).map(|stats| stats.mean)
+        })
+        .collect();
+    (class, trends)
+}
+
+// a bar per exam from █ for grade 1 to ▁ for grade 6 and a gap without a grade,
+// followed by ↓ if the last grade is more than half a grade worse than the first
+// or ↑ if it is better, e.g. "█▅▂ ↓".
+fn format_trend(grades: &[Option<f64>]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let sparkline: String = grades
+        .iter()
+        .map(|grade| match grade {
+            Some(grade) => BARS[((6.0 - grade) / 5.0 * 7.0).round().clamp(0.0, 7.0) as usize],
+            None => ' ',
+        })
+        .collect();
+    let mut present = grades.iter().flatten();
+    let arrow = match (present.next(), present.last()) {
+        (Some(first), Some(last)) if last - first > 0.5 => " ↓",
+        (Some(first), Some(last)) if first - last > 0.5 => " ↑",
+        (Some(_), Some(_)) => " →",
+        _ => "",
+    };
+    format!("{}{arrow}", sparkline.trim_end())
+}
+
+/// Prints the trend of the class and of each student over the exams in the given order,
+/// e.g. the exams of a term.
+fn format_trends(exams: &[Exam]) -> String {
+    let (class_trend, trends) = grade_trends(exams);
+    let exam_names: Vec<&str> = exams.iter().map(|(exam, _, _)| exam.as_str()).collect();
+    let width = trends
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain([5])
+        .max()
+        .unwrap_or_default();
+    let line = |name: &str, grades: &[Option<f64>]| {
+        let values: Vec<String> = grades
+            .iter()
+            .map(|grade| grade.map(format_average).unwrap_or("-".to_string()))
+            .collect();
+        format!(
+            "{name:<width$}  {:<w$}  {}\n",
+            format_trend(grades),
+            values.join(" "),
+            w = exams.len() + 2
+        )
+    };
+
+    let mut out = format!("grade trend over {}\n\n", exam_names.join(", "));
+    out += &line("class", &class_trend);
+    for (name, grades) in &trends {
+        out += &line(name, grades);
+    }
+    out
+}
+
+/// Reports the problems of the config file and prints the effective configuration.
+pub fn check_config() -> Result<(), HeadlessError> {
+    let path = get_config_file().map_err(|e| HeadlessError::InvalidInput(e.to_string()))?;
+    let mut out = io::stdout().lock();
+    let (config, issues) = match fs::read_to_string(&path) {
+        Ok(content) => AppConfig::check(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            writeln!(
+                out,
+                "No config file at {}, using the defaults.",
+                path.display()
+            )?;
+            (Some(AppConfig::new()), Vec::new())
+        }
+        Err(e) => return Err(unreadable(&path, e)),
+    };
+
+    let mut issues: Vec<(PathBuf, ConfigIssue)> = issues
+        .into_iter()
+        .map(|issue| (path.clone(), issue))
+        .collect();
+    for local in local_config_files() {
+        writeln!(out, "Keys are overridden by {}", local.display())?;
+        let content = fs::read_to_string(&local).map_err(|e| unreadable(&local, e))?;
+        let (_, local_issues) = AppConfig::check(&content);
+        issues.extend(local_issues.into_iter().map(|issue| (local.clone(), issue)));
+    }
+    for name in env_variables() {
+        writeln!(out, "Key is overridden by ${name}")?;
+    }
+    if let Err(e) = check_env() {
+        let issue = ConfigIssue {
+            line: None,
+            message: e.to_string(),
+        };
+        issues.push((PathBuf::from("environment"), issue));
+    }
+
+    for (path, issue) in issues.iter() {
+        match issue.line {
+            Some(line) => writeln!(out, "{}:{line}: {}", path.display(), issue.message)?,
+            None => writeln!(out, "{}: {}", path.display(), issue.message)?,
+        }
+    }
+    // the merged config if the local configs are valid as well.
+    if let Some(config) = config.map(|config| AppConfig::read_config().unwrap_or(config)) {
+        writeln!(out, "\neffective configuration:")?;
+        for (key, value) in config.effective() {
+            writeln!(out, "  {key:<22} {value}")?;
+        }
+    }
+    match issues.len() {
+        0 => Ok(()),
+        1 => Err(HeadlessError::InvalidInput(format!(
+            "1 problem in {}",
+            issues[0].0.display()
+        ))),
+        count => Err(HeadlessError::InvalidInput(format!(
+            "{count} problems in the config"
+        ))),
+    }
+}
+
+/// Writes the commented default config, an existing config is only replaced with `force`.
+pub fn init_config(force: bool) -> Result<(), HeadlessError> {
+    let path = get_config_file().map_err(|e| HeadlessError::InvalidInput(e.to_string()))?;
+    if path.exists() && !force {
+        return Err(HeadlessError::InvalidArgs(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        )));
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, DEFAULT_CONFIG)?;
+    writeln!(io::stdout(), "Wrote {}", path.display())?;
+    Ok(())
+}
+
+/// Writes the student list of a new course and returns the path of the file.
+pub fn new_course(
+    class: &str,
+    students: Option<usize>,
+    from: Option<&Path>,
+) -> Result<PathBuf, HeadlessError> {
+    let names = match (students, from) {
+        (_, Some(path)) => {
+            let content = fs::read_to_string(path).map_err(|e| unreadable(path, e))?;
+            student_names(&content)
+        }
+        (Some(count), None) => (1..=count).map(|n| format!("Student {n:02}")).collect(),
+        (None, None) => Vec::new(),
+    };
+
+    let dir = AppConfig::read_config()
+        .unwrap_or_default()
+        .get_course_dir();
+    let path = dir.join(format!("{class}.csv"));
+    if path.exists() {
+        return Err(HeadlessError::InvalidArgs(format!(
+            "'{}' already exists.",
+            path.display()
+        )));
+    }
+    fs::create_dir_all(&dir)?;
+    StudentList::new(class, &names).save_to_file(&path)?;
+    writeln!(
+        io::stdout(),
+        "Created {} with {} students",
+        path.display(),
+        names.len()
+    )?;
+    Ok(path)
+}
+
+// one name per line, empty lines and duplicates are skipped.
+fn student_names(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in content
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::ModelAction;
+    use crate::cli::Parser;
+    use crate::model::scale::GradeScaleType;
+
+    #[test]
+    fn test_graded_line() {
+        let scale = GradingScale::from_type(GradeScaleType::IHK, 50.0).unwrap();
+        let line = GradedLine::parse(&scale, "46").unwrap();
+        assert_eq!(line.to_string(), "46;0.92;1");
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r#"{"name":null,"points":46.0,"percentage":0.92,"grade":1}"#
+        );
+        let line = GradedLine::parse(&scale, "Ben; 20,5").unwrap();
+        assert_eq!(line.to_string(), "Ben; 20,5;0.41;5");
+        assert_eq!(line.name, Some("Ben"));
+        assert!(GradedLine::parse(&scale, "Ben;x").is_err());
+        assert!(GradedLine::parse(&scale, "51").is_err());
+        // the raw numbers are rounded at the precision of the percentages.
+        let line = GradedLine::parse(&scale, "43.7").unwrap();
+        assert_eq!(line.percentage, 0.87);
+        assert!(serde_json::to_string(&line)
+            .unwrap()
+            .contains(r#""percentage":0.87,"#));
+        for line in ["-1", "Ben;-0,5", "nan", "Ben;NaN"] {
+            let e = GradedLine::parse(&scale, line).unwrap_err();
+            assert!(e.ends_with("points are out of range"), "{line}: {e}");
+        }
+    }
+
+    #[test]
+    fn test_write_json() {
+        let mut out = Vec::new();
+        let rows = [
+            ExamResultTableRowData::new("Anna \"A\"", 46.5, 0.93, 1),
+            ExamResultTableRowData::new("Ben", 0.0, 0.0, 6).with_absent(true),
+        ];
+        write_json(&mut out, &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"[{"name":"Anna \"A\"","points":46.5,"percentage":0.93,"grade":1,"absent":false,"percentile":null},{"name":"Ben","points":0.0,"percentage":0.0,"grade":null,"absent":true,"percentile":null}]"#.to_string() + "\n"
+        );
+
+        // the average of a class without students is NaN.
+        let mut out = Vec::new();
+        write_json(&mut out, &[f64::NAN, f64::INFINITY]).unwrap();
+        assert_eq!(out, b"[null,null]\n");
+        let stats = StatsSummary::new(&Model::new(), "IHK");
+        assert_eq!(stats.average_grade, None);
+        assert_eq!(stats.distribution.len(), 6);
+    }
+
+    #[test]
+    fn test_format_stats() {
+        let mut model = Model::new();
+        model.scale.set_max_points(50.0);
+        let names = ["Anna", "Ben", "Cem"].map(String::from);
+        model.set_student_list(StudentList::new("10a", &names));
+        for (name, points) in [("Anna", 46.0), ("Ben", 20.0)] {
+            model
+                .update(ModelAction::SetStudentPoints(name.into(), points))
+                .unwrap();
+        }
+        model
+            .update(ModelAction::ToggleStudentAbsent("Cem".into()))
+            .unwrap();
+
+        let stats = format_stats(&model, "IHK");
+        assert!(stats.starts_with("10a · IHK · 50 points\n"));
+        assert!(stats.contains("students        2 (1 absent)\n"));
+        assert!(stats.contains("average points  33\n"));
+        assert!(stats.contains("std dev points  13\n"));
+        assert!(stats.contains("range points    20 – 46 (26)\n"));
+        assert!(stats.contains("iqr points      26.5 – 39.5 (13)\n"));
+        assert!(stats.contains("shape           symmetric, results spread widely\n"));
+        assert!(stats.contains("pass rate       50% (1 of 2)\n"));
+        assert!(stats.contains("failed          1\n"));
+        assert!(stats.contains("warning         more than 33% failed"));
+        assert!(stats.contains("1      1     █\n"));
+        assert!(!stats.contains("weighted grade"));
+
+        let grades = vec![("Anna".to_string(), Some(3)), ("Ben".to_string(), Some(5))];
+        model.add_exam(WeightedExam::new("exam1", 1.0, grades));
+        let stats = format_stats(&model, "IHK");
+        assert!(stats.contains("weighted grade  3.5 over 2 exams\n"));
+    }
+
+    #[test]
+    fn test_exit_code() {
+        let broken_pipe = HeadlessError::Io(io::ErrorKind::BrokenPipe.into());
+        assert_eq!(broken_pipe.exit_code(), ExitCode::SUCCESS);
+        let invalid = HeadlessError::InvalidArgs("unknown scale".into());
+        assert_eq!(invalid.exit_code(), ExitCode::from(2));
+    }
+
+    #[test]
+    fn test_convert_roster() {
+        let args = Args::parse_from(["graca"]);
+        let dir = std::env::temp_dir().join("graca_test_convert");
+        fs::create_dir_all(&dir).unwrap();
+        let (roster, course) = (dir.join("10a.txt"), dir.join("10a.csv"));
+        fs::write(&roster, "Anna\nBen\n").unwrap();
+
+        convert(&args, &roster, &course).unwrap();
+        let students = StudentList::from_csv_file(&course).unwrap();
+        assert_eq!(students.class_name(), "10a");
+        assert_eq!(students.iter_students().count(), 2);
+        assert!(matches!(
+            convert(&args, &course, &dir.join("10a.pdf")),
+            Err(HeadlessError::InvalidArgs(_))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_convert_workbook() {
+        let args = Args::parse_from(["graca", "--points", "50"]);
+        let dir = std::env::temp_dir().join("graca_test_convert_workbook");
+        fs::create_dir_all(&dir).unwrap();
+        let (course, sheet, converted) = (
+            dir.join("10a.csv"),
+            dir.join("10a.xlsx"),
+            dir.join("10b.csv"),
+        );
+        fs::write(
+            &course,
+            "name,points,notes,absent\n\"Müller, Anna\",46.5,late,false\nBen,0,,true\n",
+        )
+        .unwrap();
+
+        // the sheet of the result export is read back with the absent students.
+        convert(&args, &course, &sheet).unwrap();
+        convert(&args, &sheet, &converted).unwrap();
+        let students = StudentList::from_csv_file(&converted).unwrap();
+        let anna = students.get_student("Müller, Anna").unwrap();
+        assert_eq!(anna.total(), 46.5);
+        assert!(students.get_student("Ben").unwrap().absent);
+        assert_eq!(students.iter_students().count(), 2);
+        assert_eq!(anna.notes, "");
+
+        // the notes are only written if the columns contain them.
+        let args = Args::parse_from(["graca", "--points", "50", "--columns", "grade,notes"]);
+        assert_eq!(
+            args.columns,
+            Some(vec![ExportColumn::Grade, ExportColumn::Notes])
+        );
+        convert(&args, &course, &sheet).unwrap();
+        let students = StudentList::from_xlsx_file(&sheet).unwrap();
+        assert_eq!(students.get_student("Müller, Anna").unwrap().notes, "late");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_exams() {
+        let dir = std::env::temp_dir().join("graca_test_load_exams");
+        fs::create_dir_all(&dir).unwrap();
+        let (exam1, exam2) = (dir.join("exam1.csv"), dir.join("exam2.csv"));
+        fs::write(&exam1, "name,points\nAnna,46\nBen,20\n").unwrap();
+        fs::write(&exam2, "name,points\nAnna,90\nBen,80\n").unwrap();
+        let config = AppConfig::default();
+
+        // each exam is graded out of its own max points.
+        let args = Args::parse_from([
+            "graca",
+            "--points",
+            "50,100",
+            "--weights",
+            "1,3",
+            "--exams",
+            exam2.to_str().unwrap(),
+        ]);
+        let model = load_course(&args, &config, &exam1).unwrap();
+        assert_eq!(model.scale.max_points(), 50.0);
+        assert_eq!(model.exam_count(), 2);
+        let rows = model.get_student_data();
+        assert_eq!(rows[0].cell(TableColumn::Average), "1.75");
+        assert_eq!(rows[1].cell(TableColumn::Average), "3.5");
+
+        // negative points are rejected like too many.
+        fs::write(&exam1, "name,points\nAnna,-1\n").unwrap();
+        let Err(HeadlessError::InvalidInput(e)) = load_course(&args, &config, &exam1) else {
+            panic!("negative points are out of range");
+        };
+        assert!(e.starts_with("-1 points of Anna are out of range"));
+        fs::write(&exam1, "name,points\nAnna,46\nBen,20\n").unwrap();
+
+        // the points of the second exam are more than the first max points.
+        let args = Args::parse_from([
+            "graca",
+            "--points",
+            "50",
+            "--exams",
+            exam2.to_str().unwrap(),
+        ]);
+        assert!(matches!(
+            load_course(&args, &config, &exam1),
+            Err(HeadlessError::InvalidInput(_))
+        ));
+        let args = Args::parse_from(["graca", "--points", "50,100,80"]);
+        assert!(matches!(
+            check_exams(&args, 2),
+            Err(HeadlessError::InvalidArgs(_))
+        ));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_gradebook() {
+        let exams = vec![
+            (
+                "exam1".to_string(),
+                2.0,
+                vec![
+                    ExamResultTableRowData::new("Anna", 46.0, 0.92, 1),
+                    ExamResultTableRowData::new("Ben", 20.0, 0.4, 5),
+                ],
+            ),
+            (
+                "exam2".to_string(),
+                1.0,
+                vec![
+                    ExamResultTableRowData::new("Anna", 40.0, 0.8, 2),
+                    ExamResultTableRowData::new("Cem", 30.0, 0.6, 4).with_absent(true),
+                ],
+            ),
+        ];
+        let (records, mismatches) = gradebook(&exams);
+        assert_eq!(
+            records[0],
+            [
+                "name",
+                "exam1 points",
+                "exam1 grade",
+                "exam2 points",
+                "exam2 grade",
+                "average",
+                "trend"
+            ]
+        );
+        assert_eq!(records[1], ["Anna", "46", "1", "40", "2", "1.33", "█▇ ↓"]);
+        assert_eq!(records[2], ["Ben", "20", "5", "", "", "5", "▂"]);
+        assert_eq!(records[3], ["Cem", "", "", "30", "-", "", ""]);
+        assert_eq!(
+            records[4],
+            ["average", "33", "3", "40", "2", "3.17", "▅▇ ↑"]
+        );
+        assert_eq!(
+            mismatches,
+            ["Ben is missing in exam2", "Cem is missing in exam1"]
+        );
+    }
+
+    #[test]
+    fn test_format_trend() {
+        assert_eq!(format_trend(&[Some(1.0), Some(3.4), Some(6.0)]), "█▅▁ ↓");
+        assert_eq!(format_trend(&[Some(2.0), None, Some(2.4)]), "▇ ▆ →");
+        assert_eq!(format_trend(&[None, Some(4.0)]), " ▄");
+        assert_eq!(format_trend(&[]), "");
+    }
+
+    #[test]
+    fn test_student_names() {
+        assert_eq!(
+            student_names("Anna\n\n  Ben \nAnna\n"),
+            vec!["Anna".to_string(), "Ben".to_string()]
+        );
+    }
+}
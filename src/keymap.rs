@@ -0,0 +1,251 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use strum_macros::Display;
+
+use crate::{action::Action, ui::AppTab};
+
+/// Time after which the possible follow-up keys of a sequence are shown.
+pub const HINT_DELAY: Duration = Duration::from_millis(500);
+
+/// Key sequences of the normal mode, the keys are separated by spaces.
+pub const CHORDS: [(&str, &str); 10] = [
+    ("g s", "go to scale tab"),
+    ("g r", "go to result tab"),
+    ("g p", "go to report tab"),
+    ("g t", "go to stats tab"),
+    ("space e c", "export scale as csv"),
+    ("space e t", "export scale as toml"),
+    ("space e x", "export scale as xlsx"),
+    ("space e r", "export results as xlsx"),
+    ("space o", "open student list"),
+    ("space w", "save student list"),
+];
+
+// the action of a complete sequence.
+fn chord_action(sequence: &str) -> Option<Action> {
+    match sequence {
+        "g s" => Some(Action::SwitchTab(AppTab::Scale)),
+        "g r" => Some(Action::SwitchTab(AppTab::Result)),
+        "g p" => Some(Action::SwitchTab(AppTab::Report)),
+        "g t" => Some(Action::SwitchTab(AppTab::Stats)),
+        "space e c" => Some(Action::ExportAs("csv".into())),
+        "space e t" => Some(Action::ExportAs("toml".into())),
+        "space e x" => Some(Action::ExportAs("xlsx".into())),
+        "space e r" => Some(Action::ExportResults(None)),
+        "space o" => Some(Action::OpenFilePicker),
+        "space w" => Some(Action::ExportTo(None)),
+        _ => None,
+    }
+}
+
+fn key_name(key: KeyCode) -> Option<String> {
+    match key {
+        KeyCode::Char(' ') => Some("space".into()),
+        KeyCode::Char(c) => Some(c.to_string()),
+        _ => None,
+    }
+}
+
+/// Keys of the tabs which only the vim preset uses to move around.
+const VIM_KEYS: [char; 2] = ['h', 'l'];
+
+/// The keys to move around in the tabs, chosen with `keymap` in the config.
+/// The other keys are the same in all presets.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum KeymapPreset {
+    #[default]
+    Default, // arrows, Home and End, j/k, gg and G
+    Vim,   // h and l in addition
+    Emacs, // Ctrl+n/p/f/b, Alt+< and Alt+>, Alt+x for the command line
+}
+
+impl KeymapPreset {
+    // the key as the tabs handle it, none if the key is not bound in this preset.
+    pub fn translate(&self, key: KeyEvent) -> Option<KeyEvent> {
+        let KeyCode::Char(c) = key.code else {
+            return Some(key);
+        };
+        let mapped = match (self, key.modifiers - KeyModifiers::SHIFT, c) {
+            (KeymapPreset::Vim, _, _) => return Some(key),
+            (KeymapPreset::Emacs, KeyModifiers::CONTROL, 'n') => KeyCode::Down,
+            (KeymapPreset::Emacs, KeyModifiers::CONTROL, 'p') => KeyCode::Up,
+            (KeymapPreset::Emacs, KeyModifiers::CONTROL, 'f') => KeyCode::Right,
+            (KeymapPreset::Emacs, KeyModifiers::CONTROL, 'b') => KeyCode::Left,
+            (KeymapPreset::Emacs, KeyModifiers::CONTROL, 'g') => KeyCode::Esc,
+            (KeymapPreset::Emacs, KeyModifiers::ALT, '<') => KeyCode::Home,
+            (KeymapPreset::Emacs, KeyModifiers::ALT, '>') => KeyCode::End,
+            (KeymapPreset::Emacs, KeyModifiers::ALT, 'x') => KeyCode::Char(':'),
+            (_, KeyModifiers::NONE, c) if VIM_KEYS.contains(&c) => return None,
+            _ => return Some(key),
+        };
+        Some(KeyEvent::from(mapped))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Chord {
+    Pending,
+    Matched(Action),
+    // the keys typed so far, which should be handled one by one.
+    Unmatched(Vec<KeyCode>),
+}
+
+/// State machine for the key sequences in `CHORDS`.
+#[derive(Debug, Default)]
+pub struct Keymap {
+    pending: Vec<KeyCode>,
+    started: Option<Instant>, // time of the first key of the sequence
+}
+
+impl Keymap {
+    pub fn push(&mut self, key: KeyCode) -> Chord {
+        if self.pending.is_empty() {
+            self.started = Some(Instant::now());
+        }
+        self.pending.push(key);
+        let sequence: Option<Vec<String>> = self.pending.iter().map(|&k| key_name(k)).collect();
+        let Some(sequence) = sequence.map(|keys| keys.join(" ")) else {
+            return Chord::Unmatched(std::mem::take(&mut self.pending));
+        };
+
+        if let Some(action) = chord_action(&sequence) {
+            self.pending.clear();
+            return Chord::Matched(action);
+        }
+        let prefix = format!("{sequence} ");
+        match CHORDS.iter().any(|(keys, _)| keys.starts_with(&prefix)) {
+            true => Chord::Pending,
+            false => Chord::Unmatched(std::mem::take(&mut self.pending)),
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    pub fn cancel(&mut self) {
+        self.pending.clear();
+    }
+
+    // true once the sequence is pending for longer than `HINT_DELAY`.
+    pub fn show_hints(&self) -> bool {
+        self.is_pending()
+            && self
+                .started
+                .is_some_and(|started| started.elapsed() >= HINT_DELAY)
+    }
+
+    // the possible next keys with the action they complete, or the number of
+    // sequences they lead to.
+    pub fn next_keys(&self) -> Vec<(String, String)> {
+        let prefix = format!("{} ", self.pending());
+        let mut next_keys: Vec<(&str, Option<&str>, usize)> = Vec::new();
+        for (sequence, description) in CHORDS.iter() {
+            let Some(rest) = sequence.strip_prefix(&prefix) else {
+                continue;
+            };
+            let (key, complete) = match rest.split_once(' ') {
+                Some((key, _)) => (key, None),
+                None => (rest, Some(*description)),
+            };
+            match next_keys.iter_mut().find(|(k, _, _)| *k == key) {
+                Some((_, _, count)) => *count += 1,
+                None => next_keys.push((key, complete, 1)),
+            }
+        }
+        next_keys
+            .into_iter()
+            .map(|(key, complete, count)| {
+                let description = complete
+                    .map(str::to_string)
+                    .unwrap_or(format!("+{count} more"));
+                (key.to_string(), description)
+            })
+            .collect()
+    }
+
+    // the sequence typed so far, e.g. `space e`.
+    pub fn pending(&self) -> String {
+        self.pending
+            .iter()
+            .filter_map(|&key| key_name(key))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chords() {
+        for (sequence, _) in CHORDS {
+            assert!(chord_action(sequence).is_some(), "{sequence}");
+        }
+
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.push(KeyCode::Char(' ')), Chord::Pending);
+        assert_eq!(keymap.push(KeyCode::Char('e')), Chord::Pending);
+        assert_eq!(keymap.pending(), "space e");
+        assert_eq!(
+            keymap.push(KeyCode::Char('c')),
+            Chord::Matched(Action::ExportAs("csv".into()))
+        );
+        assert!(!keymap.is_pending());
+
+        keymap.push(KeyCode::Char(' '));
+        assert_eq!(
+            keymap.next_keys(),
+            vec![
+                ("e".to_string(), "+4 more".to_string()),
+                ("o".to_string(), "open student list".to_string()),
+                ("w".to_string(), "save student list".to_string()),
+            ]
+        );
+        keymap.cancel();
+
+        // `gg` is no sequence, so both keys are handed back.
+        assert_eq!(keymap.push(KeyCode::Char('g')), Chord::Pending);
+        assert_eq!(
+            keymap.push(KeyCode::Char('g')),
+            Chord::Unmatched(vec![KeyCode::Char('g'), KeyCode::Char('g')])
+        );
+        assert_eq!(
+            keymap.push(KeyCode::Char('j')),
+            Chord::Unmatched(vec![KeyCode::Char('j')])
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let j = key(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(KeymapPreset::Vim.translate(j), Some(j));
+        assert_eq!(KeymapPreset::Default.translate(j), Some(j));
+        assert_eq!(KeymapPreset::Emacs.translate(j), Some(j));
+        let g = key(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(KeymapPreset::Default.translate(g), Some(g));
+        let h = key(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(KeymapPreset::Vim.translate(h), Some(h));
+        assert_eq!(KeymapPreset::Default.translate(h), None);
+        assert_eq!(KeymapPreset::Emacs.translate(h), None);
+
+        let down = KeyEvent::from(KeyCode::Down);
+        assert_eq!(KeymapPreset::Default.translate(down), Some(down));
+        let ctrl_n = key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert_eq!(KeymapPreset::Emacs.translate(ctrl_n), Some(down));
+        assert_eq!(KeymapPreset::Default.translate(ctrl_n), Some(ctrl_n));
+        assert_eq!(
+            KeymapPreset::Emacs.translate(key(
+                KeyCode::Char('<'),
+                KeyModifiers::ALT | KeyModifiers::SHIFT
+            )),
+            Some(KeyEvent::from(KeyCode::Home))
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use strum_macros::Display;
+
+use crate::model::exam::ExamDate;
+use crate::model::scale::round_dp;
+
+/// The locale of the config, set once on start.
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+pub fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// The decimal places of the config, set once on start.
+static PRECISION: OnceLock<Precision> = OnceLock::new();
+
+pub fn set_precision(precision: Precision) {
+    let _ = PRECISION.set(precision);
+}
+
+pub fn precision() -> Precision {
+    PRECISION.get().copied().unwrap_or_default()
+}
+
+/// Formats of dates and decimal numbers in the header, the tables and the exports.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En, // 1.5 and 2025-03-24
+    De, // 1,5 and 24.03.2025
+}
+
+impl Locale {
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::De => ',',
+        }
+    }
+
+    pub fn format_number(&self, value: f64) -> String {
+        value
+            .to_string()
+            .replace('.', &self.decimal_separator().to_string())
+    }
+
+    // rounds to at most `decimals` places, without trailing zeros.
+    pub fn format_decimal(&self, value: f64, decimals: usize) -> String {
+        self.format_number(round_dp(value, decimals))
+    }
+
+    pub fn format_date(&self, date: &ExamDate) -> String {
+        match self {
+            Locale::En => date.to_string(),
+            Locale::De => format!("{:02}.{:02}.{:04}", date.day, date.month, date.year),
+        }
+    }
+}
+
+/// Maximum number of decimals shown for percentages, points and average grades
+/// in the tables, the header and the exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Precision {
+    pub percentage: usize, // of the percent value, 0 shows 92%
+    pub points: usize,
+    pub average: usize,
+}
+
+impl Default for Precision {
+    fn default() -> Self {
+        Self {
+            percentage: 0,
+            points: 2,
+            average: 2,
+        }
+    }
+}
+
+// e.g. 46.5 points in the format of the locale.
+pub fn format_points(points: f64) -> String {
+    locale().format_decimal(points, precision().points)
+}
+
+// a share of the points like 0.925 as "93%".
+pub fn format_percentage(share: f64) -> String {
+    let percent = locale().format_decimal(share * 100.0, precision().percentage);
+    format!("{percent}%")
+}
+
+// a share of the points like 0.925 as "0.93", e.g. for table cells without the percent sign.
+pub fn format_share(share: f64) -> String {
+    locale().format_decimal(share, precision().percentage + 2)
+}
+
+// the average grade, "-" without any grades.
+pub fn format_average(average: f64) -> String {
+    match average.is_nan() {
+        true => "-".to_string(),
+        false => locale().format_decimal(average, precision().average),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let date: ExamDate = "2025-03-24".parse().unwrap();
+        assert_eq!(Locale::En.format_date(&date), "2025-03-24");
+        assert_eq!(Locale::De.format_date(&date), "24.03.2025");
+        assert_eq!(Locale::En.format_number(47.5), "47.5");
+        assert_eq!(Locale::De.format_number(47.5), "47,5");
+        assert_eq!(Locale::De.format_number(40.0), "40");
+        assert_eq!(Locale::En.format_decimal(2.3333, 2), "2.33");
+        assert_eq!(Locale::De.format_decimal(46.96, 1), "47");
+    }
+}
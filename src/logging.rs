@@ -1,4 +1,6 @@
 use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use color_eyre::eyre::Result;
 
@@ -7,21 +9,37 @@ use tracing_subscriber::{self, layer::SubscriberExt, util::SubscriberInitExt, La
 
 use crate::config::{get_data_dir, LOG_ENV, LOG_FILE};
 
-pub fn initialize_logging() -> Result<()> {
-    let directory = get_data_dir();
-    std::fs::create_dir_all(directory.clone())?;
-    let log_path = directory.join(LOG_FILE.clone());
-    // let log_file = std::fs::File::create(log_path)?;
+/// Log file given on the command line, which replaces the one in the data dir.
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+// the file the log is written to, e.g. for the log viewer.
+pub fn log_path() -> PathBuf {
+    LOG_PATH
+        .get()
+        .cloned()
+        .unwrap_or_else(|| get_data_dir().join(LOG_FILE.as_str()))
+}
+
+// `level` and `file` from the command line take precedence over the environment.
+pub fn initialize_logging(level: Option<&str>, file: Option<&Path>) -> Result<()> {
+    if let Some(file) = file {
+        let _ = LOG_PATH.set(file.to_path_buf());
+    }
+    let log_path = log_path();
+    if let Some(directory) = log_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(directory)?;
+    }
     let log_file = OpenOptions::new()
         .append(true)
         .create(true)
         .open(log_path)?;
-    std::env::set_var(
-        "RUST_LOG",
-        std::env::var("RUST_LOG")
+    let filter = match level {
+        Some(level) => format!("{}={level}", env!("CARGO_CRATE_NAME")),
+        None => std::env::var("RUST_LOG")
             .or_else(|_| std::env::var(LOG_ENV.clone()))
             .unwrap_or_else(|_| format!("{}=info", env!("CARGO_CRATE_NAME"))),
-    );
+    };
+    std::env::set_var("RUST_LOG", filter);
     let file_subscriber = tracing_subscriber::fmt::layer()
         .with_file(true)
         .with_line_number(true)
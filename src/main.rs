@@ -1,40 +1,114 @@
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+
 use logging::initialize_logging;
 
 pub use app::App;
-use cli::{Args, Parser};
-use tracing::{debug, info};
+
+use cli::{Args, Command, Parser};
+use tracing::{debug, error, info};
 
 mod action;
 mod app;
 mod cli;
+mod clipboard;
+mod commands;
 mod config;
 mod export;
+mod headless;
+mod keymap;
+mod locale;
 mod logging;
+mod man;
+mod recent;
 mod tui;
+mod wizard;
 
 mod model;
 mod ui;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // init logging
-    initialize_logging()?;
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    // parse args first, they can change the logging.
+    let mut args = Args::parse();
+    initialize_logging(args.log_level.as_deref(), args.log_file.as_deref())?;
     info!(
         "Starting {}::{}",
         env!("CARGO_PKG_NAME").to_uppercase(),
         env!("CARGO_PKG_VERSION")
     );
-
-    // parse args.
-    let args = Args::parse();
     debug!("ARGS: {:?}", &args);
 
+    if let Some(path) = args.config.clone() {
+        config::set_config_file(path);
+    }
+    // the course dir is taken from the config without the .graca.toml next to the course.
+    let user_config = config::AppConfig::read_config().unwrap_or_default();
+    for path in args.course_files_mut() {
+        *path = user_config.find_course_file(path);
+    }
+    if let Some(course) = args.course_file() {
+        config::set_course_file(course);
+    }
+
+    // fail early instead of falling back to the default config, check-config reports the problems
+    // itself and init-config writes the file.
+    if !matches!(
+        args.command,
+        Some(Command::CheckConfig | Command::InitConfig { .. })
+    ) {
+        config::check_env()?;
+        if args.config.is_some() {
+            config::AppConfig::read_config()?;
+        }
+    }
+
+    let config = config::AppConfig::read_config().unwrap_or_default();
+    locale::set_locale(config.get_locale());
+    model::scale::set_grade_format(config.get_grade_format());
+    locale::set_precision(config.get_precision());
+
+    // started bare for the first time, e.g. from a desktop entry.
+    let has_config = config::get_config_file().is_ok_and(|path| path.exists());
+    if std::env::args_os().len() == 1 && !has_config && io::stdin().is_terminal() {
+        wizard::run(&mut args, &mut io::stdin().lock(), &mut io::stdout())?;
+    }
+
+    if let Some(command) = &args.command {
+        match headless::run(command, &args) {
+            Ok(Some(course)) => args.course = Some(course),
+            Ok(None) => return Ok(ExitCode::SUCCESS),
+            Err(e) => {
+                error!("{command:?} failed: {e}");
+                eprintln!("{}: {e}", env!("CARGO_PKG_NAME"));
+                return Ok(e.exit_code());
+            }
+        }
+    }
+
+    let scale_type = args.scale.as_deref().map(config::load_scale).transpose()?;
+    let exams = match headless::other_exams(&args, &config) {
+        Ok(exams) => exams,
+        Err(e) => {
+            eprintln!("{}: {e}", env!("CARGO_PKG_NAME"));
+            return Ok(e.exit_code());
+        }
+    };
     let mut app = App::new()
-        .with_points(args.points)
+        .with_exam(args.exam())
+        .with_sort(args.sort)
+        .with_points(args.max_points())
+        .with_exams(args.weights.first().copied().unwrap_or(1.0), exams)
+        .with_half_points(args.half_points)
         .with_course(args.course)
-        .init();
+        .with_course_name(args.course_name)
+        .with_read_only(args.read_only)
+        .with_export_columns(args.columns.clone())
+        .with_watch(matches!(args.command, Some(Command::Watch { .. })))
+        .init()
+        .with_scale(scale_type);
 
     debug!("Debug mode active.");
     let _res = app.run();
     info!("Terminate app with {:?}", _res);
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,122 @@
+use clap::CommandFactory;
+
+use crate::cli::Args;
+use crate::ui::help::KEYBINDINGS;
+
+/// Renders the man page in roff format from the command line arguments and the keybindings.
+pub fn render() -> String {
+    let mut command = Args::command();
+    command.build();
+    let name = command.get_name().to_string();
+
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{name} {}\"\n",
+        name.to_uppercase(),
+        env!("CARGO_PKG_VERSION")
+    );
+    page += &format!(
+        ".SH NAME\n{name} \\- {}\n",
+        escape(
+            &command
+                .get_about()
+                .map(|about| about.to_string())
+                .unwrap_or_default()
+        )
+    );
+    page += &format!(
+        ".SH SYNOPSIS\n.B {name}\n[OPTIONS] [COURSE]\n.br\n.B {name}\n<COMMAND> [OPTIONS]\n"
+    );
+
+    page += ".SH OPTIONS\n";
+    for arg in command.get_arguments().filter(|arg| !arg.is_hide_set()) {
+        let flags: Vec<String> = arg
+            .get_short()
+            .map(|short| format!("\\-{short}"))
+            .into_iter()
+            .chain(arg.get_long().map(|long| format!("\\-\\-{}", escape(long))))
+            .collect();
+        let flags = match flags.is_empty() {
+            true => format!("<{}>", arg.get_id().as_str().to_uppercase()),
+            false => flags.join(", "),
+        };
+        let help = arg
+            .get_help()
+            .map(|help| help.to_string())
+            .unwrap_or_default();
+        page += &format!(".TP\n\\fB{flags}\\fR\n{}\n", escape(&help));
+    }
+
+    page += ".SH COMMANDS\n";
+    for subcommand in command.get_subcommands().filter(|cmd| !cmd.is_hide_set()) {
+        let about = subcommand
+            .get_about()
+            .map(|about| about.to_string())
+            .unwrap_or_default();
+        page += &format!(
+            ".TP\n\\fB{}\\fR\n{}\n",
+            escape(subcommand.get_name()),
+            escape(&about)
+        );
+    }
+
+    // the codes of headless::HeadlessError.
+    page += ".SH EXIT STATUS\n";
+    for (code, description) in [
+        ("0", "Success."),
+        ("1", "The output could not be written."),
+        ("2", "Invalid arguments, e.g. an unknown scale."),
+        (
+            "3",
+            "Invalid input, e.g. an unreadable csv file or points out of range.",
+        ),
+    ] {
+        page += &format!(".TP\n\\fB{code}\\fR\n{}\n", escape(description));
+    }
+
+    page += ".SH ENVIRONMENT\n";
+    for (name, description) in [
+        ("GRACA_CONFIG", "Directory of config.toml and the themes."),
+        ("GRACA_DATA", "Directory of the log and the recent files."),
+        ("GRACA_LOGLEVEL", "Log level like debug, replaced by --log-level."),
+        (
+            "GRACA_<KEY>",
+            "Overrides a key of the config, e.g. GRACA_DEFAULT_SCALE=LINEAR or GRACA_FULL_WIDTH=true. \
+             The keys of config.toml are overridden by a .graca.toml in the working directory or next to the course file, \
+             these by the environment and all of them by the command line arguments.",
+        ),
+    ] {
+        page += &format!(".TP\n\\fB{}\\fR\n{}\n", escape(name), escape(description));
+    }
+
+    page += ".SH KEYBINDINGS\n";
+    for group in KEYBINDINGS.iter() {
+        page += &format!(".SS {}\n", escape(group.title));
+        for (key, description) in group.keys {
+            page += &format!(".TP\n\\fB{}\\fR\n{}\n", escape(key), escape(description));
+        }
+    }
+    page
+}
+
+// escapes the characters with a special meaning in roff.
+fn escape(text: &str) -> String {
+    let text = text.replace('\\', "\\\\").replace('-', "\\-");
+    match text.starts_with(['.', '\'']) {
+        true => format!("\\&{text}"),
+        false => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let page = render();
+        assert!(page.starts_with(".TH GRACA 1"));
+        assert!(page.contains("\\fB\\-p, \\-\\-points\\fR"));
+        assert!(page.contains(".SS Result tab"));
+        assert_eq!(escape(".5 points"), "\\&.5 points");
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::locale::locale;
+
+/// Name and date of the exam, shown in the header and written to the exports.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExamInfo {
+    pub name: Option<String>,
+    pub date: Option<ExamDate>,
+}
+
+impl ExamInfo {
+    // e.g. "Exam 1 · 2025-03-24" in the date format of the locale, empty if nothing is set.
+    pub fn label(&self) -> String {
+        let date = self.date.map(|date| locale().format_date(&date));
+        [self.name.clone(), date]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<String>>()
+            .join(" · ")
+    }
+}
+
+/// The grades of another exam of the course, they count with `weight` in the
+/// weighted average of each student.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedExam {
+    pub name: String,
+    pub weight: f64,
+    grades: Vec<(String, Option<u8>)>, // absent students have no grade
+}
+
+impl WeightedExam {
+    pub fn new(name: &str, weight: f64, grades: Vec<(String, Option<u8>)>) -> Self {
+        Self {
+            name: name.to_string(),
+            weight,
+            grades,
+        }
+    }
+
+    // the grade of the student, none if absent or not part of this exam.
+    pub fn grade(&self, name: &str) -> Option<u8> {
+        self.grades
+            .iter()
+            .find(|(student, _)| student == name)
+            .and_then(|(_, grade)| *grade)
+    }
+}
+
+/// A calendar date without time, written as `2025-03-24` or `24.03.2025`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExamDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl FromStr for ExamDate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{value}' is no date like 2025-03-24 or 24.03.2025");
+        let parts: Vec<&str> = value.trim().split(['-', '.']).collect();
+        let [year, month, day] = match (value.contains('-'), parts.as_slice()) {
+            (true, [year, month, day]) => [year, month, day],
+            (false, [day, month, year]) => [year, month, day],
+            _ => return Err(invalid()),
+        };
+        let date = ExamDate {
+            year: year.parse().map_err(|_| invalid())?,
+            month: month.parse().map_err(|_| invalid())?,
+            day: day.parse().map_err(|_| invalid())?,
+        };
+        match (1..=12).contains(&date.month) && (1..=date.days_in_month()).contains(&date.day) {
+            true => Ok(date),
+            false => Err(invalid()),
+        }
+    }
+}
+
+impl ExamDate {
+    fn days_in_month(&self) -> u8 {
+        let leap_year = self.year.is_multiple_of(4)
+            && (!self.year.is_multiple_of(100) || self.year.is_multiple_of(400));
+        match self.month {
+            2 if leap_year => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for ExamDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        let date = ExamDate {
+            year: 2025,
+            month: 3,
+            day: 24,
+        };
+        assert_eq!("2025-03-24".parse(), Ok(date));
+        assert_eq!("24.03.2025".parse(), Ok(date));
+        assert_eq!(date.to_string(), "2025-03-24");
+        assert!("2025-02-29".parse::<ExamDate>().is_err());
+        assert!("2024-02-29".parse::<ExamDate>().is_ok());
+        assert!("24/03/2025".parse::<ExamDate>().is_err());
+    }
+}
@@ -1,21 +1,59 @@
+pub mod exam;
 pub mod scale;
+pub mod stats;
 pub mod students;
 
 use std::{collections::HashMap, path::Path};
 
-use scale::{round_dp, Grade, GradeScaleType, GradingScale};
+use exam::{ExamInfo, WeightedExam};
+use scale::{round_dp, Grade, GradeScaleType, GradingError, GradingScale, PASS_GRADE};
+use stats::{percentile_rank, weighted_average, BoxPlotSummary, Statistics};
 use students::StudentList;
-use tracing::debug;
 
 use crate::{
     action::ModelAction,
-    ui::{scale_tab::GradingScaleTableRowData, students_tab::ExamResultTableRowData},
+    locale::precision,
+    ui::{
+        scale_tab::GradingScaleTableRowData, student_pane::StudentDetail,
+        students_tab::ExamResultTableRowData,
+    },
 };
 
-#[derive(Debug, Default)]
+/// Maximum number of model changes which can be undone.
+const HISTORY_SIZE: usize = 100;
+
+/// Share of failed students above which many regulations require a justification.
+pub const FAIL_LIMIT: f64 = 1.0 / 3.0;
+
+/// Largest shift of the thresholds tried by a curve, in percent of the points.
+const MAX_CURVE_SHIFT: i32 = 30;
+
+// state of the model which can be restored by undo/redo.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    scale: GradingScale,
+    student_list: StudentList,
+    unsaved_changes: bool,
+}
+
+#[derive(Debug)]
 pub struct Model {
     pub scale: GradingScale,
+    pub exam: ExamInfo,
+    pub pass_grade: Grade,    // worst grade which still counts as passed
+    pub fail_limit: f64,      // share of failed students which is still fine
+    pub weight: f64,          // of this exam in the weighted average
+    exams: Vec<WeightedExam>, // the other exams of the course
     student_list: StudentList,
+    unsaved_changes: bool,
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Model {
@@ -24,42 +62,129 @@ impl Model {
 
         Self {
             scale,
+            exam: ExamInfo::default(),
+            pass_grade: PASS_GRADE,
+            fail_limit: FAIL_LIMIT,
+            weight: 1.0,
+            exams: Vec::new(),
             student_list: StudentList::default(),
+            unsaved_changes: false,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
         }
     }
 
     pub fn load_student_data(&mut self, path: &Path) -> std::io::Result<()> {
-        self.student_list = StudentList::from_csv_file(path)?;
+        self.set_student_list(StudentList::from_file(path)?);
         Ok(())
     }
 
-    pub fn save_student_data(&self, path: &Path) -> std::io::Result<()> {
+    // replaces the students, e.g. with a list loaded in the background.
+    pub fn set_student_list(&mut self, student_list: StudentList) {
+        self.student_list = student_list;
+        self.unsaved_changes = false;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    pub fn save_student_data(&mut self, path: &Path) -> std::io::Result<()> {
         self.student_list.save_to_file(path)?;
+        self.unsaved_changes = false;
         Ok(())
     }
 
-    pub fn update(&mut self, action: ModelAction) {
+    // returns true if the student data was changed since the last load or save.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            scale: self.scale.clone(),
+            student_list: self.student_list.clone(),
+            unsaved_changes: self.unsaved_changes,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.scale = snapshot.scale;
+        self.student_list = snapshot.student_list;
+        self.unsaved_changes = snapshot.unsaved_changes;
+    }
+
+    // undo the last change. returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some(snapshot) = self.undo_stack.pop() {
+            self.redo_stack.push(self.snapshot());
+            self.restore(snapshot);
+            true
+        } else {
+            false
+        }
+    }
+
+    // redo the last undone change. returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if let Some(snapshot) = self.redo_stack.pop() {
+            self.undo_stack.push(self.snapshot());
+            self.restore(snapshot);
+            true
+        } else {
+            false
+        }
+    }
+
+    // returns the number of changes which were undone and can be redone.
+    pub fn undone_steps(&self) -> usize {
+        self.redo_stack.len()
+    }
+
+    pub fn update(&mut self, action: ModelAction) -> Result<(), GradingError> {
+        // failed changes leave no entry in the history.
+        let snapshot = self.snapshot();
+        self.apply(action)?;
+        // no-ops like setting the same points keep the redo steps.
+        if self.scale == snapshot.scale && self.student_list == snapshot.student_list {
+            self.unsaved_changes = snapshot.unsaved_changes;
+            return Ok(());
+        }
+
+        if self.undo_stack.len() == HISTORY_SIZE {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(snapshot);
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    fn apply(&mut self, action: ModelAction) -> Result<(), GradingError> {
         match action {
             ModelAction::IncrementThreshold(grade) => {
-                if let Ok(grade) = Grade::try_from(grade) {
-                    self.scale
-                        .increment_points_for_grade(grade)
-                        .expect("Grade not found");
-                }
+                self.scale
+                    .increment_points_for_grade(Grade::try_from(grade)?)?;
             }
             ModelAction::DecrementThreshold(grade) => {
-                if let Ok(grade) = Grade::try_from(grade) {
-                    self.scale
-                        .decrement_points_for_grade(grade)
-                        .expect("Grade not found");
+                self.scale
+                    .decrement_points_for_grade(Grade::try_from(grade)?)?;
+            }
+            ModelAction::SetThreshold(grade, points) => {
+                self.scale
+                    .set_points_for_grade(Grade::try_from(grade)?, points)?;
+            }
+            ModelAction::SetMaxPoints(points) => {
+                if points <= 0.0 {
+                    return Err(GradingError::InvalidPoints(points));
                 }
+                self.scale.set_max_points(points);
             }
-            ModelAction::SetMaxPoints(points) => self.scale.set_max_points(points as f64),
             ModelAction::SetScale(value) => {
                 if let Ok(scale_type) = GradeScaleType::try_from(value) {
                     self.scale.change_scale_type(scale_type);
                 }
             }
+            ModelAction::SetCustomScale(values) => {
+                self.scale.change_scale_type(GradeScaleType::Custom(values));
+            }
             ModelAction::ToggleHalfPoints => {
                 self.scale.toggle_half_points();
             }
@@ -69,6 +194,12 @@ impl Model {
             ModelAction::DecrementMaxPoints => {
                 self.scale.set_max_points(self.scale.max_points() - 1.0);
             }
+            ModelAction::ResetScale => self.scale.recalculate(),
+            ModelAction::DeleteStudent(name) => {
+                if self.student_list.remove_student(&name).is_some() {
+                    self.unsaved_changes = true;
+                }
+            }
             ModelAction::IncrementStudentPoints(name) => {
                 if let Some(student) = self.student_list.get_student_mut(&name) {
                     let new_value = match self.scale.is_using_half_points() {
@@ -78,6 +209,7 @@ impl Model {
 
                     if new_value <= self.scale.max_points() {
                         student.update_points(new_value);
+                        self.unsaved_changes = true;
                     }
                 }
             }
@@ -90,10 +222,57 @@ impl Model {
 
                     if new_value <= self.scale.max_points() {
                         student.update_points(new_value);
+                        self.unsaved_changes = true;
                     }
                 }
             }
+            ModelAction::SetStudentPoints(name, points) => {
+                let max_points = self.scale.max_points();
+                if !(0.0..=max_points).contains(&points) {
+                    return Err(GradingError::PointsOutOfRange(points, 0.0, max_points));
+                }
+                if let Some(student) = self.student_list.get_student_mut(&name) {
+                    student.update_points(points);
+                    self.unsaved_changes = true;
+                }
+            }
+            ModelAction::SetStudentNotes(name, notes) => {
+                if let Some(student) = self.student_list.get_student_mut(&name) {
+                    student.notes = notes.trim().to_string();
+                    self.unsaved_changes = true;
+                }
+            }
+            ModelAction::ToggleStudentAbsent(name) => {
+                if let Some(student) = self.student_list.get_student_mut(&name) {
+                    student.absent = !student.absent;
+                    self.unsaved_changes = true;
+                }
+            }
+            // the name is not part of the student list file.
+            ModelAction::RenameCourse(name) => {
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(GradingError::InvalidName(name.to_string()));
+                }
+                if name != self.student_list.class_name() {
+                    self.student_list.set_class_name(name);
+                    self.unsaved_changes = true;
+                }
+            }
+            ModelAction::RenameStudent(name, new_name) => {
+                if new_name == name {
+                    return Ok(());
+                }
+                if new_name.is_empty() || self.student_list.get_student(&new_name).is_some() {
+                    return Err(GradingError::InvalidName(new_name));
+                }
+                if let Some(student) = self.student_list.get_student_mut(&name) {
+                    student.name = new_name;
+                    self.unsaved_changes = true;
+                }
+            }
         }
+        Ok(())
     }
 
     pub fn get_scale_data(&self) -> Vec<GradingScaleTableRowData> {
@@ -119,11 +298,25 @@ impl Model {
             .collect()
     }
 
+    pub fn set_class_name(&mut self, name: &str) {
+        self.student_list.set_class_name(name);
+    }
+
+    // the course and the exam, e.g. for the header and the exports.
+    pub fn title(&self) -> String {
+        [self.get_class_name().to_string(), self.exam.label()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<String>>()
+            .join(" · ")
+    }
+
     pub fn get_class_name(&self) -> &str {
         self.student_list.class_name()
     }
 
     pub fn get_student_data(&self) -> Vec<ExamResultTableRowData> {
+        let present = self.points();
         let mut data = Vec::new();
         for student in self.student_list.iter_students() {
             let points = student.total();
@@ -135,16 +328,140 @@ impl Model {
                     Some(grade) => grade.to_number(),
                     None => 0,
                 },
-            );
+            )
+            .with_absent(student.absent)
+            .with_notes(&student.notes)
+            .with_average(self.weighted_average(&student.name, self.student_grade(student)))
+            .with_percentile((!student.absent).then(|| percentile_rank(&present, points)));
             data.push(row);
         }
         data
     }
 
+    pub fn get_student_detail(&self, name: &str) -> Option<StudentDetail> {
+        let student = self.student_list.get_student(name)?;
+        let points = student.total();
+        let grade = student.grade(&self.scale);
+        let thresholds = self.scale.thresholds();
+        let next_grade = grade.next_better().and_then(|better| {
+            let min = thresholds.get(&better)?;
+            Some((better.to_number(), min - points))
+        });
+
+        Some(StudentDetail {
+            name: student.name.clone(),
+            points,
+            percentage: GradingScale::percentage_for_points(points, self.scale.max_points()),
+            grade: grade.to_number(),
+            next_grade,
+            percentile: (!student.absent).then(|| percentile_rank(&self.points(), points)),
+            notes: student.notes.clone(),
+            absent: student.absent,
+        })
+    }
+
+    pub fn points(&self) -> Vec<f64> {
+        self.student_list
+            .iter_present()
+            .map(|s| s.total())
+            .collect()
+    }
+
+    // names and points of the present students, best first and equal points by name.
+    pub fn ranking(&self) -> Vec<(String, f64)> {
+        let mut ranking: Vec<(String, f64)> = self
+            .student_list
+            .iter_present()
+            .map(|student| (student.name.clone(), student.total()))
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranking
+    }
+
+    pub fn points_summary(&self) -> Option<BoxPlotSummary> {
+        BoxPlotSummary::from_values(&self.points())
+    }
+
+    // median, mode and spread of the points of all present students.
+    pub fn points_stats(&self) -> Option<Statistics> {
+        Statistics::from_values(&self.points())
+    }
+
+    // the same for the grades, as numbers from 1 to 6.
+    pub fn grade_stats(&self) -> Option<Statistics> {
+        let grades: Vec<f64> = self
+            .student_list
+            .iter_present()
+            .map(|student| student.grade(&self.scale).to_number().into())
+            .collect();
+        Statistics::from_values(&grades)
+    }
+
     pub fn grade_distribution(&self) -> HashMap<u8, usize> {
+        self.distribution(&self.scale)
+    }
+
+    // returns the outcome if the given scale would be used instead, the active scale is kept.
+    pub fn what_if(&self, scale_type: GradeScaleType) -> Option<ScaleOutcome> {
+        let scale = GradingScale::from_type(scale_type, self.scale.max_points()).ok()?;
+        let (passed, total) = self.passed_count_for(&scale);
+        let distribution = self.distribution(&scale);
+        Some(ScaleOutcome {
+            average: distribution_average(&distribution),
+            pass_rate: (total > 0).then(|| passed as f64 / total as f64),
+            distribution,
+        })
+    }
+
+    // shifts the current minimum of every grade but 6 by the same share of the points, so
+    // the average grade comes as close as possible to the target. The smallest shift wins
+    // between shifts with the same average, none without present students.
+    pub fn curve(&self, target: f64) -> Option<Curve> {
+        // the thresholds may be edited, so they can differ from the scale type.
+        let thresholds = self.scale.thresholds();
+        let values = self.scale.scale_type().values().map(|(grade, share)| {
+            let points = Grade::try_from(grade)
+                .ok()
+                .and_then(|grade| thresholds.get(&grade));
+            let share = points.map_or(share, |points| points / self.scale.max_points());
+            (grade, share)
+        });
+        let mut best: Option<Curve> = None;
+        for step in -MAX_CURVE_SHIFT..=MAX_CURVE_SHIFT {
+            let shift = step as f64 / 100.0;
+            let shifted = values.map(|(grade, share)| match grade {
+                6 => (grade, share),
+                _ => (grade, round_dp(share + shift, 4).clamp(0.01, 1.0)),
+            });
+            // clamped minimums may collide, a better grade needs a higher minimum.
+            if shifted.windows(2).any(|pair| pair[0].1 <= pair[1].1) {
+                continue;
+            }
+            let scale_type = GradeScaleType::Custom(shifted);
+            let Some(outcome) = self.what_if(scale_type).filter(|o| !o.average.is_nan()) else {
+                continue;
+            };
+            let distance = (outcome.average - target).abs();
+            let better = best.as_ref().is_none_or(|best| {
+                let best_distance = (best.outcome.average - target).abs();
+                distance < best_distance
+                    || (distance == best_distance && shift.abs() < best.shift.abs())
+            });
+            if better {
+                best = Some(Curve {
+                    scale_type,
+                    shift,
+                    outcome,
+                });
+            }
+        }
+        best
+    }
+
+    fn distribution(&self, scale: &GradingScale) -> HashMap<u8, usize> {
         let mut counts = HashMap::new();
-        for student in self.student_list.iter_students() {
-            let grade = student.grade(&self.scale); // returns a u8
+        for student in self.student_list.iter_present() {
+            let grade = student.grade(scale); // returns a u8
             counts
                 .entry(grade.to_number())
                 .and_modify(|counter| *counter += 1)
@@ -154,17 +471,252 @@ impl Model {
         counts
     }
 
+    // names of the students with the given grade in alphabetical order.
+    pub fn students_with_grade(&self, grade: u8) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .student_list
+            .iter_present()
+            .filter(|student| student.grade(&self.scale).to_number() == grade)
+            .map(|student| student.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    // returns the number of students at or above the pass grade and the number of all students.
+    pub fn passed_count(&self) -> (usize, usize) {
+        self.passed_count_for(&self.scale)
+    }
+
+    fn passed_count_for(&self, scale: &GradingScale) -> (usize, usize) {
+        let total = self.student_list.iter_present().count();
+        let passed = self
+            .student_list
+            .iter_present()
+            .filter(|student| student.grade(scale) <= self.pass_grade)
+            .count();
+        (passed, total)
+    }
+
+    // the number of present students below the pass grade.
+    pub fn failed_count(&self) -> usize {
+        let (passed, total) = self.passed_count();
+        total - passed
+    }
+
+    // the share of present students who passed, none without any.
+    pub fn pass_rate(&self) -> Option<f64> {
+        match self.passed_count() {
+            (_, 0) => None,
+            (passed, total) => Some(passed as f64 / total as f64),
+        }
+    }
+
+    // more students failed than the fail limit allows.
+    pub fn fail_limit_exceeded(&self) -> bool {
+        match self.passed_count() {
+            (_, 0) => false,
+            (passed, total) => (total - passed) as f64 / total as f64 > self.fail_limit,
+        }
+    }
+
     pub fn grade_average(&self) -> f64 {
-        let mut grades_weighted = 0;
-        let mut total_count = 0;
+        distribution_average(&self.grade_distribution())
+    }
 
-        for (grade, count) in self.grade_distribution() {
-            if (1..=6).contains(&grade) {
-                total_count += count;
-                grades_weighted += (grade as usize) * count;
-            }
+    pub fn add_exam(&mut self, exam: WeightedExam) {
+        self.exams.push(exam);
+    }
+
+    // the number of exams in the weighted average, with this one.
+    pub fn exam_count(&self) -> usize {
+        self.exams.len() + 1
+    }
+
+    // the grades of this exam, to be averaged with another one.
+    pub fn to_weighted_exam(&self, name: &str) -> WeightedExam {
+        let grades = self
+            .student_list
+            .iter_students()
+            .map(|student| (student.name.clone(), self.student_grade(student)))
+            .collect();
+        WeightedExam::new(name, self.weight, grades)
+    }
+
+    // the grade as number, none for absent students.
+    fn student_grade(&self, student: &students::Student) -> Option<u8> {
+        (!student.absent).then(|| student.grade(&self.scale).to_number())
+    }
+
+    // the average of the grades of the student in all exams by their weight, none
+    // without other exams or if the student has no grade in any of them.
+    fn weighted_average(&self, name: &str, grade: Option<u8>) -> Option<f64> {
+        if self.exams.is_empty() {
+            return None;
+        }
+        let grades: Vec<(f64, f64)> = std::iter::once((grade, self.weight))
+            .chain(
+                self.exams
+                    .iter()
+                    .map(|exam| (exam.grade(name), exam.weight)),
+            )
+            .filter_map(|(grade, weight)| Some((grade? as f64, weight)))
+            .collect();
+        weighted_average(&grades)
+    }
+
+    // the mean of the weighted averages of the students.
+    pub fn weighted_class_average(&self) -> Option<f64> {
+        let averages: Vec<f64> = self
+            .student_list
+            .iter_students()
+            .filter_map(|student| self.weighted_average(&student.name, self.student_grade(student)))
+            .collect();
+        Statistics::from_values(&averages).map(|stats| stats.mean)
+    }
+
+    // the first student with negative points or more than the max points, e.g. from a
+    // course file of another exam.
+    pub fn points_out_of_range(&self) -> Option<(String, f64)> {
+        self.student_list
+            .iter_students()
+            .find(|student| !(0.0..=self.scale.max_points()).contains(&student.total()))
+            .map(|student| (student.name.clone(), student.total()))
+    }
+}
+
+/// Grade distribution, average grade and pass rate of the class under another scale.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ScaleOutcome {
+    pub distribution: HashMap<u8, usize>,
+    pub average: f64,
+    pub pass_rate: Option<f64>, // none without present students
+}
+
+/// A scale with shifted thresholds, suggested to reach a target average grade.
+/// A negative `shift` lowers the minimum points of the grades.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub scale_type: GradeScaleType,
+    pub shift: f64, // share of the max points
+    pub outcome: ScaleOutcome,
+}
+
+// the average grade of a grade distribution, NaN if it is empty.
+fn distribution_average(distribution: &HashMap<u8, usize>) -> f64 {
+    let mut grades_weighted = 0;
+    let mut total_count = 0;
+
+    for (&grade, &count) in distribution {
+        if (1..=6).contains(&grade) {
+            total_count += count;
+            grades_weighted += (grade as usize) * count;
         }
+    }
+
+    // rounded like the averages are shown, NaN without grades.
+    round_dp(
+        grades_weighted as f64 / total_count as f64,
+        precision().average,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_what_if() {
+        let mut model = Model::new();
+        model.scale.set_max_points(100.0);
+        let names = ["Anna", "Ben"].map(String::from);
+        model.set_student_list(StudentList::new("10a", &names));
+        for (name, points) in [("Anna", 95.0), ("Ben", 45.0)] {
+            model
+                .update(ModelAction::SetStudentPoints(name.into(), points))
+                .unwrap();
+        }
+        let scale_type = *model.scale.scale_type();
+
+        let outcome = model.what_if(GradeScaleType::LINEAR).unwrap();
+        assert_eq!(outcome.distribution.values().sum::<usize>(), 2);
+        assert_eq!(outcome.pass_rate, Some(1.0));
+        assert_eq!(model.pass_rate(), Some(0.5));
+        assert!(model.fail_limit_exceeded());
+        model.fail_limit = 0.5;
+        assert!(!model.fail_limit_exceeded());
+        // the active scale is not changed.
+        assert_eq!(*model.scale.scale_type(), scale_type);
+    }
+
+    #[test]
+    fn test_curve() {
+        let mut model = Model::new();
+        model.scale.set_max_points(100.0);
+        let names = ["Anna", "Ben", "Cem"].map(String::from);
+        model.set_student_list(StudentList::new("10a", &names));
+        for (name, points) in [("Anna", 85.0), ("Ben", 55.0), ("Cem", 45.0)] {
+            model
+                .update(ModelAction::SetStudentPoints(name.into(), points))
+                .unwrap();
+        }
+        // the current average of 2, 4 and 5 is already closest to 11/3.
+        let curve = model.curve(model.grade_average()).unwrap();
+        assert_eq!(curve.shift, 0.0);
+
+        let curve = model.curve(3.0).unwrap();
+        assert!(curve.shift < 0.0);
+        assert_eq!(curve.outcome.average, 3.0);
+        let GradeScaleType::Custom(values) = curve.scale_type else {
+            panic!("a curve is a custom scale");
+        };
+        assert_eq!(values[5], (6, 0.0));
+
+        // Cem gets a 4 with the edited scale, which is kept without a shift.
+        model.update(ModelAction::SetThreshold(4, 45.0)).unwrap();
+        assert_eq!(model.grade_average(), 3.33);
+        let curve = model.curve(model.grade_average()).unwrap();
+        assert_eq!(curve.shift, 0.0);
+        let GradeScaleType::Custom(values) = curve.scale_type else {
+            panic!("a curve is a custom scale");
+        };
+        assert_eq!(values[3], (4, 0.45));
+        assert_eq!(values[0], (1, 0.92));
+
+        assert_eq!(Model::new().curve(3.0), None);
+    }
+
+    #[test]
+    fn test_undo_skips_no_ops() {
+        let mut model = Model::new();
+        let names = ["Anna"].map(String::from);
+        model.set_student_list(StudentList::new("10a", &names));
+        model
+            .update(ModelAction::SetStudentPoints("Anna".into(), 40.0))
+            .unwrap();
+        model
+            .update(ModelAction::SetStudentPoints("Anna".into(), 50.0))
+            .unwrap();
+        assert!(model.undo());
+        assert_eq!(model.undone_steps(), 1);
+
+        // the same points again change nothing, so the redo step is kept.
+        model
+            .update(ModelAction::SetStudentPoints("Anna".into(), 40.0))
+            .unwrap();
+        assert_eq!(model.undone_steps(), 1);
+        assert!(model.undo());
+        assert!(!model.undo());
 
-        round_dp(grades_weighted as f64 / total_count as f64, 2)
+        // a new course name has to be saved.
+        model
+            .update(ModelAction::RenameCourse("10a".into()))
+            .unwrap();
+        assert!(!model.has_unsaved_changes());
+        model
+            .update(ModelAction::RenameCourse("IT 23 b".into()))
+            .unwrap();
+        assert!(model.has_unsaved_changes());
+        assert_eq!(model.title(), "IT 23 b");
     }
 }
@@ -1,9 +1,13 @@
 use std::collections::BTreeMap;
+use std::sync::OnceLock;
 
 use serde::Deserialize;
-use strum_macros::{EnumIter, EnumString};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
 
-use tracing::{debug, info};
+use tracing::{debug, trace};
+
+use crate::locale::{locale, precision, Locale};
 
 const IHK_BOUNDARIES: [(u8, f64); 6] = [
     (1, 0.92),
@@ -26,7 +30,8 @@ const LINEAR_BOUNDARIES: [(u8, f64); 6] = [
     (6, 0.0),
 ];
 
-#[derive(Debug, Default, Clone, Copy, Deserialize, EnumIter)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, EnumIter)]
 pub enum GradeScaleType {
     #[default]
     IHK,
@@ -50,6 +55,41 @@ impl TryFrom<u8> for GradeScaleType {
 }
 
 impl GradeScaleType {
+    // the builtin scale with this name, ignoring the case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        GradeScaleType::iter()
+            .filter(|scale_type| !scale_type.is_custom())
+            .find(|scale_type| scale_type.text().eq_ignore_ascii_case(name))
+    }
+
+    // a custom scale from a toml file, which maps each grade to its minimum share
+    // of the points, e.g. `1 = 0.92`.
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let shares: BTreeMap<String, f64> = toml::from_str(content).map_err(|e| e.to_string())?;
+        GradeScaleType::from_shares(&shares)
+    }
+
+    // a custom scale from the minimum share of each grade, keyed by the grade.
+    pub fn from_shares(shares: &BTreeMap<String, f64>) -> Result<Self, String> {
+        let mut values = [(0, 0.0); 6];
+        for (index, value) in values.iter_mut().enumerate() {
+            let grade = index as u8 + 1;
+            let share = *shares
+                .get(&grade.to_string())
+                .ok_or(format!("the minimum of grade {grade} is missing"))?;
+            if !(0.0..=1.0).contains(&share) {
+                return Err(format!(
+                    "the minimum of grade {grade} must be between 0 and 1"
+                ));
+            }
+            *value = (grade, share);
+        }
+        if values.windows(2).any(|pair| pair[0].1 <= pair[1].1) {
+            return Err("a better grade needs a higher minimum".to_string());
+        }
+        Ok(GradeScaleType::Custom(values))
+    }
+
     // return the boundary values for a scale.
     pub fn values(&self) -> [(u8, f64); 6] {
         match self {
@@ -86,7 +126,7 @@ impl GradeScaleType {
     }
 
     // Convert to a custom scale
-    pub fn to_custom(&self) -> GradeScaleType {
+    pub fn to_custom(self) -> GradeScaleType {
         GradeScaleType::Custom(self.values())
     }
 
@@ -103,13 +143,44 @@ impl GradeScaleType {
     }
 }
 
+/// Parses the max points of an exam, which may use half points, e.g. `47.5` or `47,5`.
+/// `NaN`, infinite and negative points are no valid max points.
+pub fn parse_max_points(value: &str) -> Result<f64, GradingError> {
+    let value = value.trim();
+    let points: f64 = value
+        .replace(',', ".")
+        .parse()
+        .map_err(|_| GradingError::NotANumber(value.to_string()))?;
+    if !points.is_finite() || points <= 0.0 || (points * 2.0).fract() != 0.0 {
+        return Err(GradingError::InvalidPoints(points));
+    }
+    Ok(points)
+}
+
 #[derive(Debug)]
 pub enum GradingError {
     InvalidGrade(u8),
     InvalidPoints(f64),
+    NotANumber(String),
+    PointsOutOfRange(f64, f64, f64), // points, min, max
+    InvalidName(String),
 }
 
-#[derive(Debug, Default)]
+impl std::fmt::Display for GradingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GradingError::InvalidGrade(grade) => write!(f, "Invalid grade '{grade}'."),
+            GradingError::InvalidPoints(points) => write!(f, "Invalid points '{points}'."),
+            GradingError::NotANumber(value) => write!(f, "'{value}' is not a number."),
+            GradingError::PointsOutOfRange(points, min, max) => {
+                write!(f, "Points '{points}' must be between {min} and {max}.")
+            }
+            GradingError::InvalidName(name) => write!(f, "Invalid name '{name}'."),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct GradingScale {
     scale_type: GradeScaleType,
     total_points: f64,
@@ -125,7 +196,7 @@ impl GradingScale {
 
         let thresholds = GradingScale::calculate_thresholds(&scale_type, max_points)?;
 
-        info!("INIT GradingScale of type {}", scale_type.text());
+        trace!("INIT GradingScale of type {}", scale_type.text());
         Ok(Self {
             scale_type,
             total_points: max_points,
@@ -153,6 +224,12 @@ impl GradingScale {
         self.recalculate();
     }
 
+    pub fn set_half_points(&mut self, enabled: bool) {
+        if self.use_half_points != enabled {
+            self.toggle_half_points();
+        }
+    }
+
     // returns if half steps are active.
     pub fn is_using_half_points(&self) -> bool {
         self.use_half_points
@@ -215,6 +292,47 @@ impl GradingScale {
         }
     }
 
+    // set the threshold of a grade to an exact value. the value must fit between
+    // the thresholds of the neighboring grades and match the step size.
+    pub fn set_points_for_grade(&mut self, grade: Grade, points: f64) -> Result<(), GradingError> {
+        if self.round_to_step(points) != points {
+            return Err(GradingError::InvalidPoints(points));
+        }
+
+        let (min, max) = self.threshold_range(grade);
+        if !(min..=max).contains(&points) {
+            return Err(GradingError::PointsOutOfRange(points, min, max));
+        }
+        self.update_points_for_grade(grade, points)
+    }
+
+    // returns the allowed points for the threshold of a grade.
+    pub fn threshold_range(&self, grade: Grade) -> (f64, f64) {
+        let step = if self.is_using_half_points() {
+            0.5
+        } else {
+            1.0
+        };
+
+        // grades are ordered from best to worst.
+        let min = match self.thresholds.range(grade..).nth(1) {
+            Some((_, &worse)) => worse + step,
+            None => 0.0,
+        };
+        let max = match self.thresholds.range(..grade).next_back() {
+            Some((_, &better)) => better - step,
+            None => self.total_points,
+        };
+        (min, max)
+    }
+
+    fn round_to_step(&self, points: f64) -> f64 {
+        match self.is_using_half_points() {
+            true => (points * 2.0).round() / 2.0,
+            false => points.round(),
+        }
+    }
+
     // update points for a specific grade
     fn update_points_for_grade(
         &mut self,
@@ -222,12 +340,13 @@ impl GradingScale {
         new_points: f64,
     ) -> Result<(), GradingError> {
         debug!("UPDATE Points: {}", new_points);
+        let new_points = self.round_to_step(new_points);
         if let Some(points) = self.thresholds.get_mut(&grade) {
             // change scale type to custom if points where changed
             if !self.scale_type.is_custom() {
                 self.scale_type = self.scale_type.to_custom();
             }
-            *points = new_points.round();
+            *points = new_points;
             Ok(())
         } else {
             Err(GradingError::InvalidPoints(new_points))
@@ -241,11 +360,15 @@ impl GradingScale {
             .map(|(grade, _)| *grade)
     }
 
+    // the share of the points, rounded like the percentages are shown.
     pub fn percentage_for_points(points: f64, total: f64) -> f64 {
-        round_dp(points / total, 2)
+        round_dp(points / total, precision().percentage + 2)
     }
 }
 
+/// Worst grade which still counts as passed.
+pub const PASS_GRADE: Grade = Grade::Sufficient;
+
 /// helper function to round a number to given decimal places.
 pub fn round_dp(value: f64, dp: usize) -> f64 {
     let x = 10u32.pow(dp as u32) as f64;
@@ -274,14 +397,21 @@ impl Grade {
         }
     }
 
-    fn label(&self) -> &'static str {
-        match self {
-            Grade::VeryGood => "Very Good",
-            Grade::Good => "Good",
-            Grade::Satisfactory => "Satisfactory",
-            Grade::Sufficient => "Sufficient",
-            Grade::Poor => "Poor",
-            Grade::Fail => "Fail",
+    // the verbal grade in the language of the locale.
+    pub fn label(&self) -> &'static str {
+        match (locale(), self) {
+            (Locale::En, Grade::VeryGood) => "Very Good",
+            (Locale::En, Grade::Good) => "Good",
+            (Locale::En, Grade::Satisfactory) => "Satisfactory",
+            (Locale::En, Grade::Sufficient) => "Sufficient",
+            (Locale::En, Grade::Poor) => "Poor",
+            (Locale::En, Grade::Fail) => "Fail",
+            (Locale::De, Grade::VeryGood) => "sehr gut",
+            (Locale::De, Grade::Good) => "gut",
+            (Locale::De, Grade::Satisfactory) => "befriedigend",
+            (Locale::De, Grade::Sufficient) => "ausreichend",
+            (Locale::De, Grade::Poor) => "mangelhaft",
+            (Locale::De, Grade::Fail) => "ungenügend",
         }
     }
 
@@ -318,3 +448,107 @@ impl std::fmt::Display for Grade {
         write!(f, "{}", self.to_number())
     }
 }
+
+/// The grade format of the config, set once on start.
+static GRADE_FORMAT: OnceLock<GradeFormat> = OnceLock::new();
+
+pub fn set_grade_format(format: GradeFormat) {
+    let _ = GRADE_FORMAT.set(format);
+}
+
+pub fn grade_format() -> GradeFormat {
+    GRADE_FORMAT.get().copied().unwrap_or_default()
+}
+
+/// How grades are written in the tables and exports.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum GradeFormat {
+    #[default]
+    Numeric, // 2
+    Verbal,   // gut
+    Combined, // 2 (gut)
+}
+
+impl GradeFormat {
+    // grades which are no valid grade are written as number.
+    pub fn format(&self, grade: u8) -> String {
+        match (self, Grade::try_from(grade)) {
+            (GradeFormat::Verbal, Ok(grade)) => grade.label().to_string(),
+            (GradeFormat::Combined, Ok(grade)) => format!("{grade} ({})", grade.label()),
+            _ => grade.to_string(),
+        }
+    }
+}
+
+// formats the grade as configured, used by all tables and exports.
+pub fn format_grade(grade: u8) -> String {
+    grade_format().format(grade)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grade_format() {
+        assert_eq!(GradeFormat::Numeric.format(2), "2");
+        assert_eq!(GradeFormat::Verbal.format(2), "Good");
+        assert_eq!(GradeFormat::Combined.format(6), "6 (Fail)");
+        assert_eq!(GradeFormat::Verbal.format(0), "0");
+    }
+
+    #[test]
+    fn test_scale_from_toml() {
+        let content = "1 = 0.9\n2 = 0.8\n3 = 0.65\n4 = 0.5\n5 = 0.25\n6 = 0.0\n";
+        let scale_type = GradeScaleType::from_toml(content).unwrap();
+        assert!(scale_type.is_custom());
+        assert_eq!(scale_type.values()[2], (3, 0.65));
+
+        assert!(GradeScaleType::from_toml("1 = 0.9\n").is_err());
+        assert!(GradeScaleType::from_toml(&content.replace("0.65", "0.85")).is_err());
+        assert_eq!(
+            GradeScaleType::from_name("techniker").map(|s| s.text()),
+            Some("TECHNIKER")
+        );
+    }
+
+    #[test]
+    fn test_parse_max_points() {
+        assert_eq!(parse_max_points("40").unwrap(), 40.0);
+        assert_eq!(parse_max_points(" 47,5 ").unwrap(), 47.5);
+        assert!(parse_max_points("47.3").is_err());
+        assert!(matches!(
+            parse_max_points("abc"),
+            Err(GradingError::NotANumber(value)) if value == "abc"
+        ));
+        for value in ["0", "-10", "NaN", "inf"] {
+            assert!(matches!(
+                parse_max_points(value),
+                Err(GradingError::InvalidPoints(_))
+            ));
+        }
+        assert_eq!(
+            parse_max_points(" abc ").unwrap_err().to_string(),
+            "'abc' is not a number."
+        );
+    }
+
+    #[test]
+    fn test_set_points_for_grade() {
+        // IHK with 100 points: 92, 81, 67, 50, 30, 0
+        let mut scale = GradingScale::from_type(GradeScaleType::IHK, 100.0).unwrap();
+        assert_eq!(scale.threshold_range(Grade::Good), (68.0, 91.0));
+        assert!(scale.set_points_for_grade(Grade::Good, 85.0).is_ok());
+        assert_eq!(scale.thresholds()[&Grade::Good], 85.0);
+
+        assert!(scale.set_points_for_grade(Grade::Good, 92.0).is_err());
+        assert!(scale.set_points_for_grade(Grade::VeryGood, 101.0).is_err());
+        assert!(scale.set_points_for_grade(Grade::Good, 84.5).is_err());
+
+        scale.toggle_half_points();
+        assert!(scale.set_points_for_grade(Grade::Good, 84.5).is_ok());
+        assert!(scale.set_points_for_grade(Grade::Good, 84.25).is_err());
+    }
+}
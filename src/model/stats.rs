@@ -0,0 +1,280 @@
+/// Location, spread and shape of the points or grades of the students.
+/// `std_dev` is the standard deviation of the whole class, not of a sample.
+/// `q1` and `q3` are the quartiles, a quarter of the values is below or above them.
+/// `kurtosis` is the excess kurtosis, 0 for a normal distribution.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Statistics {
+    pub mean: f64,
+    pub median: f64,
+    pub mode: Vec<f64>, // all values which occur most often, in ascending order
+    pub std_dev: f64,
+    pub min: f64,
+    pub q1: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub skewness: f64, // 0 if all values are equal
+    pub kurtosis: f64,
+}
+
+impl Statistics {
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / count;
+        // central moments of the distribution.
+        let moment = |power: i32| {
+            sorted
+                .iter()
+                .map(|value| (value - mean).powi(power))
+                .sum::<f64>()
+                / count
+        };
+        let variance = moment(2);
+        let (skewness, kurtosis) = match variance > 0.0 {
+            true => (
+                moment(3) / variance.powf(1.5),
+                moment(4) / variance.powi(2) - 3.0,
+            ),
+            false => (0.0, 0.0),
+        };
+
+        // runs of equal values in the sorted list.
+        let mut runs: Vec<(f64, usize)> = Vec::new();
+        for &value in sorted.iter() {
+            match runs.last_mut() {
+                Some((last, count)) if *last == value => *count += 1,
+                _ => runs.push((value, 1)),
+            }
+        }
+        let most = runs
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or_default();
+
+        Some(Self {
+            mean,
+            median: quantile(&sorted, 0.5),
+            mode: runs
+                .into_iter()
+                .filter(|(_, count)| *count == most)
+                .map(|(value, _)| value)
+                .collect(),
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            q1: quantile(&sorted, 0.25),
+            q3: quantile(&sorted, 0.75),
+            max: sorted[sorted.len() - 1],
+            skewness,
+            kurtosis,
+        })
+    }
+
+    pub fn range(&self) -> f64 {
+        self.max - self.min
+    }
+
+    // the interquartile range, in which the middle half of the values lies.
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+
+    // what the shape of the points tells about the exam, e.g. "left-skewed: exam was easy".
+    pub fn shape(&self) -> String {
+        let skew = match self.skewness {
+            s if s < -0.5 => "left-skewed: exam was easy",
+            s if s > 0.5 => "right-skewed: exam was hard",
+            _ => "symmetric",
+        };
+        match self.kurtosis {
+            k if k > 1.0 => format!("{skew}, most results close together"),
+            k if k < -1.0 => format!("{skew}, results spread widely"),
+            _ => skew.to_string(),
+        }
+    }
+}
+
+/// Five-number summary of the student points, as shown in a box plot.
+/// `min` and `max` are the whisker ends, values beyond 1.5 IQR are outliers.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BoxPlotSummary {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub outliers: Vec<f64>,
+}
+
+impl BoxPlotSummary {
+    // the quartiles are the ones of the statistics of the values.
+    pub fn from_values(values: &[f64]) -> Option<Self> {
+        let stats = Statistics::from_values(values)?;
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let (q1, q3) = (stats.q1, stats.q3);
+        let fence = 1.5 * stats.iqr();
+        let (inside, outliers): (Vec<f64>, Vec<f64>) = sorted
+            .iter()
+            .partition(|&&value| value >= q1 - fence && value <= q3 + fence);
+
+        Some(Self {
+            min: inside.first().copied().unwrap_or(q1),
+            q1,
+            median: stats.median,
+            q3,
+            max: inside.last().copied().unwrap_or(q3),
+            outliers,
+        })
+    }
+
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+}
+
+/// Counts the values in bins of `bin_width` from 0 to `max`. A value equal to
+/// `max` is counted in the last bin.
+pub fn histogram(values: &[f64], bin_width: f64, max: f64) -> Vec<usize> {
+    if bin_width <= 0.0 || max <= 0.0 {
+        return Vec::new();
+    }
+    let bins = (max / bin_width).ceil() as usize;
+    let mut counts = vec![0; bins];
+    for &value in values.iter().filter(|v| (0.0..=max).contains(*v)) {
+        let index = ((value / bin_width) as usize).min(bins - 1);
+        counts[index] += 1;
+    }
+    counts
+}
+
+/// Percentage of the values at or above each of `steps` evenly spaced points
+/// from 0 to `max`.
+pub fn cumulative_share(values: &[f64], max: f64, steps: usize) -> Vec<f64> {
+    if values.is_empty() || steps == 0 {
+        return Vec::new();
+    }
+    (0..steps)
+        .map(|step| {
+            let points = match steps {
+                1 => 0.0,
+                steps => max * step as f64 / (steps - 1) as f64,
+            };
+            let count = values.iter().filter(|&&value| value >= points).count();
+            count as f64 / values.len() as f64 * 100.0
+        })
+        .collect()
+}
+
+/// Average of values with their weights, e.g. the grades of exams which count twice.
+/// None without any weight.
+pub fn weighted_average(values: &[(f64, f64)]) -> Option<f64> {
+    let total: f64 = values.iter().map(|(_, weight)| weight).sum();
+    match total > 0.0 {
+        true => Some(
+            values
+                .iter()
+                .map(|(value, weight)| value * weight)
+                .sum::<f64>()
+                / total,
+        ),
+        false => None,
+    }
+}
+
+/// Share of the values below `value`, equal values count half. E.g. 0.8 means
+/// the value is better than 80% of the class.
+pub fn percentile_rank(values: &[f64], value: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let below = values.iter().filter(|&&other| other < value).count();
+    let equal = values.iter().filter(|&&other| other == value).count();
+    (below as f64 + equal as f64 / 2.0) / values.len() as f64
+}
+
+// linear interpolation between the closest ranks. `sorted` must not be empty.
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics() {
+        assert_eq!(Statistics::from_values(&[]), None);
+
+        let stats = Statistics::from_values(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.median, 4.5);
+        assert_eq!(stats.mode, vec![4.0]);
+        assert_eq!(stats.std_dev, 2.0);
+        assert_eq!(stats.range(), 7.0);
+        assert_eq!((stats.q1, stats.q3), (4.0, 5.5));
+        assert_eq!(stats.iqr(), 1.5);
+
+        assert_eq!(stats.shape(), "right-skewed: exam was hard");
+
+        let stats = Statistics::from_values(&[3.0, 1.0]).unwrap();
+        assert_eq!(stats.mode, vec![1.0, 3.0]);
+        assert_eq!(stats.skewness, 0.0);
+        assert_eq!(stats.kurtosis, -2.0);
+        assert_eq!(stats.shape(), "symmetric, results spread widely");
+    }
+
+    #[test]
+    fn test_box_plot_summary() {
+        assert_eq!(BoxPlotSummary::from_values(&[]), None);
+
+        let summary = BoxPlotSummary::from_values(&[50.0, 10.0, 20.0, 30.0, 40.0, 100.0]).unwrap();
+        assert_eq!(summary.q1, 22.5);
+        assert_eq!(summary.median, 35.0);
+        assert_eq!(summary.q3, 47.5);
+        assert_eq!(summary.iqr(), 25.0);
+        assert_eq!(summary.min, 10.0);
+        assert_eq!(summary.max, 50.0);
+        assert_eq!(summary.outliers, vec![100.0]);
+    }
+
+    #[test]
+    fn test_weighted_average() {
+        assert_eq!(weighted_average(&[(1.0, 2.0), (4.0, 1.0)]), Some(2.0));
+        assert_eq!(weighted_average(&[(3.0, 0.0)]), None);
+        assert_eq!(weighted_average(&[]), None);
+    }
+
+    #[test]
+    fn test_percentile_rank() {
+        let values = [10.0, 20.0, 20.0, 40.0];
+        assert_eq!(percentile_rank(&values, 40.0), 0.875);
+        assert_eq!(percentile_rank(&values, 20.0), 0.5);
+        assert_eq!(percentile_rank(&values, 10.0), 0.125);
+        assert_eq!(percentile_rank(&[], 10.0), 0.0);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let values = [0.0, 4.5, 5.0, 12.0, 20.0];
+        assert_eq!(histogram(&values, 5.0, 20.0), vec![2, 1, 1, 1]);
+        assert_eq!(histogram(&values, 8.0, 20.0), vec![3, 1, 1]);
+        assert!(histogram(&values, 0.0, 20.0).is_empty());
+    }
+
+    #[test]
+    fn test_cumulative_share() {
+        let values = [0.0, 5.0, 10.0, 10.0];
+        assert_eq!(cumulative_share(&values, 10.0, 3), vec![100.0, 75.0, 50.0]);
+        assert!(cumulative_share(&[], 10.0, 3).is_empty());
+    }
+}
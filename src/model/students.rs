@@ -1,15 +1,67 @@
+use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use calamine::{open_workbook, Data, DataType, Reader, Xlsx, XlsxError};
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use super::scale::{Grade, GradingScale};
+use crate::config::get_data_dir;
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+/// Names of renamed courses, since the course file only holds the students. Stored
+/// in the data dir, one course per line as `<path>\t<name>`.
+fn course_names_path() -> PathBuf {
+    get_data_dir().join("course_names")
+}
+
+fn read_course_names() -> Vec<(PathBuf, String)> {
+    fs::read_to_string(course_names_path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(path, name)| (PathBuf::from(path), name.to_string()))
+        .collect()
+}
+
+// the name given to the course file, if it was renamed.
+fn saved_course_name(path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+    read_course_names()
+        .into_iter()
+        .find(|(course, _)| *course == path)
+        .map(|(_, name)| name)
+}
+
+// remembers the name of the course file, a name like the file name is forgotten.
+fn save_course_name(path: &Path, name: &str) -> io::Result<()> {
+    let path = path.canonicalize()?;
+    let mut names = read_course_names();
+    let count = names.len();
+    names.retain(|(course, _)| *course != path);
+    let renamed = StudentList::course_name(&path).is_ok_and(|stem| stem != name);
+    if !renamed && names.len() == count {
+        return Ok(());
+    }
+    if renamed {
+        names.push((path, name.to_string()));
+    }
+    let content: Vec<String> = names
+        .iter()
+        .map(|(course, name)| format!("{}\t{name}", course.display()))
+        .collect();
+    fs::create_dir_all(get_data_dir())?;
+    fs::write(course_names_path(), content.join("\n"))
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Student {
     pub name: String,
     points: f64, // todo! change to Vec of points later
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub absent: bool, // did not take the exam, so not part of the statistics
 }
 
 impl Student {
@@ -17,14 +69,11 @@ impl Student {
         Self {
             name: name.to_owned(),
             points: 0.0,
+            notes: String::new(),
+            absent: false,
         }
     }
 
-    pub fn with_points(mut self, points: f64) -> Self {
-        self.update_points(points);
-        self
-    }
-
     pub fn update_points(&mut self, new_value: f64) {
         self.points = new_value;
     }
@@ -39,19 +88,42 @@ impl Student {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct StudentList {
     course: String,
     students: Vec<Student>,
 }
 
 impl StudentList {
-    pub fn from_csv_file(path: &Path) -> io::Result<Self> {
-        // Extract metadata from filename
-        let course_name = path
-            .file_stem()
+    pub fn new(course: &str, names: &[String]) -> Self {
+        Self {
+            course: course.to_string(),
+            students: names.iter().map(|name| Student::new(name)).collect(),
+        }
+    }
+
+    // reads a csv file or the first sheet of an xlsx workbook, chosen by the extension.
+    // A renamed course keeps its name.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let mut list = match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("xlsx") => StudentList::from_xlsx_file(path)?,
+            _ => StudentList::from_csv_file(path)?,
+        };
+        if let Some(name) = saved_course_name(path) {
+            list.course = name;
+        }
+        Ok(list)
+    }
+
+    // the course is named after the file.
+    fn course_name(path: &Path) -> io::Result<&str> {
+        path.file_stem()
             .and_then(|f| f.to_str())
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid filename"))?;
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid filename"))
+    }
+
+    pub fn from_csv_file(path: &Path) -> io::Result<Self> {
+        let course_name = StudentList::course_name(path)?;
 
         debug!("Try to open '{:?}'", path);
         let mut reader = csv::Reader::from_path(path)?;
@@ -70,6 +142,76 @@ impl StudentList {
         })
     }
 
+    // the first sheet needs a header row with a name column, the columns points, notes
+    // and absent are optional. The German headers of the result export are understood
+    // as well, where a grade of "-" marks an absent student. The students end at the
+    // first row without a name, so the summary below the exported results is skipped.
+    pub fn from_xlsx_file(path: &Path) -> io::Result<Self> {
+        let course_name = StudentList::course_name(path)?;
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        debug!("Try to open '{:?}'", path);
+        let mut workbook: Xlsx<_> =
+            open_workbook(path).map_err(|e: XlsxError| invalid(e.to_string()))?;
+        let sheet = workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| invalid("the workbook has no sheet".to_string()))?
+            .map_err(|e| invalid(e.to_string()))?;
+
+        let mut rows = sheet.rows();
+        let header: Vec<String> = rows
+            .next()
+            .unwrap_or_default()
+            .iter()
+            .map(|cell| cell.to_string().trim().to_lowercase())
+            .collect();
+        let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+        let name_column = column(&["name"])
+            .ok_or_else(|| invalid("the first sheet has no name column".to_string()))?;
+        let points_column = column(&["points", "punkte"]);
+        let notes_column = column(&["notes", "notizen"]);
+        let absent_column = column(&["absent"]);
+        let grade_column = column(&["grade", "note"]);
+
+        let mut students = Vec::new();
+        for (index, row) in rows.enumerate() {
+            let cell = |column: Option<usize>| column.and_then(|column| row.get(column));
+            let text = |column: Option<usize>| {
+                cell(column)
+                    .map(|data| data.to_string().trim().to_string())
+                    .unwrap_or_default()
+            };
+            let name = text(Some(name_column));
+            if name.is_empty() {
+                break;
+            }
+            let points = match cell(points_column) {
+                None | Some(Data::Empty) => 0.0,
+                Some(data) => data
+                    .as_f64()
+                    .or_else(|| data.to_string().trim().replace(',', ".").parse().ok())
+                    .ok_or_else(|| {
+                        // the header is the first row of the sheet.
+                        invalid(format!("'{data}' in row {} are no points", index + 2))
+                    })?,
+            };
+            let absent = cell(absent_column)
+                .is_some_and(|data| data.get_bool().unwrap_or(*data == "true"))
+                || text(grade_column) == "-";
+            students.push(Student {
+                name,
+                points,
+                notes: text(notes_column),
+                absent,
+            });
+        }
+
+        Ok(StudentList {
+            course: course_name.to_string(),
+            students,
+        })
+    }
+
     pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
         let mut writer = csv::Writer::from_path(path)?;
 
@@ -77,6 +219,10 @@ impl StudentList {
             writer.serialize(student)?;
         }
         writer.flush()?;
+        // the students are saved anyway.
+        if let Err(e) = save_course_name(path, &self.course) {
+            warn!("Could not save the course name: {e}");
+        }
         Ok(())
     }
 
@@ -84,12 +230,17 @@ impl StudentList {
         &self.course
     }
 
+    pub fn set_class_name(&mut self, name: &str) {
+        self.course = name.to_string();
+    }
+
     pub fn iter_students(&self) -> impl Iterator<Item = &Student> {
         self.students.iter()
     }
 
-    pub fn iter_students_mut(&mut self) -> impl Iterator<Item = &mut Student> {
-        self.students.iter_mut()
+    // the students who took the exam.
+    pub fn iter_present(&self) -> impl Iterator<Item = &Student> {
+        self.students.iter().filter(|s| !s.absent)
     }
 
     pub fn get_student(&self, name: &str) -> Option<&Student> {
@@ -99,6 +250,11 @@ impl StudentList {
     pub fn get_student_mut(&mut self, name: &str) -> Option<&mut Student> {
         self.students.iter_mut().find(|s| s.name == name)
     }
+
+    pub fn remove_student(&mut self, name: &str) -> Option<Student> {
+        let index = self.students.iter().position(|s| s.name == name)?;
+        Some(self.students.remove(index))
+    }
 }
 
 impl std::fmt::Display for StudentList {
@@ -0,0 +1,148 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::config::get_data_dir;
+
+/// Number of remembered course files.
+const MAX_RECENT_FILES: usize = 10;
+
+/// A course file and when it was opened the last time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    pub opened: Option<SystemTime>, // unknown for lists of older versions
+}
+
+impl RecentFile {
+    // e.g. "just now", "5 min ago", "yesterday" or "12 days ago".
+    pub fn opened_ago(&self, now: SystemTime) -> String {
+        let Some(opened) = self.opened else {
+            return String::new();
+        };
+        let minutes = now.duration_since(opened).unwrap_or_default().as_secs() / 60;
+        match (minutes, minutes / 60, minutes / (60 * 24)) {
+            (0, _, _) => "just now".to_string(),
+            (minutes, 0, _) => format!("{minutes} min ago"),
+            (_, hours, 0) => format!("{hours} h ago"),
+            (_, _, 1) => "yesterday".to_string(),
+            (_, _, days) => format!("{days} days ago"),
+        }
+    }
+}
+
+/// The course files opened last, newest first. Stored in the data dir,
+/// one file per line as `<unix seconds>\t<path>`.
+#[derive(Debug, Default, Clone)]
+pub struct RecentFiles {
+    files: Vec<RecentFile>,
+}
+
+impl RecentFiles {
+    fn file_path() -> PathBuf {
+        get_data_dir().join("recent_files")
+    }
+
+    // a missing or unreadable file is an empty list.
+    pub fn load() -> Self {
+        let files = fs::read_to_string(RecentFiles::file_path())
+            .map(|content| content.lines().map(RecentFiles::parse_line).collect())
+            .unwrap_or_default();
+        Self { files }
+    }
+
+    // lines without a timestamp are paths written by older versions.
+    fn parse_line(line: &str) -> RecentFile {
+        if let Some((Ok(secs), path)) = line
+            .split_once('\t')
+            .map(|(secs, path)| (secs.parse::<u64>(), path))
+        {
+            return RecentFile {
+                path: PathBuf::from(path),
+                opened: Some(UNIX_EPOCH + Duration::from_secs(secs)),
+            };
+        }
+        RecentFile {
+            path: PathBuf::from(line),
+            opened: None,
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let content: Vec<String> = self
+            .files
+            .iter()
+            .map(|file| {
+                let secs = file
+                    .opened
+                    .and_then(|opened| opened.duration_since(UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or_default();
+                format!("{secs}\t{}", file.path.display())
+            })
+            .collect();
+        fs::create_dir_all(get_data_dir())?;
+        fs::write(RecentFiles::file_path(), content.join("\n"))
+    }
+
+    // put the path in front with the current time, without duplicates.
+    pub fn add(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or(path.to_path_buf());
+        self.files.retain(|file| file.path != path);
+        self.files.insert(
+            0,
+            RecentFile {
+                path,
+                opened: Some(SystemTime::now()),
+            },
+        );
+        self.files.truncate(MAX_RECENT_FILES);
+    }
+
+    // files which were removed in the meantime are skipped.
+    pub fn existing(&self) -> Vec<RecentFile> {
+        self.files
+            .iter()
+            .filter(|file| file.path.exists())
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let mut recent = RecentFiles::default();
+        for name in ["a.csv", "b.csv", "a.csv"] {
+            recent.add(Path::new(name));
+        }
+        let paths: Vec<&Path> = recent.files.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(paths, vec![Path::new("a.csv"), Path::new("b.csv")]);
+
+        for i in 0..20 {
+            recent.add(Path::new(&format!("{i}.csv")));
+        }
+        assert_eq!(recent.files.len(), MAX_RECENT_FILES);
+        assert_eq!(recent.files[0].path, PathBuf::from("19.csv"));
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let file = RecentFiles::parse_line("1700000000\t/tmp/10a.csv");
+        assert_eq!(file.path, PathBuf::from("/tmp/10a.csv"));
+        let opened = file.opened.unwrap();
+        assert_eq!(RecentFiles::parse_line("/tmp/10a.csv").opened, None);
+
+        let ago = |secs| file.opened_ago(opened + Duration::from_secs(secs));
+        assert_eq!(ago(30), "just now");
+        assert_eq!(ago(5 * 60), "5 min ago");
+        assert_eq!(ago(3 * 3600), "3 h ago");
+        assert_eq!(ago(30 * 3600), "yesterday");
+        assert_eq!(ago(12 * 86400), "12 days ago");
+    }
+}
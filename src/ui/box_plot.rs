@@ -0,0 +1,102 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Padding, Paragraph, Widget},
+};
+
+use super::theme::{AppStyle, THEME};
+use crate::locale::format_points;
+use crate::model::stats::BoxPlotSummary;
+
+/// Horizontal box-and-whiskers plot of the student points from 0 to max points.
+#[derive(Debug, Default, Clone)]
+pub struct BoxPlot {
+    summary: Option<BoxPlotSummary>,
+    max_points: f64,
+}
+
+impl BoxPlot {
+    pub fn set_data(&mut self, summary: Option<BoxPlotSummary>, max_points: f64) {
+        self.summary = summary;
+        self.max_points = max_points;
+    }
+}
+
+impl Widget for &BoxPlot {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = THEME
+            .frame()
+            .title(Line::raw(" 📦 Points "))
+            .padding(Padding::horizontal(2));
+
+        let Some(summary) = &self.summary else {
+            Paragraph::new(Line::from("No student data loaded.").style(THEME.text()))
+                .block(block)
+                .render(area, buf);
+            return;
+        };
+
+        let width = block.inner(area).width as usize;
+        // map points to a column of the plot.
+        let column = |points: f64| {
+            let ratio = (points / self.max_points.max(1.0)).clamp(0.0, 1.0);
+            (ratio * width.saturating_sub(1) as f64).round() as usize
+        };
+
+        let mut plot = vec![' '; width];
+        let (min, q1, median, q3, max) = (
+            column(summary.min),
+            column(summary.q1),
+            column(summary.median),
+            column(summary.q3),
+            column(summary.max),
+        );
+        for (index, symbol) in plot.iter_mut().enumerate() {
+            *symbol = match index {
+                i if i == median => '┃',
+                i if i == min => '├',
+                i if i == max => '┤',
+                i if (q1..=q3).contains(&i) => '█',
+                i if (min..max).contains(&i) => '─',
+                _ => ' ',
+            };
+        }
+        for &outlier in summary.outliers.iter() {
+            if let Some(symbol) = plot.get_mut(column(outlier)) {
+                *symbol = '•';
+            }
+        }
+
+        let value = |label: &str, points: f64| {
+            [
+                Span::from(format!(" {label} ")).style(THEME.tag(true)),
+                Span::from(format!(" {}  ", format_points(points))).style(THEME.text()),
+            ]
+        };
+        let values: Vec<Span> = [
+            ("min", summary.min),
+            ("Q1", summary.q1),
+            ("median", summary.median),
+            ("Q3", summary.q3),
+            ("max", summary.max),
+            ("IQR", summary.iqr()),
+        ]
+        .into_iter()
+        .flat_map(|(label, points)| value(label, points))
+        .collect();
+
+        let lines = vec![
+            Line::from(plot.into_iter().collect::<String>()).style(THEME.bar_chart()),
+            Line::from(format!(
+                "0{:>width$}",
+                self.max_points,
+                width = width.saturating_sub(1)
+            ))
+            .style(THEME.text()),
+            Line::from(values).centered(),
+        ];
+
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+}
@@ -0,0 +1,148 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Clear, Widget},
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::theme::{AppStyle, THEME};
+use crate::commands::{common_prefix, complete};
+
+#[derive(Debug, Default)]
+pub struct CommandLine {
+    input: Input,
+    suggestions: Vec<String>,
+    error: Option<String>, // why the last input could not be executed
+    history: Vec<String>,  // executed commands of this session, oldest first
+    history_index: Option<usize>,
+}
+
+impl CommandLine {
+    pub fn value(&self) -> &str {
+        self.input.value()
+    }
+
+    pub fn reset(&mut self) {
+        self.input.reset();
+        self.suggestions.clear();
+        self.error = None;
+        self.history_index = None;
+    }
+
+    // remember an executed command, repeated commands are only stored once.
+    pub fn push_history(&mut self) {
+        let value = self.value().trim().to_string();
+        if !value.is_empty() && self.history.last() != Some(&value) {
+            self.history.push(value);
+        }
+    }
+
+    fn select_history(&mut self, older: bool) {
+        let index = match (self.history_index, older) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < self.history.len() => Some(index + 1),
+            (Some(_), false) => None,
+        };
+        let value = index.map(|i| self.history[i].clone()).unwrap_or_default();
+        self.set_value(value);
+        self.history_index = index;
+        self.update_suggestions();
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    // show the error in the line above the command line.
+    pub fn render_error(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(error) = &self.error {
+            Clear.render(area, buf);
+            Line::from(format!(" {error}"))
+                .style(THEME.error_text())
+                .render(area, buf);
+        }
+    }
+
+    fn set_value(&mut self, value: String) {
+        self.input = Input::new(value);
+    }
+
+    // complete the input as far as all suggestions agree.
+    fn complete(&mut self) {
+        let completions = complete(self.value());
+        if let Some(prefix) = common_prefix(&completions) {
+            self.set_value(if completions.len() == 1 && !prefix.ends_with('/') {
+                format!("{prefix} ")
+            } else {
+                prefix
+            });
+        }
+        self.update_suggestions();
+    }
+
+    fn update_suggestions(&mut self) {
+        self.suggestions = complete(self.value());
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) {
+        self.error = None;
+        match key.code {
+            KeyCode::Tab => self.complete(),
+            KeyCode::Up => self.select_history(true),
+            KeyCode::Down => self.select_history(false),
+            _ => {
+                self.input.handle_event(&Event::Key(key));
+                self.update_suggestions();
+            }
+        }
+    }
+}
+
+impl Widget for &CommandLine {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let value = self.value();
+        let (before, after) = value.split_at(
+            value
+                .char_indices()
+                .nth(self.input.cursor())
+                .map_or(value.len(), |(i, _)| i),
+        );
+        let mut chars = after.chars();
+        let cursor = chars.next().map_or(" ".to_string(), String::from);
+        let mut spans = vec![
+            Span::from(":").style(THEME.indicator(None)),
+            Span::from(format!(" {before}")).style(THEME.text()),
+            Span::from(cursor).style(THEME.text().reversed()),
+            Span::from(chars.as_str().to_string()).style(THEME.text()),
+        ];
+
+        // show the rest of the first suggestion as ghost text.
+        if let Some(rest) = self
+            .suggestions
+            .first()
+            .and_then(|first| first.strip_prefix(value))
+        {
+            spans.push(Span::from(rest.to_string()).style(THEME.text().dim()));
+        }
+
+        if self.suggestions.len() > 1 {
+            let others: Vec<&str> = self
+                .suggestions
+                .iter()
+                .map(|s| s.rsplit([' ', '/']).find(|p| !p.is_empty()).unwrap_or(s))
+                .collect();
+            spans.push(
+                Span::from(format!("   [{}]", others.join(" "))).style(THEME.text().italic()),
+            );
+        }
+
+        Line::from(spans)
+            .style(THEME.bottom_bar())
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,70 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Padding, Paragraph, Widget, Wrap},
+};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::action::Action;
+
+/// A yes/no dialog which emits the given action once confirmed.
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    message: String,
+    action: Action,
+}
+
+impl ConfirmDialog {
+    pub fn new(message: &str, action: Action) -> Self {
+        Self {
+            message: message.to_string(),
+            action,
+        }
+    }
+
+    pub fn handle_event(&self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                Some(Action::Confirmed(Box::new(self.action.clone())))
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                Some(Action::CloseConfirmation)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Widget for &ConfirmDialog {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(area, Constraint::Length(44), Constraint::Length(7));
+
+        let block = THEME
+            .frame()
+            .title(" ❓ Confirm ")
+            .padding(Padding::horizontal(1));
+
+        let text = vec![
+            Line::from(self.message.as_str()).style(THEME.text()),
+            Line::default(),
+            Line::from(vec![
+                Span::from(" y ").style(THEME.tag(true)),
+                Span::from(" yes   ").style(THEME.text()),
+                Span::from(" n ").style(THEME.tag(true)),
+                Span::from(" no ").style(THEME.text()),
+            ]),
+        ];
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(text)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+}
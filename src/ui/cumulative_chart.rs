@@ -0,0 +1,52 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Line,
+    widgets::{Paragraph, Sparkline, Widget},
+};
+
+use super::theme::{AppStyle, THEME};
+use crate::model::stats::cumulative_share;
+
+/// Sparkline of the share of students at or above each point value. Steep
+/// parts show where moving a threshold changes the grade of many students.
+#[derive(Debug, Default, Clone)]
+pub struct CumulativeChart {
+    points: Vec<f64>,
+    max_points: f64,
+}
+
+impl CumulativeChart {
+    pub fn set_data(&mut self, points: Vec<f64>, max_points: f64) {
+        self.points = points;
+        self.max_points = max_points;
+    }
+}
+
+impl Widget for &CumulativeChart {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = THEME
+            .frame()
+            .title(Line::raw(" 📈 At or above "))
+            .title_bottom(Line::from(" 0 ").left_aligned())
+            .title_bottom(Line::from(format!(" {} ", self.max_points)).right_aligned());
+
+        // one value per column.
+        let inner = block.inner(area);
+        let shares = cumulative_share(&self.points, self.max_points, inner.width as usize);
+        if shares.is_empty() {
+            Paragraph::new(Line::from("No student data loaded.").style(THEME.text()))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let data: Vec<u64> = shares.iter().map(|share| share.round() as u64).collect();
+        Sparkline::default()
+            .block(block)
+            .data(&data)
+            .max(100)
+            .style(THEME.bar_chart())
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,141 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Padding, Row, Table, Widget},
+};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::{
+    action::{Action, ModelAction},
+    locale::{format_average, format_percentage, format_points},
+    model::{
+        scale::{GradeScaleType, GradingScale},
+        Curve,
+    },
+};
+
+/// Compares the minimum points of the current scale with a curve for a target
+/// average grade. Accepting the curve makes it the custom scale.
+#[derive(Debug, Clone)]
+pub struct CurvePreview {
+    target: f64,
+    curve: Curve,
+    average: f64,                    // with the current scale
+    thresholds: Vec<(u8, f64, f64)>, // grade, current and curved minimum points
+}
+
+impl CurvePreview {
+    pub fn new(scale: &GradingScale, curve: Curve, average: f64, target: f64) -> Self {
+        let curved = GradingScale::from_type(curve.scale_type, scale.max_points())
+            .map(|curved| curved.thresholds())
+            .unwrap_or_default();
+        let thresholds = scale
+            .thresholds()
+            .into_iter()
+            .map(|(grade, points)| {
+                let curved = curved.get(&grade).copied().unwrap_or(points);
+                (grade.to_number(), points, curved)
+            })
+            .collect();
+        Self {
+            target,
+            curve,
+            average,
+            thresholds,
+        }
+    }
+
+    pub fn handle_event(&self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                let GradeScaleType::Custom(values) = self.curve.scale_type else {
+                    return Some(Action::CloseCurvePreview);
+                };
+                Some(Action::UpdateModel(ModelAction::SetCustomScale(values)))
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                Some(Action::CloseCurvePreview)
+            }
+            _ => None,
+        }
+    }
+
+    // e.g. "Ø 3.67 → 3 (target 3)" and "67% passed with the curve".
+    fn summary(&self) -> [String; 2] {
+        let average = format!(
+            "Ø {} → {} (target {})",
+            format_average(self.average),
+            format_average(self.curve.outcome.average),
+            format_average(self.target)
+        );
+        let pass_rate = match self.curve.outcome.pass_rate {
+            Some(rate) => format!("{} passed with the curve", format_percentage(rate)),
+            None => String::new(),
+        };
+        [average, pass_rate]
+    }
+}
+
+impl Widget for &CurvePreview {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let height = self.thresholds.len() as u16 + 7;
+        let popup_area = centered_rect(area, Constraint::Length(44), Constraint::Length(height));
+
+        let shift = format_percentage(self.curve.shift.abs());
+        let title = match self.curve.shift {
+            s if s < 0.0 => format!(" 📐 Curve · thresholds {shift} lower "),
+            s if s > 0.0 => format!(" 📐 Curve · thresholds {shift} higher "),
+            _ => " 📐 Curve · thresholds kept ".to_string(),
+        };
+        let block = THEME.frame().title(title).padding(Padding::horizontal(1));
+        let inner = block.inner(popup_area);
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+
+        let [table_area, summary_area, keys_area] = Layout::vertical([
+            Constraint::Length(self.thresholds.len() as u16 + 1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let rows = self.thresholds.iter().map(|(grade, current, curved)| {
+            Row::new([
+                grade.to_string(),
+                format_points(*current),
+                format_points(*curved),
+            ])
+        });
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["Grade", "Now", "Curved"]).style(THEME.table_header()))
+        .style(THEME.text())
+        .render(table_area, buf);
+
+        let [average, pass_rate] = self.summary();
+        let lines = vec![Line::default(), Line::from(average), Line::from(pass_rate)];
+        for (line, row) in lines.into_iter().zip(summary_area.rows()) {
+            line.style(THEME.text()).render(row, buf);
+        }
+
+        Line::from(vec![
+            Span::from(" y ").style(THEME.tag(true)),
+            Span::from(" accept as custom scale   ").style(THEME.text()),
+            Span::from(" n ").style(THEME.tag(true)),
+            Span::from(" discard ").style(THEME.text()),
+        ])
+        .centered()
+        .render(keys_area, buf);
+    }
+}
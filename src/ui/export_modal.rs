@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, HighlightSpacing, List, ListState, Paragraph, StatefulWidget, Widget},
+};
+use tracing::debug;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::{
+    action::Action,
+    export::{resolve_path, EXPORT_FORMATS as FORMATS},
+};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+enum ExportStep {
+    #[default]
+    Format,
+    FileName,
+}
+
+#[derive(Debug, Default)]
+pub struct ExportModal {
+    step: ExportStep,
+    state: ListState,
+    input: Input,
+    dir: PathBuf,
+    file_stem: String,
+}
+
+impl ExportModal {
+    // reset the modal, the file name defaults to the given name in `dir`.
+    // `format` is selected first if it is given.
+    pub fn open(&mut self, dir: &Path, file_stem: &str, format: Option<&str>) {
+        self.step = ExportStep::Format;
+        self.state.select(Some(
+            FORMATS
+                .iter()
+                .position(|f| Some(*f) == format)
+                .unwrap_or_default(),
+        ));
+        self.input.reset();
+        self.dir = dir.to_path_buf();
+        self.file_stem = file_stem.to_string();
+    }
+
+    fn selected_format(&self) -> &'static str {
+        FORMATS[self
+            .state
+            .selected()
+            .unwrap_or_default()
+            .min(FORMATS.len() - 1)]
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        debug!("EVENT: {:?}", key);
+        match self.step {
+            ExportStep::Format => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.state.select_previous();
+                    None
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.state.select_next();
+                    None
+                }
+                KeyCode::Enter => {
+                    let file_name = format!("{}.{}", self.file_stem, self.selected_format());
+                    self.input = Input::new(self.dir.join(file_name).display().to_string());
+                    self.step = ExportStep::FileName;
+                    None
+                }
+                KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseExportModal),
+                _ => None,
+            },
+            ExportStep::FileName => match key.code {
+                KeyCode::Enter => resolve_path(self.input.value().trim())
+                    .map(|path| Action::ExportTo(Some(path)))
+                    .or(Some(Action::CloseExportModal)),
+                KeyCode::Esc => {
+                    self.step = ExportStep::Format;
+                    None
+                }
+                _ => {
+                    self.input.handle_event(&Event::Key(key));
+                    None
+                }
+            },
+        }
+    }
+}
+
+impl Widget for &mut ExportModal {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(area, Constraint::Length(50), Constraint::Length(9));
+
+        let block = THEME
+            .frame()
+            .title(" 💾 Export Grading Scale ")
+            .title_bottom(Line::from(" Enter to confirm · Esc to go back ").right_aligned());
+
+        let inner = block.inner(popup_area);
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+
+        let [format_area, _, file_area] = Layout::vertical([
+            Constraint::Length(FORMATS.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let list = List::new(FORMATS.map(|format| format!(" {format}")))
+            .style(THEME.text())
+            .highlight_style(THEME.table_row_selected())
+            .highlight_symbol(THEME.highlight_symbol())
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, format_area, buf, &mut self.state);
+
+        if self.step == ExportStep::FileName {
+            Paragraph::new(Line::from(vec![
+                Span::from(" File ").style(THEME.tag(true)),
+                Span::from(format!(" {}", self.input.value())).style(THEME.text()),
+            ]))
+            .render(file_area, buf);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::Widget,
+};
+
+use crate::locale::format_percentage;
+
+/// Banner above the report if more students failed than the fail limit allows.
+#[derive(Debug, Default, Clone)]
+pub struct FailWarning {
+    failed: usize,
+    total: usize,
+    limit: f64, // share of failed students which is still fine
+}
+
+impl FailWarning {
+    pub fn set_limit(&mut self, limit: f64) {
+        self.limit = limit;
+    }
+
+    pub fn set_data(&mut self, failed: usize, total: usize) {
+        self.failed = failed;
+        self.total = total;
+    }
+
+    // the text of the banner, none while the limit is kept.
+    pub fn message(&self) -> Option<String> {
+        let share = match self.total {
+            0 => return None,
+            total => self.failed as f64 / total as f64,
+        };
+        (share > self.limit).then(|| {
+            format!(
+                "⚠ {} of {} students failed ({}), more than the limit of {}. A justification may be required.",
+                self.failed,
+                self.total,
+                format_percentage(share),
+                format_percentage(self.limit)
+            )
+        })
+    }
+}
+
+impl Widget for &FailWarning {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(message) = self.message() else {
+            return;
+        };
+        let style = Style::default().fg(Color::White).bg(Color::Red).bold();
+        buf.set_style(area, style);
+        Line::from(message)
+            .style(style)
+            .centered()
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        let mut warning = FailWarning::default();
+        warning.set_limit(1.0 / 3.0);
+        assert_eq!(warning.message(), None);
+
+        warning.set_data(10, 30);
+        assert_eq!(warning.message(), None);
+        warning.set_data(13, 30);
+        assert_eq!(
+            warning.message().unwrap(),
+            "⚠ 13 of 30 students failed (43%), more than the limit of 33%. A justification may be required."
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Clear, HighlightSpacing, List, ListItem, ListState, StatefulWidget, Widget},
+};
+use tracing::debug;
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::action::Action;
+
+/// File extensions which can be opened as a student list.
+const SUPPORTED_EXTENSIONS: [&str; 2] = ["csv", "xlsx"];
+
+#[derive(Debug, Default)]
+pub struct FilePicker {
+    dir: PathBuf,
+    entries: Vec<PathBuf>,
+    state: ListState,
+}
+
+impl FilePicker {
+    pub fn change_dir(&mut self, dir: &Path) {
+        self.dir = dir.canonicalize().unwrap_or(dir.to_path_buf());
+        self.refresh();
+    }
+
+    // read the directory content again and reset the selection.
+    pub fn refresh(&mut self) {
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for path in entries.filter_map(|entry| entry.ok()).map(|e| e.path()) {
+                let hidden = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.'));
+                if hidden {
+                    continue;
+                }
+
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if FilePicker::is_supported(&path) {
+                    files.push(path);
+                }
+            }
+        }
+        dirs.sort();
+        files.sort();
+
+        self.entries = dirs;
+        self.entries.append(&mut files);
+        self.state.select(Some(0));
+    }
+
+    fn is_supported(path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SUPPORTED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+    }
+
+    // the parent dir is always shown as first entry.
+    fn selected(&self) -> Option<PathBuf> {
+        match self.state.selected()? {
+            0 => self.dir.parent().map(Path::to_path_buf),
+            index => self.entries.get(index - 1).cloned(),
+        }
+    }
+
+    fn go_to_parent(&mut self) {
+        if let Some(parent) = self.dir.parent().map(Path::to_path_buf) {
+            self.change_dir(&parent);
+        }
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        debug!("EVENT: {:?}", key);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.select_previous();
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.select_next();
+                None
+            }
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => {
+                self.go_to_parent();
+                None
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => match self.selected() {
+                Some(path) if path.is_dir() => {
+                    self.change_dir(&path);
+                    None
+                }
+                Some(path) => Some(Action::LoadStudentList(path)),
+                None => None,
+            },
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseFilePicker),
+            _ => None,
+        }
+    }
+}
+
+impl Widget for &mut FilePicker {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let name = |path: &PathBuf| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            match path.is_dir() {
+                true => format!("📁 {name}/"),
+                false => format!("📄 {name}"),
+            }
+        };
+
+        let items: Vec<ListItem> = std::iter::once("📁 ../".to_string())
+            .chain(self.entries.iter().map(name))
+            .enumerate()
+            .map(|(i, entry)| ListItem::new(entry).style(THEME.table_row(i)))
+            .collect();
+
+        let block = THEME
+            .frame()
+            .title(format!(" 📂 {} ", self.dir.display()))
+            .title_bottom(Line::from(" .csv .xlsx ").right_aligned());
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(THEME.table_row_selected())
+            .highlight_symbol(THEME.highlight_symbol())
+            .highlight_spacing(HighlightSpacing::Always);
+
+        let popup_area = centered_rect(area, Constraint::Max(70), Constraint::Percentage(80));
+        Clear.render(popup_area, buf);
+        StatefulWidget::render(list, popup_area, buf, &mut self.state);
+    }
+}
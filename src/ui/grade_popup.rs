@@ -0,0 +1,86 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Clear, HighlightSpacing, List, ListItem, ListState, StatefulWidget, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+    truncate,
+};
+use crate::action::Action;
+
+const POPUP_WIDTH: u16 = 40;
+
+/// Lists the students who got the grade selected in the report tab.
+#[derive(Debug, Default)]
+pub struct GradeStudentsPopup {
+    grade: u8,
+    names: Vec<String>,
+    state: ListState,
+}
+
+impl GradeStudentsPopup {
+    pub fn new(grade: u8, names: Vec<String>) -> Self {
+        Self {
+            grade,
+            names,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.select_next(),
+            KeyCode::Enter => {
+                let name = self.names.get(self.state.selected()?)?;
+                return Some(Action::SelectStudent(name.clone()));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Some(Action::CloseGradeStudents),
+            _ => {}
+        }
+        None
+    }
+}
+
+impl Widget for &mut GradeStudentsPopup {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = match self.names.is_empty() {
+            true => vec![ListItem::new("no students").style(THEME.text())],
+            false => self
+                .names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    // the popup borders and the highlight symbol take the rest of the width.
+                    let width = POPUP_WIDTH as usize - 2 - THEME.highlight_symbol().width();
+                    ListItem::new(truncate(name, width)).style(THEME.table_row(i))
+                })
+                .collect(),
+        };
+
+        let block = THEME
+            .frame()
+            .title(format!(" 🎓 Grade {} ", self.grade))
+            .title_bottom(Line::from(format!(" {} students ", self.names.len())).right_aligned());
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(THEME.table_row_selected())
+            .highlight_symbol(THEME.highlight_symbol())
+            .highlight_spacing(HighlightSpacing::Always);
+
+        let popup_area = centered_rect(
+            area,
+            Constraint::Length(POPUP_WIDTH),
+            Constraint::Max(self.names.len().max(1) as u16 + 2),
+        );
+        Clear.render(popup_area, buf);
+        StatefulWidget::render(list, popup_area, buf, &mut self.state);
+    }
+}
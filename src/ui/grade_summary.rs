@@ -0,0 +1,75 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+
+use super::theme::{AppStyle, THEME};
+use crate::locale::{format_average, format_percentage};
+
+/// The "Notenspiegel": students per grade, average and pass quota in one line,
+/// as it goes into the exam protocol.
+#[derive(Debug, Default, Clone)]
+pub struct GradeSummary {
+    counts: [u8; 6],
+    avg: f64,
+    passed: usize,
+    total: usize,
+}
+
+impl GradeSummary {
+    pub fn set_data(&mut self, counts: &[u8; 6], avg: f64, passed: usize, total: usize) {
+        self.counts = counts.to_owned();
+        self.avg = avg;
+        self.passed = passed;
+        self.total = total;
+    }
+
+    pub fn text(&self) -> String {
+        let counts: Vec<String> = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(g, count)| format!("{}: {count}", g + 1))
+            .collect();
+        let quota = match self.total {
+            0 => 0.0,
+            total => self.passed as f64 / total as f64,
+        };
+        format!(
+            "{} | Ø {} | passed {}/{} ({})",
+            counts.join(", "),
+            format_average(self.avg),
+            self.passed,
+            self.total,
+            format_percentage(quota)
+        )
+    }
+}
+
+impl Widget for &GradeSummary {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = THEME.frame().title(Line::raw(" 📋 Notenspiegel "));
+
+        Paragraph::new(Line::from(self.text()).centered())
+            .style(THEME.text())
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let mut summary = GradeSummary::default();
+        summary.set_data(&[3, 7, 5, 2, 1, 0], 2.5, 17, 18);
+        assert_eq!(
+            summary.text(),
+            "1: 3, 2: 7, 3: 5, 4: 2, 5: 1, 6: 0 | Ø 2.5 | passed 17/18 (94%)"
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{
+        Clear, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
+        Widget,
+    },
+};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::{action::Action, keymap};
+
+/// The tab or mode a group of keybindings belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    General,
+    Scale,
+    Result,
+    Report,
+    Edit,
+    Insert,
+    Search,
+    FilePicker,
+    Export,
+    GradeStudents,
+    RowMenu,
+    Confirm,
+    Help,
+    Log,
+    Chord,
+    Welcome,
+}
+
+/// A group of keybindings which belong to the same tab or mode.
+/// `hints` is the short version shown in the bottom bar.
+pub struct KeyGroup {
+    pub context: KeyContext,
+    pub title: &'static str,
+    pub keys: &'static [(&'static str, &'static str)],
+    pub hints: &'static [(&'static str, &'static str)],
+}
+
+pub const KEYBINDINGS: [KeyGroup; 16] = [
+    KeyGroup {
+        context: KeyContext::General,
+        title: "General",
+        keys: &[
+            ("1 2 3 4 / F2 F3 F4", "switch tab"),
+            ("<count><key>", "repeat a key, e.g. 5+ or 10PageUp"),
+            ("j k gg G", "move in the tabs"),
+            ("h l", "move in the tabs with keymap = \"vim\""),
+            (
+                "Ctrl+n/p/f/b Alt+< Alt+>",
+                "move in the tabs with keymap = \"emacs\"",
+            ),
+            ("v", "toggle split view of scale and results"),
+            ("Tab / Shift+Tab", "focus next / previous pane"),
+            ("f", "toggle full width layout"),
+            ("I T L C", "switch grading scale"),
+            (".", "toggle half points"),
+            ("p", "set max points"),
+            ("u / Ctrl+z", "undo"),
+            ("Ctrl+r / Ctrl+y", "redo"),
+            ("Ctrl+t", "switch theme"),
+            (":", "enter command"),
+            ("o", "open student list"),
+            ("w", "save student list"),
+            ("e", "export grading scale"),
+            ("E", "export grading scale, choose the format"),
+            ("? / F1", "show this help"),
+            ("F12", "show the log"),
+            ("q / Ctrl+c", "quit"),
+        ],
+        hints: &[(":", "Command"), ("?", "Help"), ("q", "Quit")],
+    },
+    KeyGroup {
+        context: KeyContext::Chord,
+        title: "Key sequences",
+        keys: &keymap::CHORDS,
+        hints: &[("Esc", "Cancel")],
+    },
+    KeyGroup {
+        context: KeyContext::Scale,
+        title: "Scale tab",
+        keys: &[
+            ("↑ ↓", "select grade"),
+            ("Home End / Ctrl+u Ctrl+d", "first / last grade"),
+            ("Alt+1 … Alt+6", "jump to grade"),
+            ("← →", "select min / max column"),
+            ("+ -", "change threshold"),
+            ("mouse drag on MIN", "change threshold"),
+            (
+                "Enter / i",
+                "type threshold (Enter to apply, Esc to cancel)",
+            ),
+            ("PageUp PageDown", "change max points"),
+            ("r", "reset thresholds"),
+            ("Esc", "clear column selection"),
+        ],
+        hints: &[
+            ("+ -", "Threshold"),
+            ("Enter", "Edit"),
+            ("I T L C", "Scale"),
+        ],
+    },
+    KeyGroup {
+        context: KeyContext::Result,
+        title: "Result tab",
+        keys: &[
+            ("↑ ↓", "select student"),
+            ("Home End", "first / last student"),
+            ("PageUp PageDown / Ctrl+u Ctrl+d", "scroll page-wise"),
+            ("← →", "select column"),
+            ("Enter", "student actions"),
+            ("c", "edit name / points (Enter to apply, Esc to cancel)"),
+            ("+ -", "change points"),
+            ("d", "delete student"),
+            ("i", "toggle student details"),
+            ("s", "sort by name / points / grade"),
+            ("S", "reverse sort order"),
+            ("/", "search student"),
+            ("n N", "next / previous match"),
+            ("Esc", "clear the grade filter"),
+        ],
+        hints: &[
+            ("↑ ↓", "Select"),
+            ("+ -", "Points"),
+            ("Enter", "Actions"),
+            ("s", "Sort"),
+            ("/", "Search"),
+        ],
+    },
+    KeyGroup {
+        context: KeyContext::Report,
+        title: "Report tab",
+        keys: &[
+            ("o", "toggle horizontal / vertical chart"),
+            ("h", "toggle grade / points distribution"),
+            (
+                "c",
+                "compare grades, average and pass rate with another scale",
+            ),
+            ("[ ]", "change bin width of the points distribution"),
+            ("← →", "select grade"),
+            ("Enter", "list the students of the selected grade"),
+            ("r", "show the selected grade in the result tab"),
+        ],
+        hints: &[("o", "Orientation"), ("h", "Points"), ("c", "Compare")],
+    },
+    KeyGroup {
+        context: KeyContext::Edit,
+        title: "Inline editor",
+        keys: &[("Enter", "apply"), ("Esc", "cancel")],
+        hints: &[("Enter", "Apply"), ("Esc", "Cancel")],
+    },
+    KeyGroup {
+        context: KeyContext::Insert,
+        title: "Insert mode",
+        keys: &[
+            ("Tab", "complete command or path"),
+            ("↑ ↓", "previous / next command"),
+            ("toggle-column <name>", "show / hide a table column"),
+            ("rename-course <name>", "change the course name"),
+            ("recent [n]", "list or open the recent files"),
+            ("curve [avg]", "suggest a scale for the target average"),
+            ("Enter", "execute"),
+            ("Esc", "cancel"),
+        ],
+        hints: &[("Tab", "Complete"), ("Enter", "Execute"), ("Esc", "Cancel")],
+    },
+    KeyGroup {
+        context: KeyContext::Search,
+        title: "Search",
+        keys: &[("Enter", "keep matches"), ("Esc", "clear search")],
+        hints: &[("Enter", "Keep"), ("Esc", "Clear")],
+    },
+    KeyGroup {
+        context: KeyContext::FilePicker,
+        title: "File picker",
+        keys: &[
+            ("↑ ↓ / j k", "select entry"),
+            ("Enter / l", "open file or directory"),
+            ("Backspace / h", "parent directory"),
+            ("Esc", "close"),
+        ],
+        hints: &[("Enter", "Open"), ("h", "Parent"), ("Esc", "Close")],
+    },
+    KeyGroup {
+        context: KeyContext::Export,
+        title: "Export",
+        keys: &[
+            ("↑ ↓ / j k", "select format"),
+            ("Enter", "confirm format / file name"),
+            ("Esc", "go back / close"),
+        ],
+        hints: &[("Enter", "Confirm"), ("Esc", "Back")],
+    },
+    KeyGroup {
+        context: KeyContext::GradeStudents,
+        title: "Students of a grade",
+        keys: &[
+            ("↑ ↓ / j k", "select student"),
+            ("Enter", "show student in the result tab"),
+            ("Esc", "close"),
+        ],
+        hints: &[("↑ ↓", "Select"), ("Enter", "Show"), ("Esc", "Close")],
+    },
+    KeyGroup {
+        context: KeyContext::RowMenu,
+        title: "Student actions",
+        keys: &[
+            ("↑ ↓ / j k", "select action"),
+            ("Enter", "run the selected action"),
+            (
+                "e r n a d y",
+                "edit points / rename / note / absent / delete / copy",
+            ),
+            ("Esc", "close"),
+        ],
+        hints: &[("↑ ↓", "Select"), ("Enter", "Run"), ("Esc", "Close")],
+    },
+    KeyGroup {
+        context: KeyContext::Confirm,
+        title: "Confirm",
+        keys: &[("y / Enter", "yes"), ("n / Esc", "no")],
+        hints: &[("y", "Yes"), ("n", "No")],
+    },
+    KeyGroup {
+        context: KeyContext::Help,
+        title: "Help",
+        keys: &[
+            ("↑ ↓ / j k", "scroll"),
+            ("PageUp PageDown", "scroll a page"),
+            ("Home End / g G", "scroll to the top / bottom"),
+            ("Esc / q", "close help"),
+        ],
+        hints: &[("↑↓", "Scroll"), ("Esc", "Close")],
+    },
+    KeyGroup {
+        context: KeyContext::Welcome,
+        title: "Welcome screen",
+        keys: &[
+            ("↑ ↓ / j k", "select entry"),
+            ("Enter", "open recent file / entry"),
+            ("1-9", "open recent file by number"),
+            ("o", "open another file"),
+            ("Esc", "start without course"),
+        ],
+        hints: &[("Enter", "Open"), ("o", "Open file"), ("Esc", "Skip")],
+    },
+    KeyGroup {
+        context: KeyContext::Log,
+        title: "Log",
+        keys: &[
+            ("↑ ↓ / j k", "scroll"),
+            ("g G", "first / last line"),
+            ("l", "filter by level"),
+            ("r", "reload"),
+            ("Esc / F12", "close log"),
+        ],
+        hints: &[("l", "Level"), ("r", "Reload"), ("Esc", "Close")],
+    },
+];
+
+// returns the bottom bar hints of the given context.
+pub fn hints(context: KeyContext) -> &'static [(&'static str, &'static str)] {
+    KEYBINDINGS
+        .iter()
+        .find(|group| group.context == context)
+        .map(|group| group.hints)
+        .unwrap_or_default()
+}
+
+/// Lists all keybindings, scrollable if they don't fit on the screen.
+#[derive(Debug, Default)]
+pub struct HelpPopup {
+    scroll: usize,    // first shown line
+    page_size: usize, // number of visible lines, updated on render
+}
+
+impl HelpPopup {
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') | KeyCode::F(1) => {
+                return Some(Action::HideHelp)
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(self.page_size),
+            KeyCode::PageDown => self.scroll += self.page_size,
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            // clamped to the last page on render.
+            KeyCode::End | KeyCode::Char('G') => self.scroll = usize::MAX,
+            _ => {}
+        }
+        None
+    }
+}
+
+impl Widget for &mut HelpPopup {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let key_width = KEYBINDINGS
+            .iter()
+            .flat_map(|group| group.keys.iter())
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or_default();
+        let description_width = KEYBINDINGS
+            .iter()
+            .flat_map(|group| group.keys.iter())
+            .map(|(_, description)| description.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines: Vec<Line> = Vec::new();
+        for group in KEYBINDINGS.iter() {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(group.title).style(THEME.table_header().bold()));
+            for (key, description) in group.keys {
+                lines.push(Line::from(vec![
+                    Span::from(format!(" {:>key_width$} ", key)).style(THEME.tag(true)),
+                    Span::from(format!(" {description}")).style(THEME.text()),
+                ]));
+            }
+        }
+
+        let popup_area = centered_rect(
+            area,
+            Constraint::Length((key_width + description_width) as u16 + 8),
+            Constraint::Length(lines.len() as u16 + 4),
+        );
+
+        // borders and the empty line above and below are no lines.
+        self.page_size = (popup_area.height.saturating_sub(4) as usize).max(1);
+        let max_scroll = lines.len().saturating_sub(self.page_size);
+        self.scroll = self.scroll.min(max_scroll);
+
+        let block = THEME
+            .frame()
+            .title(" ⌨ Keybindings ")
+            .title_bottom(Line::from(" Esc to close ").right_aligned())
+            .padding(Padding::new(1, 1, 1, 1));
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((self.scroll as u16, 0))
+            .render(popup_area, buf);
+
+        if max_scroll > 0 {
+            let scrollbar_area = Rect {
+                y: popup_area.y + 1,
+                height: popup_area.height.saturating_sub(2),
+                ..popup_area
+            };
+            let mut scroll_state = ScrollbarState::new(max_scroll).position(self.scroll);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(THEME.text())
+                .render(scrollbar_area, buf, &mut scroll_state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll() {
+        let mut popup = HelpPopup::default();
+        let area = Rect::new(0, 0, 100, 24);
+        let mut buf = Buffer::empty(area);
+        popup.render(area, &mut buf);
+        assert_eq!(popup.page_size, 20);
+
+        popup.handle_event(KeyEvent::from(KeyCode::PageDown));
+        assert_eq!(popup.scroll, 20);
+        popup.handle_event(KeyEvent::from(KeyCode::End));
+        popup.render(area, &mut buf);
+        let lines: usize = KEYBINDINGS.iter().map(|group| group.keys.len() + 2).sum();
+        assert_eq!(popup.scroll, lines - 1 - 20);
+        popup.handle_event(KeyEvent::from(KeyCode::Char('g')));
+        assert_eq!(popup.scroll, 0);
+    }
+}
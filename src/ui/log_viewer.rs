@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Clear, Paragraph, Widget},
+};
+use tracing::Level;
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::action::Action;
+
+/// Number of lines read from the end of the log file.
+const TAIL_LINES: usize = 500;
+
+/// Shows the tail of the log file, filtered by the minimum level.
+#[derive(Debug, Default)]
+pub struct LogViewer {
+    path: PathBuf,
+    lines: Vec<String>,
+    level: Option<Level>, // most verbose level shown, all lines if none
+    scroll: usize,        // lines hidden below the view
+}
+
+impl LogViewer {
+    pub fn new(path: &Path) -> Self {
+        let mut viewer = Self {
+            path: path.to_path_buf(),
+            ..Default::default()
+        };
+        viewer.reload();
+        viewer
+    }
+
+    // read the file again and jump to the end.
+    fn reload(&mut self) {
+        self.lines = match fs::read_to_string(&self.path) {
+            Ok(content) => {
+                let lines: Vec<&str> = content.lines().collect();
+                let start = lines.len().saturating_sub(TAIL_LINES);
+                lines[start..].iter().map(|line| line.to_string()).collect()
+            }
+            Err(e) => vec![format!("Could not read '{}': {e}", self.path.display())],
+        };
+        self.scroll = 0;
+    }
+
+    // all, then only errors and more verbose levels from there.
+    fn cycle_level(&mut self) {
+        self.level = match self.level {
+            None => Some(Level::ERROR),
+            Some(Level::ERROR) => Some(Level::WARN),
+            Some(Level::WARN) => Some(Level::INFO),
+            Some(Level::INFO) => Some(Level::DEBUG),
+            Some(_) => None,
+        };
+        self.scroll = 0;
+    }
+
+    fn visible_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .map(|line| line.as_str())
+            .filter(|line| match (self.level, line_level(line)) {
+                (Some(level), Some(line_level)) => line_level <= level,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .collect()
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        let max_scroll = self.visible_lines().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = (self.scroll + 1).min(max_scroll),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageUp => self.scroll = (self.scroll + 10).min(max_scroll),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('g') => self.scroll = max_scroll,
+            KeyCode::Char('G') => self.scroll = 0,
+            KeyCode::Char('l') => self.cycle_level(),
+            KeyCode::Char('r') => self.reload(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::F(12) => return Some(Action::HideLog),
+            _ => {}
+        }
+        None
+    }
+}
+
+// the level is the second word of a line, after the timestamp.
+fn line_level(line: &str) -> Option<Level> {
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+fn level_style(level: Option<Level>) -> Style {
+    match level {
+        Some(Level::ERROR) => THEME.error_text(),
+        Some(Level::WARN) => Style::default().fg(Color::Yellow),
+        _ => THEME.text(),
+    }
+}
+
+impl Widget for &mut LogViewer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area =
+            centered_rect(area, Constraint::Percentage(90), Constraint::Percentage(90));
+        let height = popup_area.height.saturating_sub(2) as usize;
+
+        let visible = self.visible_lines();
+        let end = visible.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(height);
+        let lines: Vec<Line> = visible[start..end]
+            .iter()
+            .map(|line| Line::from(line.to_string()).style(level_style(line_level(line))))
+            .collect();
+
+        let level = match self.level {
+            Some(level) => format!(" level ≤ {level} "),
+            None => " all levels ".to_string(),
+        };
+        let block = THEME
+            .frame()
+            .title(format!(" 📜 {} ", self.path.display()))
+            .title_bottom(Line::from(level).left_aligned())
+            .title_bottom(Line::from(" l level · r reload · Esc close ").right_aligned());
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_filter() {
+        let mut viewer = LogViewer {
+            lines: vec![
+                "2024-05-01T10:00:00.000000Z ERROR src/app.rs:1: failed".to_string(),
+                "2024-05-01T10:00:01.000000Z  INFO src/app.rs:2: loaded".to_string(),
+                "2024-05-01T10:00:02.000000Z DEBUG src/app.rs:3: ACTION: Quit".to_string(),
+                "continued message".to_string(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(viewer.visible_lines().len(), 4);
+
+        viewer.cycle_level();
+        assert_eq!(viewer.visible_lines().len(), 1);
+
+        viewer.cycle_level();
+        viewer.cycle_level();
+        assert_eq!(viewer.visible_lines().len(), 2);
+    }
+}
@@ -1,24 +1,135 @@
-use strum_macros::EnumIter;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    text::{Line, Text},
+};
+use serde::Deserialize;
+use strum_macros::{Display, EnumIter, EnumString};
+use theme::{AppStyle, THEME};
+use tui_input::Input;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+pub mod box_plot;
+pub mod command_line;
+pub mod confirm;
+pub mod cumulative_chart;
+pub mod curve_preview;
+pub mod export_modal;
+pub mod fail_warning;
+pub mod file_picker;
+pub mod grade_popup;
+pub mod grade_summary;
+pub mod help;
+pub mod log_viewer;
+pub mod note_popup;
+pub mod notification;
+pub mod overlay;
+pub mod pass_gauge;
+pub mod points_popup;
 pub mod report_tab;
+pub mod row_menu;
 pub mod scale_tab;
+pub mod stats_tab;
+pub mod student_pane;
 pub mod students_tab;
 pub mod theme;
+pub mod welcome;
+pub mod which_key;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AppTab {
     #[default]
     Scale,
     Result,
     Report,
+    Stats,
 }
 
-impl AppTab {
-    pub fn to_string(&self) -> String {
+impl std::fmt::Display for AppTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AppTab::Scale => "Scale [1]".into(),
-            AppTab::Result => "Result [2]".into(),
-            AppTab::Report => "Report [3]".into(),
+            AppTab::Scale => write!(f, "Scale"),
+            AppTab::Result => write!(f, "Result"),
+            AppTab::Report => write!(f, "Report"),
+            AppTab::Stats => write!(f, "Stats"),
         }
     }
 }
+
+/// Columns of the scale and result table, which can be hidden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Display, EnumString, EnumIter)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TableColumn {
+    // scale table
+    Grade,
+    Label,
+    Min,
+    Max,
+    Pct,
+    // result table, which also shows the grade
+    Name,
+    Points,
+    Percentage,
+    Percentile,
+    Average,
+}
+
+impl TableColumn {
+    // the columns hidden if nothing else is configured.
+    pub fn default_hidden() -> Vec<TableColumn> {
+        vec![TableColumn::Label, TableColumn::Percentile]
+    }
+}
+
+/// helper function to create a centered rect inside the given area.
+pub fn centered_rect(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
+    let [area] = Layout::horizontal([horizontal])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
+    area
+}
+
+/// helper function to show the value of an inline editor with a cursor and a validation error below.
+pub fn editor_text<'a>(editor: &Input, error: Option<&str>) -> Text<'a> {
+    Text::from(vec![
+        Line::default(),
+        Line::from(format!("{}▏", editor.value())),
+        Line::from(error.unwrap_or_default().to_string()).style(THEME.error_text()),
+    ])
+}
+
+/// helper function to shorten a text to the given display width, the cut is marked with an ellipsis.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut used = 1; // the ellipsis
+    for c in text.chars() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            break;
+        }
+        truncated.push(c);
+    }
+    if width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("Anna", 4), "Anna");
+        assert_eq!(truncate("Annabelle", 5), "Anna…");
+        // wide characters take two columns.
+        assert_eq!(truncate("李小龍先生", 6), "李小…");
+        assert_eq!(truncate("Anna", 0), "");
+    }
+}
@@ -0,0 +1,65 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::action::{Action, ModelAction};
+
+/// Small modal to edit the note of a student.
+#[derive(Debug, Default)]
+pub struct NotePopup {
+    name: String,
+    input: Input,
+}
+
+impl NotePopup {
+    // start with the current note as value.
+    pub fn new(name: &str, notes: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            input: Input::new(notes.to_string()),
+        }
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Enter => Some(Action::UpdateModel(ModelAction::SetStudentNotes(
+                self.name.clone(),
+                self.input.value().to_string(),
+            ))),
+            KeyCode::Esc => Some(Action::CloseNotePopup),
+            _ => {
+                self.input.handle_event(&Event::Key(key));
+                None
+            }
+        }
+    }
+}
+
+impl Widget for &NotePopup {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(area, Constraint::Length(50), Constraint::Length(3));
+
+        let block = THEME
+            .frame()
+            .title(format!(" 📝 Note for {} ", self.name))
+            .title_bottom(Line::from(" Enter to apply · Esc to cancel ").right_aligned());
+
+        let line = Line::from(vec![
+            Span::from(format!(" {}", self.input.value())).style(THEME.text()),
+            Span::from(" ").style(THEME.text().reversed()),
+        ]);
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(line).block(block).render(popup_area, buf);
+    }
+}
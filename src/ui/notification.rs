@@ -0,0 +1,102 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::Widget,
+};
+
+use super::theme::{AppStyle, THEME};
+
+/// How long a single notification is shown.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    message: String,
+    level: NotificationLevel,
+    shown_since: Option<Instant>,
+}
+
+/// Queue of notifications which are shown one after another.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Notification>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        self.queue.push_back(Notification {
+            message: message.into(),
+            level,
+            shown_since: None,
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Info, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        tracing::error!("{message}");
+        self.push(NotificationLevel::Error, message);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    // drop the current notification if it was shown long enough.
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        if let Some(current) = self.queue.front_mut() {
+            match current.shown_since {
+                Some(since) if now.duration_since(since) >= TOAST_DURATION => {
+                    self.queue.pop_front();
+                }
+                None => current.shown_since = Some(now),
+                _ => {}
+            }
+        }
+        if let Some(next) = self.queue.front_mut() {
+            next.shown_since.get_or_insert(now);
+        }
+    }
+}
+
+impl Widget for &Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(current) = self.queue.front() else {
+            return;
+        };
+
+        let style = match current.level {
+            NotificationLevel::Info => THEME.indicator(None),
+            NotificationLevel::Error => Style::default()
+                .fg(THEME.background_color(true))
+                .bg(Color::Red),
+        };
+
+        let pending = match self.queue.len() {
+            1 => String::new(),
+            n => format!(" (+{})", n - 1),
+        };
+
+        Line::from(format!(" {}{pending} ", current.message))
+            .centered()
+            .style(style)
+            .render(area, buf);
+    }
+}
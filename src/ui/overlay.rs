@@ -0,0 +1,145 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+use super::{
+    confirm::ConfirmDialog, curve_preview::CurvePreview, export_modal::ExportModal,
+    file_picker::FilePicker, grade_popup::GradeStudentsPopup, help::HelpPopup, help::KeyContext,
+    log_viewer::LogViewer, note_popup::NotePopup, points_popup::MaxPointsPopup, row_menu::RowMenu,
+    welcome::WelcomeScreen,
+};
+use crate::action::Action;
+
+/// A modal widget, which is drawn above the tabs.
+#[derive(Debug)]
+pub enum Overlay {
+    Welcome(WelcomeScreen),
+    Help(HelpPopup),
+    Log(LogViewer),
+    FilePicker(FilePicker),
+    Export(ExportModal),
+    MaxPoints(MaxPointsPopup),
+    GradeStudents(GradeStudentsPopup),
+    RowMenu(RowMenu),
+    Note(NotePopup),
+    Confirm(ConfirmDialog),
+    Curve(CurvePreview),
+}
+
+impl Overlay {
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match self {
+            Overlay::Welcome(screen) => screen.handle_event(key),
+            Overlay::Help(popup) => popup.handle_event(key),
+            Overlay::Log(viewer) => viewer.handle_event(key),
+            Overlay::FilePicker(picker) => picker.handle_event(key),
+            Overlay::Export(modal) => modal.handle_event(key),
+            Overlay::MaxPoints(popup) => popup.handle_event(key),
+            Overlay::GradeStudents(popup) => popup.handle_event(key),
+            Overlay::RowMenu(menu) => menu.handle_event(key),
+            Overlay::Note(popup) => popup.handle_event(key),
+            Overlay::Confirm(dialog) => dialog.handle_event(key),
+            Overlay::Curve(preview) => preview.handle_event(key),
+        }
+    }
+
+    pub fn key_context(&self) -> KeyContext {
+        match self {
+            Overlay::Welcome(_) => KeyContext::Welcome,
+            Overlay::Help(_) => KeyContext::Help,
+            Overlay::Log(_) => KeyContext::Log,
+            Overlay::FilePicker(_) => KeyContext::FilePicker,
+            Overlay::Export(_) => KeyContext::Export,
+            Overlay::MaxPoints(_) => KeyContext::Edit,
+            Overlay::GradeStudents(_) => KeyContext::GradeStudents,
+            Overlay::RowMenu(_) => KeyContext::RowMenu,
+            Overlay::Note(_) => KeyContext::Edit,
+            Overlay::Confirm(_) | Overlay::Curve(_) => KeyContext::Confirm,
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        match self {
+            Overlay::Welcome(screen) => screen.render(area, buf),
+            Overlay::Help(popup) => popup.render(area, buf),
+            Overlay::Log(viewer) => viewer.render(area, buf),
+            Overlay::FilePicker(picker) => picker.render(area, buf),
+            Overlay::Export(modal) => modal.render(area, buf),
+            Overlay::MaxPoints(popup) => popup.render(area, buf),
+            Overlay::GradeStudents(popup) => popup.render(area, buf),
+            Overlay::RowMenu(menu) => menu.render(area, buf),
+            Overlay::Note(popup) => popup.render(area, buf),
+            Overlay::Confirm(dialog) => dialog.render(area, buf),
+            Overlay::Curve(preview) => preview.render(area, buf),
+        }
+    }
+}
+
+/// Stack of the open modals. Only the top one receives key events.
+#[derive(Debug, Default)]
+pub struct OverlayStack {
+    overlays: Vec<Overlay>,
+}
+
+impl OverlayStack {
+    pub fn push(&mut self, overlay: Overlay) {
+        self.overlays.push(overlay);
+    }
+
+    // removes the topmost overlay which matches, even if it's not on top.
+    pub fn close(&mut self, matches: impl Fn(&Overlay) -> bool) {
+        if let Some(index) = self.overlays.iter().rposition(matches) {
+            self.overlays.remove(index);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overlays.is_empty()
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        self.overlays.last_mut()?.handle_event(key)
+    }
+
+    pub fn key_context(&self) -> Option<KeyContext> {
+        self.overlays.last().map(|overlay| overlay.key_context())
+    }
+}
+
+impl Widget for &mut OverlayStack {
+    // each overlay dims everything below it.
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for overlay in self.overlays.iter_mut() {
+            buf.set_style(area, Style::default().add_modifier(Modifier::DIM));
+            overlay.render(area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_close_overlay_below_top() {
+        let mut stack = OverlayStack::default();
+        stack.push(Overlay::Export(ExportModal::default()));
+        stack.push(Overlay::Confirm(ConfirmDialog::new("?", Action::Quit)));
+        assert_eq!(stack.key_context(), Some(KeyContext::Confirm));
+
+        stack.close(|overlay| matches!(overlay, Overlay::Export(_)));
+        assert_eq!(stack.key_context(), Some(KeyContext::Confirm));
+
+        let key = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(stack.handle_event(key), Some(Action::CloseConfirmation));
+
+        stack.close(|overlay| matches!(overlay, Overlay::Confirm(_)));
+        assert!(stack.is_empty());
+        assert_eq!(stack.handle_event(key), None);
+    }
+}
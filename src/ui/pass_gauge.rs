@@ -0,0 +1,66 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Gauge, Widget},
+};
+
+use super::theme::{AppStyle, THEME};
+use crate::locale::format_percentage;
+
+/// Share of students who passed the exam, colored by the warning thresholds.
+#[derive(Debug, Default, Clone)]
+pub struct PassGauge {
+    passed: usize,
+    total: usize,
+    warning: f64,  // below this quota the gauge turns yellow
+    critical: f64, // below this quota the gauge turns red
+}
+
+impl PassGauge {
+    pub fn set_thresholds(&mut self, warning: f64, critical: f64) {
+        self.warning = warning;
+        self.critical = critical;
+    }
+
+    pub fn set_data(&mut self, passed: usize, total: usize) {
+        self.passed = passed;
+        self.total = total;
+    }
+
+    fn ratio(&self) -> f64 {
+        match self.total {
+            0 => 0.0,
+            total => self.passed as f64 / total as f64,
+        }
+    }
+}
+
+impl Widget for &PassGauge {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let ratio = self.ratio();
+        let color = match ratio {
+            r if r < self.critical => Color::Red,
+            r if r < self.warning => Color::Yellow,
+            _ => Color::Green,
+        };
+
+        let block = THEME.frame().title(Line::raw(" ✅ Pass Quota "));
+
+        let label = Span::from(format!(
+            "{}/{} passed ({})",
+            self.passed,
+            self.total,
+            format_percentage(ratio)
+        ))
+        .style(THEME.text().bold());
+
+        Gauge::default()
+            .block(block)
+            .gauge_style(Style::default().fg(color).bg(THEME.background_color(false)))
+            .ratio(ratio)
+            .label(label)
+            .render(area, buf);
+    }
+}
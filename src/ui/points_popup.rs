@@ -0,0 +1,81 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget},
+};
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::{
+    action::{Action, ModelAction},
+    model::scale::{parse_max_points, GradingError},
+};
+
+/// Small modal to type the max points of the exam.
+#[derive(Debug, Default)]
+pub struct MaxPointsPopup {
+    input: Input,
+    error: Option<String>,
+}
+
+impl MaxPointsPopup {
+    // start with the current max points as value.
+    pub fn open(&mut self, max_points: f64) {
+        self.input = Input::new(max_points.to_string());
+        self.error = None;
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        self.error = None;
+        match key.code {
+            KeyCode::Enter => {
+                let value = self.input.value().trim();
+                match parse_max_points(value) {
+                    Ok(points) => Some(Action::UpdateModel(ModelAction::SetMaxPoints(points))),
+                    Err(e @ GradingError::NotANumber(_)) => {
+                        self.error = Some(e.to_string());
+                        None
+                    }
+                    Err(_) => {
+                        self.error = Some(format!("'{value}' are no valid points."));
+                        None
+                    }
+                }
+            }
+            KeyCode::Esc => Some(Action::CloseMaxPointsPopup),
+            _ => {
+                self.input.handle_event(&Event::Key(key));
+                None
+            }
+        }
+    }
+}
+
+impl Widget for &MaxPointsPopup {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(area, Constraint::Length(36), Constraint::Length(5));
+
+        let block = THEME
+            .frame()
+            .title(" 💯 Max Points ")
+            .title_bottom(Line::from(" Enter to apply · Esc to cancel ").right_aligned());
+
+        let lines = vec![
+            Line::from(vec![
+                Span::from(" Points ").style(THEME.tag(true)),
+                Span::from(format!(" {}", self.input.value())).style(THEME.text()),
+                Span::from(" ").style(THEME.text().reversed()),
+            ]),
+            Line::from(self.error.clone().unwrap_or_default()).style(THEME.error_text()),
+        ];
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+}
@@ -1,17 +1,47 @@
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     buffer::Buffer,
     layout::{Direction, Rect},
-    style::Stylize,
+    style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Bar, BarChart, BarGroup, Block, BorderType, Borders, Padding, Widget},
+    widgets::{Bar, BarChart, BarGroup, Padding, Widget},
 };
 
 use super::theme::{AppStyle, THEME};
+use crate::{
+    action::Action,
+    locale::{format_average, format_percentage},
+    model::{scale::GradeScaleType, stats::histogram},
+};
+
+/// Bin width of the points histogram if nothing else is configured.
+pub const DEFAULT_BIN_WIDTH: f64 = 5.0;
+
+/// Grade distribution, average and pass rate if another scale would be used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub scale_type: GradeScaleType,
+    pub data: [u8; 6],
+    pub avg: f64,
+    pub pass_rate: Option<f64>,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct ExamChart {
     data: [u8; 6],
     avg: f64,
+    horizontal: bool,
+    show_points: bool, // show a histogram of the raw points instead of the grades
+    points: Vec<f64>,
+    thresholds: Vec<(u8, f64)>, // min points per grade, best grade first
+    max_points: f64,
+    bin_width: f64,
+    comparisons: Vec<Comparison>, // outcome with other scales
+    compare_with: Option<usize>,  // index into comparisons
+    selected: Option<usize>,      // index of the selected grade bar
+    target_avg: Option<f64>,
+    passed: usize,
+    failed: usize,
 }
 
 impl ExamChart {
@@ -23,6 +53,238 @@ impl ExamChart {
         self.data = values.to_owned();
         self.avg = avg;
     }
+
+    pub fn set_pass_counts(&mut self, passed: usize, failed: usize) {
+        self.passed = passed;
+        self.failed = failed;
+    }
+
+    // e.g. "17 passed, 13 failed (57%)", empty without students.
+    fn pass_label(&self) -> String {
+        match self.passed + self.failed {
+            0 => String::new(),
+            total => format!(
+                "{} passed, {} failed ({})",
+                self.passed,
+                self.failed,
+                format_percentage(self.passed as f64 / total as f64)
+            ),
+        }
+    }
+
+    pub fn set_points(&mut self, points: Vec<f64>, thresholds: Vec<(u8, f64)>, max_points: f64) {
+        self.points = points;
+        self.thresholds = thresholds;
+        self.max_points = max_points;
+    }
+
+    pub fn set_comparisons(&mut self, comparisons: Vec<Comparison>) {
+        self.comparisons = comparisons;
+    }
+
+    // cycle through the comparison scales and back to no comparison.
+    fn cycle_comparison(&mut self) {
+        self.compare_with = match self.compare_with {
+            None if !self.comparisons.is_empty() => Some(0),
+            Some(index) if index + 1 < self.comparisons.len() => Some(index + 1),
+            _ => None,
+        };
+    }
+
+    pub fn set_target_average(&mut self, target: Option<f64>) {
+        self.target_avg = target;
+    }
+
+    // draw a marker for the given average grade next to the bars, interpolated
+    // between the bar centers. `inner` is the area of the bars.
+    fn render_marker(
+        &self,
+        grade: f64,
+        symbol: &str,
+        style: Style,
+        (bar_width, bar_gap): (u16, u16),
+        inner: Rect,
+        buf: &mut Buffer,
+    ) {
+        if !(1.0..=6.0).contains(&grade) {
+            return;
+        }
+        let offset = ((grade - 1.0) * (bar_width + bar_gap) as f64 + bar_width as f64 / 2.0) as u16;
+        let (x, y) = match self.horizontal {
+            true => (inner.right() + 1, inner.y + offset),
+            false => (inner.x + offset, inner.y.saturating_sub(1)),
+        };
+        if x < buf.area.right() && y < inner.bottom() {
+            buf.set_string(x, y, symbol, style);
+        }
+    }
+
+    pub fn set_bin_width(&mut self, bin_width: f64) {
+        self.bin_width = bin_width.max(1.0);
+    }
+
+    // move the grade selection, starting at the best grade.
+    fn select_grade(&mut self, step: isize) {
+        self.selected = match self.selected {
+            None => Some(0),
+            Some(index) => Some((index as isize + step).clamp(0, 5) as usize),
+        };
+    }
+
+    // the selected bar is drawn in the text color.
+    fn bar_style(&self, index: usize, style: Style) -> Style {
+        match self.selected == Some(index) && !self.show_points {
+            true => style.fg(THEME.text_color(false)).bold(),
+            false => style,
+        }
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('o') => self.horizontal = !self.horizontal,
+            KeyCode::Char('h') => self.show_points = !self.show_points,
+            KeyCode::Char('c') => self.cycle_comparison(),
+            KeyCode::Char('[') => self.set_bin_width(self.bin_width - 1.0),
+            KeyCode::Char(']') => self.set_bin_width(self.bin_width + 1.0),
+            KeyCode::Left => self.select_grade(-1),
+            KeyCode::Right => self.select_grade(1),
+            KeyCode::Enter if !self.show_points => {
+                let index = self.selected?;
+                return Some(Action::ShowGradeStudents(index as u8 + 1));
+            }
+            KeyCode::Char('r') if !self.show_points => {
+                let index = self.selected?;
+                return Some(Action::FilterGrade(Some(index as u8 + 1)));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn grade_for_points(&self, points: f64) -> u8 {
+        self.thresholds
+            .iter()
+            .find(|(_, min)| points >= *min)
+            .map(|(grade, _)| *grade)
+            .unwrap_or(6)
+    }
+
+    // count and share of the class, shortened if the bar is too narrow.
+    fn value_label(count: u64, total: u64, max_width: u16) -> String {
+        let pct = match total {
+            0 => 0.0,
+            total => count as f64 / total as f64 * 100.0,
+        };
+        [format!("{count} ({pct:.0}%)"), format!("{pct:.0}%")]
+            .into_iter()
+            .find(|label| label.len() < max_width as usize)
+            .unwrap_or(count.to_string())
+    }
+
+    fn bins(&self) -> Vec<usize> {
+        histogram(&self.points, self.bin_width, self.max_points)
+    }
+
+    // one bar per bin, colored by the grade of the lower bound.
+    fn points_bars(&self, value_width: u16) -> Vec<Bar<'_>> {
+        let total = self.points.len() as u64;
+        self.bins()
+            .into_iter()
+            .enumerate()
+            .map(|(index, count)| {
+                let start = index as f64 * self.bin_width;
+                let style = Style::default().fg(THEME.grade_color(self.grade_for_points(start)));
+                Bar::default()
+                    .value(count as u64)
+                    .text_value(ExamChart::value_label(count as u64, total, value_width))
+                    .label(Line::from(start.to_string()))
+                    .style(style)
+                    .value_style(style.reversed())
+            })
+            .collect()
+    }
+
+    fn grade_bars(&self, value_width: u16) -> Vec<Bar<'_>> {
+        let total = self.data.iter().map(|&c| c as u64).sum();
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(g, &c)| {
+                let style = self.bar_style(g, THEME.bar_chart());
+                Bar::default()
+                    .value(c as u64)
+                    .text_value(ExamChart::value_label(c as u64, total, value_width))
+                    .label(Line::from((g + 1).to_string()))
+                    .style(style)
+                    .value_style(style.reversed())
+            })
+            .collect()
+    }
+
+    fn comparison(&self) -> Option<&Comparison> {
+        match self.show_points {
+            true => None,
+            false => self.comparisons.get(self.compare_with?),
+        }
+    }
+
+    // one group per grade with the current and the compared scale side by side.
+    fn grouped_grade_bars(
+        &self,
+        scale_type: &GradeScaleType,
+        data: &[u8; 6],
+        value_width: u16,
+    ) -> Vec<BarGroup<'_>> {
+        let compared_style = Style::default().fg(THEME.scale_color(scale_type));
+        let current_total = self.data.iter().map(|&c| c as u64).sum();
+        let compared_total = data.iter().map(|&c| c as u64).sum();
+        self.data
+            .iter()
+            .zip(data.iter())
+            .enumerate()
+            .map(|(g, (&current, &compared))| {
+                let label = Line::from((g + 1).to_string());
+                let style = self.bar_style(g, THEME.bar_chart());
+                let current = Bar::default()
+                    .value(current as u64)
+                    .text_value(ExamChart::value_label(
+                        current as u64,
+                        current_total,
+                        value_width,
+                    ))
+                    .style(style)
+                    .value_style(style.reversed());
+                let compared = Bar::default()
+                    .value(compared as u64)
+                    .text_value(ExamChart::value_label(
+                        compared as u64,
+                        compared_total,
+                        value_width,
+                    ))
+                    .style(compared_style)
+                    .value_style(compared_style.reversed());
+                // horizontal groups would put the label in an extra line.
+                match self.horizontal {
+                    true => BarGroup::default().bars(&[current.label(label), compared]),
+                    false => BarGroup::default()
+                        .label(label.centered())
+                        .bars(&[current, compared]),
+                }
+            })
+            .collect()
+    }
+}
+
+// e.g. "Ø 2.5 · 80% passed", to compare the outcome of two scales.
+fn outcome_label(avg: f64, pass_rate: Option<f64>) -> String {
+    match pass_rate {
+        Some(rate) => format!(
+            "Ø {} · {} passed",
+            format_average(avg),
+            format_percentage(rate)
+        ),
+        None => format!("Ø {}", format_average(avg)),
+    }
 }
 
 impl Widget for &ExamChart {
@@ -30,18 +292,25 @@ impl Widget for &ExamChart {
     where
         Self: Sized,
     {
-        let block = Block::new()
-            .title(Line::raw(" 📊 Grade Distribution "))
+        let mut title = match self.show_points {
+            true => format!(" 📊 Points Distribution (bin width {}) ", self.bin_width),
+            false => " 📊 Grade Distribution ".to_string(),
+        };
+        let pass_label = self.pass_label();
+        if !pass_label.is_empty() {
+            title += &format!("· {pass_label} ");
+        }
+        let mut block = THEME
+            .frame()
+            .title(Line::raw(title))
             .title_bottom(
                 Line::from(vec![
                     Span::from(" AVG ").style(THEME.tag(true)),
-                    Span::from(format!(" {} ", self.avg)).style(THEME.tag(true).reversed().bold()),
+                    Span::from(format!(" {} ", format_average(self.avg)))
+                        .style(THEME.tag(true).reversed().bold()),
                 ])
                 .right_aligned(),
             )
-            .title_style(THEME.block_title())
-            .borders(Borders::ALL)
-            .border_type(BorderType::Plain)
             .padding(Padding {
                 left: 2,
                 right: 3,
@@ -49,18 +318,13 @@ impl Widget for &ExamChart {
                 bottom: 1,
             });
 
-        let bars: Vec<Bar> = self
-            .data
-            .iter()
-            .enumerate()
-            .map(|(g, &c)| {
-                Bar::default()
-                    .value(c as u64)
-                    .label(Line::from((g + 1).to_string()))
-                    .style(THEME.bar_chart())
-                    .value_style(THEME.bar_chart().reversed())
-            })
-            .collect();
+        let comparison = self.comparison();
+        let bar_count = match (comparison, self.show_points) {
+            (Some(_), _) => 12,
+            (None, true) => self.bins().len().max(1) as u16,
+            (None, false) => 6,
+        };
+        let grouped = comparison.is_some();
 
         let clamped_area = Rect {
             x: area.x,
@@ -69,19 +333,113 @@ impl Widget for &ExamChart {
             height: std::cmp::min(30, area.height),
         };
 
-        BarChart::default()
-            .block(block.padding(Padding {
-                left: 4,
-                right: 4,
-                top: 1,
-                bottom: 1,
-            }))
-            .data(BarGroup::default().bars(&bars))
-            .bar_width((area.width - 25) / 6)
-            .bar_gap(3)
+        // horizontal bars share the height, vertical bars the width.
+        let (direction, bar_width, bar_gap) = match (self.horizontal, grouped, self.show_points) {
+            (true, true, _) => (
+                Direction::Horizontal,
+                (clamped_area.height.saturating_sub(9) / bar_count).max(1),
+                0,
+            ),
+            (true, false, _) => (
+                Direction::Horizontal,
+                (clamped_area.height.saturating_sub(3 + bar_count) / bar_count).max(1),
+                1,
+            ),
+            (false, true, _) => (
+                Direction::Vertical,
+                (area.width.saturating_sub(25) / bar_count).max(1),
+                0,
+            ),
+            (false, false, true) => (
+                Direction::Vertical,
+                (area.width.saturating_sub(12 + bar_count) / bar_count).max(1),
+                1,
+            ),
+            (false, false, false) => (
+                Direction::Vertical,
+                (area.width.saturating_sub(25) / 6).max(1),
+                3,
+            ),
+        };
+        // horizontal bars have enough room for the value.
+        let value_width = match self.horizontal {
+            true => u16::MAX,
+            false => bar_width,
+        };
+
+        let groups = match comparison {
+            Some(comparison) => {
+                let scale_type = &comparison.scale_type;
+                let current_rate = match self.passed + self.failed {
+                    0 => None,
+                    total => Some(self.passed as f64 / total as f64),
+                };
+                block = block.title_bottom(
+                    Line::from(vec![
+                        Span::from(format!(
+                            " ■ current {} ",
+                            outcome_label(self.avg, current_rate)
+                        ))
+                        .style(THEME.bar_chart()),
+                        Span::from(format!(
+                            " ■ {} {} ",
+                            scale_type.text(),
+                            outcome_label(comparison.avg, comparison.pass_rate)
+                        ))
+                        .style(Style::default().fg(THEME.scale_color(scale_type))),
+                    ])
+                    .left_aligned(),
+                );
+                self.grouped_grade_bars(scale_type, &comparison.data, value_width)
+            }
+            None if self.show_points => {
+                vec![BarGroup::default().bars(&self.points_bars(value_width))]
+            }
+            None => vec![BarGroup::default().bars(&self.grade_bars(value_width))],
+        };
+
+        if let (None, false, Some(target)) = (comparison, self.show_points, self.target_avg) {
+            block = block.title_bottom(
+                Line::from(format!(" ▼ target {} ", format_average(target)))
+                    .style(THEME.text().italic())
+                    .left_aligned(),
+            );
+        }
+        let block = block.padding(Padding {
+            left: 4,
+            right: 4,
+            top: 1,
+            bottom: 1,
+        });
+        let inner = block.inner(clamped_area);
+        let chart = groups
+            .into_iter()
+            .fold(BarChart::default(), |chart, group| chart.data(group));
+        chart
+            .block(block)
+            .bar_width(bar_width)
+            .bar_gap(bar_gap)
+            .group_gap(match self.horizontal {
+                true => 1,
+                false => 3,
+            })
             .style(THEME.block())
             .label_style(THEME.text().italic())
-            .direction(Direction::Vertical)
+            .direction(direction)
             .render(clamped_area, buf);
+
+        // the markers only fit the simple grade chart.
+        if comparison.is_none() && !self.show_points {
+            let marker = match self.horizontal {
+                true => "◀",
+                false => "▼",
+            };
+            if let Some(target) = self.target_avg {
+                let style = THEME.text().italic();
+                self.render_marker(target, marker, style, (bar_width, bar_gap), inner, buf);
+            }
+            let style = THEME.tag(true).bold();
+            self.render_marker(self.avg, marker, style, (bar_width, bar_gap), inner, buf);
+        }
     }
 }
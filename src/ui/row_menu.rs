@@ -0,0 +1,134 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    text::{Line, Span},
+    widgets::{Clear, HighlightSpacing, List, ListItem, ListState, StatefulWidget, Widget},
+};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+    TableColumn,
+};
+use crate::action::{Action, ModelAction};
+
+/// The operations on a student, opened with Enter in the result table.
+#[derive(Debug, Default)]
+pub struct RowMenu {
+    name: String,
+    absent: bool,
+    state: ListState,
+}
+
+impl RowMenu {
+    pub fn new(name: &str, absent: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            absent,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    // the shortcut, label and action of each entry.
+    fn entries(&self) -> Vec<(char, &'static str, Action)> {
+        let name = self.name.clone();
+        vec![
+            ('e', "Edit points", Action::EditCell(TableColumn::Points)),
+            ('r', "Rename", Action::EditCell(TableColumn::Name)),
+            ('n', "Edit note", Action::OpenNotePopup(name.clone())),
+            (
+                'a',
+                match self.absent {
+                    true => "Mark present",
+                    false => "Mark absent",
+                },
+                Action::UpdateModel(ModelAction::ToggleStudentAbsent(name.clone())),
+            ),
+            (
+                'd',
+                "Delete",
+                Action::UpdateModel(ModelAction::DeleteStudent(name.clone())),
+            ),
+            ('y', "Copy row", Action::CopyStudent(name)),
+        ]
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        let entries = self.entries();
+        let index = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.select_previous();
+                return None;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.select_next();
+                return None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Some(Action::CloseRowMenu),
+            KeyCode::Enter => self.state.selected()?,
+            KeyCode::Char(c) => entries.iter().position(|(key, _, _)| *key == c)?,
+            _ => return None,
+        };
+        let (_, _, action) = entries.into_iter().nth(index)?;
+        Some(Action::RowMenuChoice(Box::new(action)))
+    }
+}
+
+impl Widget for &mut RowMenu {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let entries = self.entries();
+        let items: Vec<ListItem> = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (key, label, _))| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!(" {key} "), THEME.tag(true)),
+                    Span::raw(format!(" {label}")),
+                ]))
+                .style(THEME.table_row(i))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(THEME.frame().title(format!(" 🧑 {} ", self.name)))
+            .highlight_style(THEME.table_row_selected())
+            .highlight_symbol(THEME.highlight_symbol())
+            .highlight_spacing(HighlightSpacing::Always);
+
+        let popup_area = centered_rect(
+            area,
+            Constraint::Length(32),
+            Constraint::Length(entries.len() as u16 + 2),
+        );
+        Clear.render(popup_area, buf);
+        StatefulWidget::render(list, popup_area, buf, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn test_shortcuts() {
+        let mut menu = RowMenu::new("Anna", false);
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(
+            menu.handle_event(key('a')),
+            Some(Action::RowMenuChoice(Box::new(Action::UpdateModel(
+                ModelAction::ToggleStudentAbsent("Anna".into())
+            ))))
+        );
+        assert_eq!(menu.handle_event(key('x')), None);
+
+        menu.handle_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert_eq!(
+            menu.handle_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(Action::RowMenuChoice(Box::new(Action::EditCell(
+                TableColumn::Name
+            ))))
+        );
+    }
+}
@@ -1,33 +1,90 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::cmp::Ordering;
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
-    layout::{Alignment, Constraint},
+    layout::{Alignment, Constraint, Layout},
     prelude::{Buffer, Rect},
     text::Text,
-    widgets::{Block, Borders, Cell, Row, StatefulWidget, Table, TableState, Widget},
+    widgets::{Cell, Row, StatefulWidget, Table, TableState, Widget},
 };
 
-use super::theme::{AppStyle, THEME};
+use super::{
+    editor_text,
+    theme::{AppStyle, THEME},
+    TableColumn,
+};
 use crate::{
     action::{Action, ModelAction},
-    model::scale::GradeScaleType,
+    export::ExportColumn,
+    locale::{format_percentage, format_points, precision},
+    model::scale::{format_grade, round_dp, Grade, GradeScaleType},
 };
+use serde::Serialize;
 use tracing::debug;
+use tui_input::{backend::crossterm::EventHandler, Input};
 
 pub struct GradingScaleTable {
     pub state: TableState,
     scale_type: GradeScaleType,
     data: Vec<GradingScaleTableRowData>,
+    hidden_columns: Vec<TableColumn>,
+    editor: Option<Input>, // inline editor for the min points of the selected grade
+    editor_error: Option<String>,
+    pending_g: bool, // first key of `gg` was pressed
+    focused: bool,
+    area: Rect,        // where the table was rendered last, for mouse events
+    drag: Option<u16>, // row of the last drag position on a MIN cell
 }
 
+/// All columns of the scale table in display order.
+const COLUMNS: [TableColumn; 5] = [
+    TableColumn::Grade,
+    TableColumn::Label,
+    TableColumn::Min,
+    TableColumn::Max,
+    TableColumn::Pct,
+];
+
 impl GradingScaleTable {
     pub fn new(scale_type: GradeScaleType) -> Self {
         Self {
             state: TableState::default().with_selected(0),
             scale_type,
             data: vec![],
+            hidden_columns: TableColumn::default_hidden(),
+            editor: None,
+            editor_error: None,
+            pending_g: false,
+            focused: false,
+            area: Rect::default(),
+            drag: None,
         }
     }
 
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn set_hidden_columns(&mut self, hidden_columns: &[TableColumn]) {
+        self.hidden_columns = hidden_columns.to_vec();
+        self.state.select_column(None);
+    }
+
+    // the grade column is always shown.
+    fn visible_columns(&self) -> Vec<TableColumn> {
+        COLUMNS
+            .into_iter()
+            .filter(|col| *col == TableColumn::Grade || !self.hidden_columns.contains(col))
+            .collect()
+    }
+
+    fn select_column(&mut self, column: TableColumn) {
+        let index = self.visible_columns().iter().position(|c| *c == column);
+        self.state.select_column(index);
+    }
+
     // return the selected grade as u8 if a row is selected
     pub fn selected(&self) -> Option<u8> {
         if let Some(index) = self.state.selected() {
@@ -41,25 +98,126 @@ impl GradingScaleTable {
         self.data = data;
     }
 
+    fn select_last(&mut self) {
+        self.state.select(Some(self.data.len().saturating_sub(1)));
+    }
+
     pub fn select_col_min(&mut self) {
-        self.state.select_column(Some(1));
+        self.select_column(TableColumn::Min);
     }
 
     pub fn select_col_max(&mut self) {
-        self.state.select_column(Some(2));
+        self.select_column(TableColumn::Max);
+    }
+
+    // select the row of the given grade, if the scale has it.
+    fn select_grade(&mut self, grade: u8) {
+        if let Some(index) = self.data.iter().position(|row| row.grade == grade) {
+            self.state.select(Some(index));
+        }
+    }
+
+    // returns true while the inline editor is open and should receive all keys.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    fn start_editing(&mut self) {
+        if let Some(index) = self.state.selected() {
+            self.editor = Some(Input::new(self.data[index].min.to_string()));
+            self.select_col_min();
+        }
+    }
+
+    pub fn cancel_editing(&mut self) {
+        self.editor = None;
+        self.editor_error = None;
+    }
+
+    // close the editor if the value was applied, otherwise show why not.
+    pub fn finish_editing(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.editor = None,
+            Err(e) => self.editor_error = Some(e),
+        }
+    }
+
+    fn handle_editor_event(&mut self, key: KeyEvent) -> Option<Action> {
+        let editor = self.editor.as_mut()?;
+        self.editor_error = None;
+        match key.code {
+            KeyCode::Esc => {
+                self.editor = None;
+                None
+            }
+            KeyCode::Enter => {
+                // accept a decimal comma as well.
+                let value = editor.value().trim().replace(',', ".");
+                let Ok(points) = value.parse::<f64>() else {
+                    self.editor_error = Some(format!("'{value}' is not a number."));
+                    return None;
+                };
+                let grade = self.selected()?;
+                Some(Action::UpdateModel(ModelAction::SetThreshold(
+                    grade, points,
+                )))
+            }
+            _ => {
+                editor.handle_event(&Event::Key(key));
+                None
+            }
+        }
     }
 
     pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
         debug!("EVENT: {:?}", key);
+        if self.is_editing() {
+            return self.handle_editor_event(key);
+        }
+
+        let pending_g = std::mem::take(&mut self.pending_g);
+        if key.modifiers == KeyModifiers::CONTROL {
+            // all grades fit on one page.
+            match key.code {
+                KeyCode::Char('d') => self.select_last(),
+                KeyCode::Char('u') => self.state.select(Some(0)),
+                _ => {}
+            }
+            return None;
+        }
+        // plain digits switch the tab.
+        if key.modifiers == KeyModifiers::ALT {
+            if let KeyCode::Char(c @ '1'..='6') = key.code {
+                self.select_grade(c as u8 - b'0');
+            }
+            return None;
+        }
+
         match key.code {
-            KeyCode::Up | KeyCode::Char('j') => {
+            KeyCode::Up | KeyCode::Char('k') => {
                 self.state.select_previous();
                 None
             }
-            KeyCode::Down | KeyCode::Char('k') => {
+            KeyCode::Down | KeyCode::Char('j') => {
                 self.state.select_next();
                 None
             }
+            KeyCode::Home => {
+                self.state.select(Some(0));
+                None
+            }
+            KeyCode::Char('g') if pending_g => {
+                self.state.select(Some(0));
+                None
+            }
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                None
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.select_last();
+                None
+            }
             KeyCode::Left | KeyCode::Char('h') => {
                 self.select_col_min();
                 None
@@ -90,6 +248,11 @@ impl GradingScaleTable {
                     None
                 }
             }
+            KeyCode::Enter | KeyCode::Char('i') => {
+                self.start_editing();
+                None
+            }
+            KeyCode::Char('r') => Some(Action::UpdateModel(ModelAction::ResetScale)),
             KeyCode::PageUp => Some(Action::UpdateModel(ModelAction::IncrementMaxPoints)),
             KeyCode::PageDown => Some(Action::UpdateModel(ModelAction::DecrementMaxPoints)),
             _ => None,
@@ -97,32 +260,108 @@ impl GradingScaleTable {
     }
 }
 
+impl GradingScaleTable {
+    // index of the table row at the given terminal row.
+    fn row_at(&self, row: u16) -> Option<usize> {
+        // skip the border and the header line.
+        let first_row = self.area.y + 2;
+        if row < first_row || row >= self.area.bottom().saturating_sub(1) {
+            return None;
+        }
+        let index = (row - first_row) as usize / 3 + self.state.offset();
+        (index < self.data.len()).then_some(index)
+    }
+
+    // column at the given terminal column, same layout as the table.
+    fn column_at(&self, column: u16) -> Option<TableColumn> {
+        let columns = self.visible_columns();
+        let inner = Rect {
+            x: self.area.x + 4, // border and highlight symbol
+            width: self.area.width.saturating_sub(5),
+            ..self.area
+        };
+        let widths = columns.iter().map(|column| match column {
+            TableColumn::Grade | TableColumn::Label => Constraint::Fill(2),
+            _ => Constraint::Fill(1),
+        });
+        Layout::horizontal(widths)
+            .spacing(1)
+            .split(inner)
+            .iter()
+            .position(|rect| rect.left() <= column && column < rect.right())
+            .map(|index| columns[index])
+    }
+
+    // drag the MIN cell up to raise and down to lower the threshold.
+    pub fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Option<Action> {
+        if self.is_editing() {
+            return None;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let index = self.row_at(mouse.row)?;
+                self.state.select(Some(index));
+                if self.column_at(mouse.column) == Some(TableColumn::Min) {
+                    self.select_col_min();
+                    self.drag = Some(mouse.row);
+                }
+                None
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let last_row = self.drag?;
+                let grade = self.selected()?;
+                // one step per event, the remaining distance follows with the next one.
+                let (action, row) = match mouse.row.cmp(&last_row) {
+                    Ordering::Less => (ModelAction::IncrementThreshold(grade), last_row - 1),
+                    Ordering::Greater => (ModelAction::DecrementThreshold(grade), last_row + 1),
+                    Ordering::Equal => return None,
+                };
+                self.drag = Some(row);
+                Some(Action::UpdateModel(action))
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.drag = None;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
 impl Widget for &mut GradingScaleTable {
     fn render(self, area: Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
-        let header = [
-            Text::from("GRADE"),
-            Text::from("MIN").alignment(Alignment::Center),
-            Text::from("MAX").alignment(Alignment::Center),
-            Text::from("PCT").alignment(Alignment::Center),
-        ]
-        .into_iter()
-        .map(Cell::from)
-        .collect::<Row>()
-        .style(THEME.table_header())
-        .height(1);
+        let columns = self.visible_columns();
+
+        let header = columns
+            .iter()
+            .map(|column| match column {
+                TableColumn::Grade => Text::from("GRADE"),
+                column => {
+                    Text::from(column.to_string().to_uppercase()).alignment(Alignment::Center)
+                }
+            })
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(THEME.table_header())
+            .height(1);
 
+        let editing = self.editor.as_ref().zip(self.state.selected());
         let rows = self.data.iter().enumerate().map(|(i, data)| {
-            let item = data.as_str_array();
-            item.into_iter()
-                .enumerate()
-                .map(|(idx, content)| {
-                    let text = if idx == 0 {
-                        Text::from(format!("\n{content}\n"))
-                    } else {
-                        Text::from(format!("\n{content}\n")).alignment(Alignment::Center)
+            columns
+                .iter()
+                .map(|&column| {
+                    let text = match editing {
+                        Some((editor, row)) if row == i && column == TableColumn::Min => {
+                            editor_text(editor, self.editor_error.as_deref())
+                        }
+                        _ => Text::from(format!("\n{}\n", data.cell(column))),
+                    };
+                    let text = match column {
+                        TableColumn::Grade => text,
+                        _ => text.alignment(Alignment::Center),
                     };
                     Cell::from(text)
                 })
@@ -131,37 +370,32 @@ impl Widget for &mut GradingScaleTable {
                 .height(3)
         });
 
-        let bar = " █ ";
-        let table = Table::new(
-            rows,
-            [
-                // + 1 is for padding.
-                Constraint::Fill(2),
-                Constraint::Fill(1),
-                Constraint::Fill(1),
-                Constraint::Fill(1),
-            ],
-        )
-        .header(header)
-        .row_highlight_style(
-            THEME
-                .table_row_selected()
-                .bg(THEME.scale_color(&self.scale_type)),
-        )
-        .cell_highlight_style(
-            THEME
-                .table_col_selected()
-                .fg(THEME.scale_color(&self.scale_type)),
-        )
-        .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]))
-        .highlight_spacing(ratatui::widgets::HighlightSpacing::Always)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" 💯 Grading Scale ")
-                .style(THEME.block())
-                .title_style(THEME.block_title()),
-        );
+        let widths = columns.iter().map(|column| match column {
+            TableColumn::Grade | TableColumn::Label => Constraint::Fill(2),
+            _ => Constraint::Fill(1),
+        });
+
+        let bar = THEME.highlight_symbol();
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(
+                THEME
+                    .table_row_selected()
+                    .bg(THEME.scale_color(&self.scale_type)),
+            )
+            .cell_highlight_style(
+                THEME
+                    .table_col_selected()
+                    .fg(THEME.scale_color(&self.scale_type)),
+            )
+            .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]))
+            .highlight_spacing(ratatui::widgets::HighlightSpacing::Always)
+            .block(
+                THEME
+                    .frame()
+                    .title(" 💯 Grading Scale ")
+                    .border_style(THEME.border(self.focused)),
+            );
 
         let clamped_area = Rect {
             x: area.x,
@@ -169,15 +403,19 @@ impl Widget for &mut GradingScaleTable {
             width: area.width,
             height: std::cmp::min(21, area.height), // clamp height to 21 if area is bigger.
         };
+        self.area = clamped_area;
         StatefulWidget::render(table, clamped_area, buf, &mut self.state);
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct GradingScaleTableRowData {
     grade: u8,
+    #[serde(rename = "min_points")]
     min: f64,
+    #[serde(rename = "max_points")]
     max: f64,
+    #[serde(rename = "percentage")]
     pct: f64,
 }
 
@@ -191,12 +429,70 @@ impl GradingScaleTableRowData {
         }
     }
 
+    pub fn grade(&self) -> u8 {
+        self.grade
+    }
+
     pub fn as_str_array(&self) -> [String; 4] {
         [
-            self.grade.to_string(),
-            self.min.to_string(),
-            self.max.to_string(),
-            format!("{}%", (self.pct * 100.0).round()),
+            format_grade(self.grade),
+            format_points(self.min),
+            format_points(self.max),
+            format_percentage(self.pct),
+        ]
+    }
+
+    // return the displayed value of a table column.
+    pub fn cell(&self, column: TableColumn) -> String {
+        let [grade, min, max, pct] = self.as_str_array();
+        match column {
+            TableColumn::Grade => grade,
+            TableColumn::Label => Grade::try_from(self.grade)
+                .map(|g| g.label().to_string())
+                .unwrap_or_default(),
+            TableColumn::Min => min,
+            TableColumn::Max => max,
+            TableColumn::Pct => pct,
+            _ => String::new(),
+        }
+    }
+
+    // the numbers with a `.` whatever the locale, e.g. for csv files which are read by programs.
+    fn as_plain_array(&self) -> [String; 4] {
+        let precision = precision();
+        [
+            format_grade(self.grade),
+            round_dp(self.min, precision.points).to_string(),
+            round_dp(self.max, precision.points).to_string(),
+            format!("{}%", round_dp(self.pct * 100.0, precision.percentage)),
         ]
     }
+
+    // return the values for the given export columns in the same order.
+    pub fn as_columns(&self, columns: &[ExportColumn]) -> Vec<String> {
+        self.columns_of(self.as_str_array(), columns)
+    }
+
+    // like `as_columns`, but the numbers are not formatted for the locale.
+    pub fn as_plain_columns(&self, columns: &[ExportColumn]) -> Vec<String> {
+        self.columns_of(self.as_plain_array(), columns)
+    }
+
+    fn columns_of(&self, values: [String; 4], columns: &[ExportColumn]) -> Vec<String> {
+        let [grade, min, max, pct] = values;
+        columns
+            .iter()
+            .map(|column| match column {
+                ExportColumn::Grade => grade.clone(),
+                ExportColumn::Label => Grade::try_from(self.grade)
+                    .map(|g| g.label().to_string())
+                    .unwrap_or_default(),
+                ExportColumn::Min => min.clone(),
+                ExportColumn::Max => max.clone(),
+                ExportColumn::Pct => pct.clone(),
+                // the columns of the students are not part of the scale.
+                ExportColumn::Percentile | ExportColumn::Notes => String::new(),
+            })
+            .collect()
+    }
 }
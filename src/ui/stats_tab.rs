@@ -0,0 +1,288 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    text::Line,
+    widgets::{Padding, Paragraph, Row, Table, Widget},
+};
+
+use super::theme::{AppStyle, THEME};
+use crate::{
+    locale::{format_average, format_percentage, format_points},
+    model::stats::Statistics,
+};
+
+/// Number of students listed with the best and the worst results.
+const PERFORMERS: usize = 3;
+
+/// Name and points of a student.
+type Performance = (String, f64);
+
+/// Median, mode, spread and shape of the points and grades side by side,
+/// with what the shape of the points tells about the exam below.
+/// The best and worst results follow the table.
+#[derive(Debug, Default, Clone)]
+pub struct StatsTab {
+    points: Option<Statistics>,
+    grades: Option<Statistics>,
+    ranking: Vec<Performance>, // best first
+    max_points: f64,
+    weighted_average: Option<(f64, usize)>, // the grade over the number of exams
+}
+
+impl StatsTab {
+    pub fn set_data(&mut self, points: Option<Statistics>, grades: Option<Statistics>) {
+        self.points = points;
+        self.grades = grades;
+    }
+
+    pub fn set_weighted_average(&mut self, average: Option<f64>, exams: usize) {
+        self.weighted_average = average.map(|average| (average, exams));
+    }
+
+    pub fn set_ranking(&mut self, ranking: Vec<Performance>, max_points: f64) {
+        self.ranking = ranking;
+        self.max_points = max_points;
+    }
+
+    // the best and the worst results, worst first. Nobody is listed twice.
+    fn performers(&self) -> (Vec<&Performance>, Vec<&Performance>) {
+        let best: Vec<&Performance> = self.ranking.iter().take(PERFORMERS).collect();
+        let worst = self
+            .ranking
+            .iter()
+            .rev()
+            .take(PERFORMERS.min(self.ranking.len() - best.len()))
+            .collect();
+        (best, worst)
+    }
+
+    // e.g. "highest 46 · lowest 20 · best 4 points (8%) below full marks".
+    fn extremes(&self) -> Option<String> {
+        let (_, highest) = self.ranking.first()?;
+        let (_, lowest) = self.ranking.last()?;
+        let gap = self.max_points - highest;
+        let best = match gap > 0.0 {
+            true => format!(
+                "best {} points ({}) below full marks",
+                format_points(gap),
+                format_percentage(gap / self.max_points)
+            ),
+            false => "best with full marks".to_string(),
+        };
+        Some(format!(
+            "highest {} · lowest {} · {best}",
+            format_points(*highest),
+            format_points(*lowest)
+        ))
+    }
+
+    // the label and the values for points and grades of each row.
+    fn rows(&self) -> Vec<[String; 3]> {
+        let (Some(points), Some(grades)) = (&self.points, &self.grades) else {
+            return Vec::new();
+        };
+        let mode = |values: &[f64], format: fn(f64) -> String| {
+            values
+                .iter()
+                .map(|&value| format(value))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let range = |stats: &Statistics, format: fn(f64) -> String| {
+            format!(
+                "{} – {} ({})",
+                format(stats.min),
+                format(stats.max),
+                format(stats.range())
+            )
+        };
+        // e.g. "25 – 40 (15)" with the interquartile range in brackets.
+        let quartiles = |stats: &Statistics, format: fn(f64) -> String| {
+            format!(
+                "{} – {} ({})",
+                format(stats.q1),
+                format(stats.q3),
+                format(stats.iqr())
+            )
+        };
+        let mut rows = vec![
+            [
+                "Average".into(),
+                format_points(points.mean),
+                format_average(grades.mean),
+            ],
+            [
+                "Median".into(),
+                format_points(points.median),
+                format_average(grades.median),
+            ],
+            [
+                "Mode".into(),
+                mode(&points.mode, format_points),
+                mode(&grades.mode, format_average),
+            ],
+            [
+                "Std deviation".into(),
+                format_points(points.std_dev),
+                format_average(grades.std_dev),
+            ],
+            [
+                "Range".into(),
+                range(points, format_points),
+                range(grades, format_average),
+            ],
+            [
+                "Quartiles".into(),
+                quartiles(points, format_points),
+                quartiles(grades, format_average),
+            ],
+            [
+                "Skewness".into(),
+                format_average(points.skewness),
+                format_average(grades.skewness),
+            ],
+            [
+                "Kurtosis".into(),
+                format_average(points.kurtosis),
+                format_average(grades.kurtosis),
+            ],
+        ];
+        // only with other exams, which have no points of this scale.
+        if let Some((average, exams)) = self.weighted_average {
+            rows.insert(
+                1,
+                [
+                    format!("Weighted ({exams} exams)"),
+                    "-".into(),
+                    format_average(average),
+                ],
+            );
+        }
+        rows
+    }
+}
+
+impl Widget for &StatsTab {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut block = THEME
+            .frame()
+            .title(Line::raw(" 🧮 Statistics "))
+            .padding(Padding::uniform(1));
+        if let Some(points) = &self.points {
+            block = block.title_bottom(Line::from(format!(" {} ", points.shape())).italic());
+        }
+
+        let rows = self.rows();
+        if rows.is_empty() {
+            Paragraph::new(Line::from("No student data loaded.").style(THEME.text()))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [table_area, performers_area] = Layout::vertical([
+            Constraint::Length(rows.len() as u16 + 2),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        Table::new(
+            rows.into_iter()
+                .map(|[label, points, grades]| Row::new([label, points, grades])),
+            [
+                Constraint::Length(16),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["", "Points", "Grade"]).style(THEME.table_header()))
+        .style(THEME.text())
+        .render(table_area, buf);
+
+        let Some(extremes) = self.extremes() else {
+            return;
+        };
+        let [title_area, extremes_area, lists_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(performers_area);
+        Line::from("Top and bottom results")
+            .style(THEME.table_header())
+            .render(title_area, buf);
+        Line::from(extremes)
+            .style(THEME.text())
+            .render(extremes_area, buf);
+
+        let (best, worst) = self.performers();
+        let [best_area, worst_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                .spacing(2)
+                .areas(lists_area);
+        let list =
+            |title: &str, results: Vec<&Performance>| {
+                let mut lines = vec![Line::from(title.to_string()).bold()];
+                lines.extend(results.into_iter().map(|(name, points)| {
+                    Line::from(format!("{} {name}", format_points(*points)))
+                }));
+                Paragraph::new(lines).style(THEME.text())
+            };
+        list("Best", best).render(best_area, buf);
+        if !worst.is_empty() {
+            list("Worst", worst).render(worst_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let mut tab = StatsTab::default();
+        assert!(tab.rows().is_empty());
+
+        tab.set_data(
+            Statistics::from_values(&[20.0, 46.0]),
+            Statistics::from_values(&[1.0, 5.0]),
+        );
+        let rows = tab.rows();
+        assert_eq!(rows[0], ["Average", "33", "3"].map(String::from));
+        assert_eq!(rows[2][2], "1, 5");
+        assert_eq!(rows[4][1], "20 – 46 (26)");
+        assert_eq!(rows[5][1], "26.5 – 39.5 (13)");
+
+        tab.set_weighted_average(Some(2.5), 2);
+        assert_eq!(
+            tab.rows()[1],
+            ["Weighted (2 exams)", "-", "2.5"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn test_performers() {
+        let mut tab = StatsTab::default();
+        assert_eq!(tab.extremes(), None);
+
+        let ranking = [("Anna", 46.0), ("Ben", 30.0), ("Cem", 20.0), ("Dana", 12.5)];
+        tab.set_ranking(
+            ranking
+                .map(|(name, points)| (name.to_string(), points))
+                .to_vec(),
+            50.0,
+        );
+        let (best, worst) = tab.performers();
+        assert_eq!(best.len(), 3);
+        let worst: Vec<&str> = worst.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(worst, vec!["Dana"]);
+        assert_eq!(
+            tab.extremes().unwrap(),
+            "highest 46 · lowest 12.5 · best 4 points (8%) below full marks"
+        );
+    }
+}
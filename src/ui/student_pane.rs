@@ -0,0 +1,100 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Padding, Paragraph, Widget, Wrap},
+};
+
+use super::theme::{AppStyle, THEME};
+use crate::locale::{format_percentage, format_points};
+
+/// Everything known about a single student, shown in the side pane of the result tab.
+#[derive(Debug, Default, Clone)]
+pub struct StudentDetail {
+    pub name: String,
+    pub points: f64,
+    pub percentage: f64,
+    pub grade: u8,
+    pub next_grade: Option<(u8, f64)>, // next better grade and the missing points
+    pub percentile: Option<f64>,       // share of the class with less points
+    pub notes: String,
+    pub absent: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct StudentPane {
+    detail: Option<StudentDetail>,
+}
+
+impl StudentPane {
+    pub fn set_detail(&mut self, detail: Option<StudentDetail>) {
+        self.detail = detail;
+    }
+}
+
+impl Widget for &StudentPane {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = THEME
+            .frame()
+            .title(" 🧑 Student ")
+            .padding(Padding::horizontal(1));
+
+        let Some(detail) = &self.detail else {
+            Paragraph::new(Line::from("No student selected.").style(THEME.text()))
+                .block(block)
+                .render(area, buf);
+            return;
+        };
+
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::from(format!(" {label:<10}")).style(THEME.tag(false)),
+                Span::from(format!(" {value}")).style(THEME.text()),
+            ])
+        };
+
+        let next_grade = match detail.next_grade {
+            Some((grade, missing)) => format!("{missing} points to {grade}"),
+            None => "best grade reached".to_string(),
+        };
+        let notes = match detail.notes.is_empty() {
+            true => "—".to_string(),
+            false => detail.notes.clone(),
+        };
+
+        let lines = vec![
+            Line::from(detail.name.as_str()).style(THEME.text().bold()),
+            Line::default(),
+            field("Points", format_points(detail.points)),
+            field("Percent", format_percentage(detail.percentage)),
+            field(
+                "Grade",
+                match detail.absent {
+                    true => "absent".to_string(),
+                    false => detail.grade.to_string(),
+                },
+            ),
+            field("Next", next_grade),
+            field(
+                "Rank",
+                match detail.percentile {
+                    Some(share) => format!("better than {}", format_percentage(share)),
+                    None => "—".to_string(),
+                },
+            ),
+            Line::default(),
+            Line::from("Tasks").style(THEME.table_header()),
+            // only the total is stored for now.
+            Line::from(format!("total {}", format_points(detail.points))).style(THEME.text()),
+            Line::default(),
+            Line::from("Notes").style(THEME.table_header()),
+            Line::from(notes).style(THEME.text()),
+        ];
+
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
@@ -1,27 +1,114 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Text},
     widgets::{
-        Block, BorderType, Borders, Cell, Row, ScrollbarState, StatefulWidget, Table, TableState,
-        Widget,
+        Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Table,
+        TableState, Widget,
     },
 };
 
-use super::theme::{AppStyle, THEME};
-use crate::action::{Action, ModelAction};
+use super::{
+    editor_text,
+    theme::{AppStyle, THEME},
+    truncate, TableColumn,
+};
+use crate::{
+    action::{Action, ModelAction},
+    locale::{format_average, format_percentage, format_points, format_share, precision},
+    model::scale::{format_grade, grade_format, round_dp, GradeFormat, PASS_GRADE},
+};
+use serde::Serialize;
+use strum_macros::{Display, EnumString};
 use tracing::debug;
+use tui_input::{backend::crossterm::EventHandler, Input};
+use unicode_width::UnicodeWidthStr;
+
+const ITEM_HEIGHT: usize = 3;
+
+/// All columns of the result table in display order.
+const COLUMNS: [TableColumn; 6] = [
+    TableColumn::Name,
+    TableColumn::Points,
+    TableColumn::Percentage,
+    TableColumn::Percentile,
+    TableColumn::Grade,
+    TableColumn::Average,
+];
+
+/// Columns the result table can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum SortColumn {
+    Name,
+    Points,
+    Grade,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+// e.g. `points:desc`, ascending if no direction is given.
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (column, direction) = value.split_once(':').unwrap_or((value, "asc"));
+        let column = column
+            .parse()
+            .map_err(|_| format!("'{column}' is no column, expected name, points or grade"))?;
+        let descending = match direction {
+            "asc" => false,
+            "desc" => true,
+            _ => {
+                return Err(format!(
+                    "'{direction}' is no direction, expected asc or desc"
+                ))
+            }
+        };
+        Ok(Self { column, descending })
+    }
+}
 
-const ITEM_HEIGHT: usize = 4;
+impl SortOrder {
+    fn compare(&self, a: &ExamResultTableRowData, b: &ExamResultTableRowData) -> Ordering {
+        let ordering = match self.column {
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::Points => a.points.total_cmp(&b.points),
+            SortColumn::Grade => a.grade.cmp(&b.grade),
+        };
+        match self.descending {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct ExamResultTable {
     title: String,
     state: TableState,
     scroll_state: ScrollbarState,
-    data: Vec<ExamResultTableRowData>,
+    data: Vec<ExamResultTableRowData>, // the shown rows
+    all_data: Vec<ExamResultTableRowData>,
+    grade_filter: Option<u8>, // only students with this grade are shown
+    page_size: usize,         // number of visible rows, updated on render
+    search: Input,
+    sort: Option<SortOrder>,
+    hidden_columns: Vec<TableColumn>,
+    editor: Option<(TableColumn, Input)>, // inline editor for the selected cell
+    editor_error: Option<String>,
+    pending_g: bool, // first key of `gg` was pressed
+    pass_grade: u8,  // students with a worse grade failed
+    focused: bool,
 }
 
 impl ExamResultTable {
@@ -33,9 +120,28 @@ impl ExamResultTable {
                 .with_selected_column(1),
             scroll_state: ScrollbarState::default(),
             data: Vec::new(),
+            all_data: Vec::new(),
+            grade_filter: None,
+            page_size: 1,
+            search: Input::default(),
+            sort: None,
+            hidden_columns: TableColumn::default_hidden(),
+            editor: None,
+            editor_error: None,
+            pending_g: false,
+            pass_grade: PASS_GRADE.to_number(),
+            focused: false,
         }
     }
 
+    pub fn set_pass_grade(&mut self, grade: u8) {
+        self.pass_grade = grade;
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
     pub fn with_title(mut self, title: &str) -> Self {
         self.set_title(title);
         self
@@ -46,13 +152,294 @@ impl ExamResultTable {
         self
     }
 
+    fn is_failing(&self, row: &ExamResultTableRowData) -> bool {
+        !row.absent && row.grade > self.pass_grade
+    }
+
+    pub fn selected_name(&self) -> Option<&str> {
+        self.selected_row().map(|row| row.name.as_str())
+    }
+
+    pub fn selected_row(&self) -> Option<&ExamResultTableRowData> {
+        self.data.get(self.state.selected()?)
+    }
+
     pub fn set_title(&mut self, title: &str) {
         self.title = title.into();
     }
 
+    // the selection follows the student, not the row index.
     pub fn set_data(&mut self, data: Vec<ExamResultTableRowData>) {
-        self.data = data;
+        self.all_data = data;
+        self.apply_filter();
+    }
+
+    pub fn set_grade_filter(&mut self, grade: Option<u8>) {
+        self.grade_filter = grade;
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        let selected_name = self.selected_name().map(str::to_string);
+        self.data = self
+            .all_data
+            .iter()
+            .filter(|row| match self.grade_filter {
+                Some(grade) => !row.absent && row.grade == grade,
+                None => true,
+            })
+            .cloned()
+            .collect();
+        self.apply_sort();
         self.scroll_state = ScrollbarState::new((self.data.len().saturating_sub(1)) * ITEM_HEIGHT);
+        match selected_name {
+            Some(name) if self.select_name(&name) => {}
+            // the student was removed, so keep the row.
+            _ => {
+                let last = self.data.len().saturating_sub(1);
+                self.state
+                    .select(self.state.selected().map(|index| index.min(last)));
+            }
+        }
+        self.scroll_to_selected();
+    }
+
+    // returns false if there is no student with this name.
+    pub fn select_name(&mut self, name: &str) -> bool {
+        match self.data.iter().position(|row| row.name == name) {
+            Some(index) => {
+                self.state.select(Some(index));
+                self.scroll_to_selected();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_hidden_columns(&mut self, hidden_columns: &[TableColumn]) {
+        self.hidden_columns = hidden_columns.to_vec();
+        // the points column is highlighted, as the points can be changed.
+        let index = self
+            .visible_columns()
+            .iter()
+            .position(|c| *c == TableColumn::Points);
+        self.state.select_column(index);
+    }
+
+    // the name column is always shown, the weighted average only with other exams.
+    fn visible_columns(&self) -> Vec<TableColumn> {
+        let averaged = self.all_data.iter().any(|row| row.average.is_some());
+        COLUMNS
+            .into_iter()
+            .filter(|col| *col != TableColumn::Average || averaged)
+            .filter(|col| *col == TableColumn::Name || !self.hidden_columns.contains(col))
+            .collect()
+    }
+
+    fn selected_column(&self) -> Option<TableColumn> {
+        let index = self.state.selected_column()?;
+        self.visible_columns().get(index).copied()
+    }
+
+    fn select_column(&mut self, forward: bool) {
+        let count = self.visible_columns().len();
+        let index = match (self.state.selected_column(), forward) {
+            (Some(index), true) => (index + 1).min(count - 1),
+            (Some(index), false) => index.saturating_sub(1),
+            (None, _) => 0,
+        };
+        self.state.select_column(Some(index));
+    }
+
+    // returns true while the inline editor is open and should receive all keys.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    // only the name and the points of a student can be edited.
+    fn start_editing(&mut self) {
+        let Some(row) = self.state.selected().and_then(|index| self.data.get(index)) else {
+            return;
+        };
+        if let Some(column @ (TableColumn::Name | TableColumn::Points)) = self.selected_column() {
+            self.editor = Some((column, Input::new(row.cell(column))));
+        }
+    }
+
+    // open the editor in the given column of the selected row.
+    pub fn edit_cell(&mut self, column: TableColumn) {
+        if let Some(index) = self.visible_columns().iter().position(|c| *c == column) {
+            self.state.select_column(Some(index));
+            self.start_editing();
+        }
+    }
+
+    pub fn cancel_editing(&mut self) {
+        self.editor = None;
+        self.editor_error = None;
+    }
+
+    // close the editor if the value was applied, otherwise show why not.
+    pub fn finish_editing(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.editor = None,
+            Err(e) => self.editor_error = Some(e),
+        }
+    }
+
+    fn handle_editor_event(&mut self, key: KeyEvent) -> Option<Action> {
+        let (column, editor) = self.editor.as_mut()?;
+        self.editor_error = None;
+        match key.code {
+            KeyCode::Esc => {
+                self.editor = None;
+                None
+            }
+            KeyCode::Enter => {
+                let value = editor.value().trim().to_string();
+                let name = self.data.get(self.state.selected()?)?.name.clone();
+                let action = match column {
+                    TableColumn::Name => ModelAction::RenameStudent(name, value),
+                    // accept a decimal comma as well.
+                    _ => match value.replace(',', ".").parse() {
+                        Ok(points) => ModelAction::SetStudentPoints(name, points),
+                        Err(_) => {
+                            self.editor_error = Some(format!("'{value}' is not a number."));
+                            return None;
+                        }
+                    },
+                };
+                Some(Action::UpdateModel(action))
+            }
+            _ => {
+                editor.handle_event(&Event::Key(key));
+                None
+            }
+        }
+    }
+
+    pub fn set_sort(&mut self, sort: Option<SortOrder>) {
+        self.sort = sort;
+        self.apply_sort();
+    }
+
+    // sort the rows and keep the selected student selected.
+    fn apply_sort(&mut self) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+        let selected_name = self
+            .state
+            .selected()
+            .and_then(|index| self.data.get(index))
+            .map(|row| row.name.clone());
+
+        self.data.sort_by(|a, b| sort.compare(a, b));
+
+        if let Some(name) = selected_name {
+            let index = self.data.iter().position(|row| row.name == name);
+            self.state.select(index.or(Some(0)));
+            self.scroll_to_selected();
+        }
+    }
+
+    // cycle through name -> points -> grade -> unsorted.
+    fn cycle_sort_column(&mut self) {
+        let descending = self.sort.is_some_and(|sort| sort.descending);
+        let column = match self.sort.map(|sort| sort.column) {
+            None => Some(SortColumn::Name),
+            Some(SortColumn::Name) => Some(SortColumn::Points),
+            Some(SortColumn::Points) => Some(SortColumn::Grade),
+            Some(SortColumn::Grade) => None,
+        };
+        self.set_sort(column.map(|column| SortOrder { column, descending }));
+    }
+
+    fn toggle_sort_direction(&mut self) {
+        if let Some(sort) = self.sort {
+            self.set_sort(Some(SortOrder {
+                descending: !sort.descending,
+                ..sort
+            }));
+        }
+    }
+
+    pub fn search_query(&self) -> &str {
+        self.search.value()
+    }
+
+    // return the indices of all rows whose name contains the search query.
+    pub fn search_matches(&self) -> Vec<usize> {
+        let query = self.search_query().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.name.to_lowercase().contains(&query))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    // select the next (or previous) match, starting at the given offset from the selection.
+    fn select_match(&mut self, forward: bool, offset: usize) {
+        let matches = self.search_matches();
+        let current = self.state.selected().unwrap_or_default();
+        let next = match forward {
+            true => matches
+                .iter()
+                .find(|&&index| index >= current + offset)
+                .or(matches.first()),
+            false => matches
+                .iter()
+                .rev()
+                .find(|&&index| index < current)
+                .or(matches.last()),
+        };
+        if let Some(&index) = next {
+            self.state.select(Some(index));
+            self.scroll_to_selected();
+        }
+    }
+
+    pub fn handle_search_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                self.search.reset();
+                Some(Action::LeaveSearchMode)
+            }
+            KeyCode::Enter => Some(Action::LeaveSearchMode),
+            _ => {
+                self.search.handle_event(&Event::Key(key));
+                self.select_match(true, 0);
+                None
+            }
+        }
+    }
+
+    fn select_page_up(&mut self) {
+        let index = self.state.selected().unwrap_or_default();
+        self.state
+            .select(Some(index.saturating_sub(self.page_size)));
+        self.scroll_to_selected();
+    }
+
+    fn select_page_down(&mut self) {
+        let index = self.state.selected().unwrap_or_default() + self.page_size;
+        self.state
+            .select(Some(index.min(self.data.len().saturating_sub(1))));
+        self.scroll_to_selected();
+    }
+
+    fn select_first(&mut self) {
+        self.state.select(Some(0));
+        self.scroll_to_selected();
+    }
+
+    fn select_last(&mut self) {
+        self.state.select(Some(self.data.len().saturating_sub(1)));
+        self.scroll_to_selected();
     }
 
     fn scroll_to_selected(&mut self) {
@@ -64,17 +451,92 @@ impl ExamResultTable {
 
     pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
         debug!("EVENT: {:?}", key);
+        if self.is_editing() {
+            return self.handle_editor_event(key);
+        }
+
+        let pending_g = std::mem::take(&mut self.pending_g);
+        if key.modifiers == KeyModifiers::CONTROL {
+            match key.code {
+                KeyCode::Char('d') => self.select_page_down(),
+                KeyCode::Char('u') => self.select_page_up(),
+                _ => {}
+            }
+            return None;
+        }
+
         match key.code {
-            KeyCode::Up => {
+            KeyCode::Up | KeyCode::Char('k') => {
                 self.state.select_previous();
                 self.scroll_to_selected();
                 None
             }
-            KeyCode::Down => {
+            KeyCode::Down | KeyCode::Char('j') => {
                 self.state.select_next();
                 self.scroll_to_selected();
                 None
             }
+            KeyCode::Home => {
+                self.select_first();
+                None
+            }
+            KeyCode::Char('g') if pending_g => {
+                self.select_first();
+                None
+            }
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                None
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.select_last();
+                None
+            }
+            KeyCode::Left => {
+                self.select_column(false);
+                None
+            }
+            KeyCode::Right => {
+                self.select_column(true);
+                None
+            }
+            KeyCode::Enter => self
+                .selected_name()
+                .map(|name| Action::OpenRowMenu(name.to_string())),
+            KeyCode::Char('c') => {
+                self.start_editing();
+                None
+            }
+            KeyCode::Esc if self.grade_filter.is_some() => Some(Action::FilterGrade(None)),
+            KeyCode::Char('i') => Some(Action::ToggleStudentPane),
+            KeyCode::Char('s') => {
+                self.cycle_sort_column();
+                None
+            }
+            KeyCode::Char('S') => {
+                self.toggle_sort_direction();
+                None
+            }
+            KeyCode::Char('/') => {
+                self.search.reset();
+                Some(Action::EnterSearchMode)
+            }
+            KeyCode::Char('n') => {
+                self.select_match(true, 1);
+                None
+            }
+            KeyCode::Char('N') => {
+                self.select_match(false, 0);
+                None
+            }
+            KeyCode::PageUp => {
+                self.select_page_up();
+                None
+            }
+            KeyCode::PageDown => {
+                self.select_page_down();
+                None
+            }
             KeyCode::Char('+') => {
                 if let Some(index) = self.state.selected() {
                     Some(Action::UpdateModel(ModelAction::IncrementStudentPoints(
@@ -93,6 +555,11 @@ impl ExamResultTable {
                     None
                 }
             }
+            KeyCode::Char('d') => self.state.selected().and_then(|index| {
+                self.data
+                    .get(index)
+                    .map(|row| Action::UpdateModel(ModelAction::DeleteStudent(row.name.clone())))
+            }),
             _ => None,
         }
     }
@@ -101,85 +568,169 @@ impl ExamResultTable {
 impl Widget for &mut ExamResultTable {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // exam table
-        let block = Block::new()
-            .title(Line::raw(format!(" 🚸 {} ", self.title)))
-            .title_style(THEME.block_title())
-            .style(THEME.block())
-            .borders(Borders::ALL)
-            .border_type(BorderType::Plain);
-
-        let header = [
-            Text::from("Name"),
-            Text::from("Points").alignment(Alignment::Center),
-            Text::from("Percentage").alignment(Alignment::Center),
-            Text::from("Grade").alignment(Alignment::Center),
-        ]
-        .into_iter()
-        .map(Cell::from)
-        .collect::<Row>()
-        .style(THEME.table_header())
-        .height(1);
+        let failing = self.data.iter().filter(|row| self.is_failing(row)).count();
+        let filter = match self.grade_filter {
+            Some(grade) => format!(" · grade {grade}"),
+            None => String::new(),
+        };
+        let title = match failing {
+            0 => format!(" 🚸 {}{filter} ", self.title),
+            n => format!(" 🚸 {}{filter} · {n} failing ", self.title),
+        };
+        let mut block = THEME
+            .frame()
+            .title(Line::raw(title))
+            .border_style(THEME.border(self.focused));
+
+        let sort_indicator = |column: SortColumn| match self.sort {
+            Some(sort) if sort.column == column && sort.descending => " ▼",
+            Some(sort) if sort.column == column => " ▲",
+            _ => "",
+        };
+        let columns = self.visible_columns();
+        let widths: Vec<Constraint> = columns
+            .iter()
+            .map(|column| match column {
+                TableColumn::Name => Constraint::Min(2),
+                _ => Constraint::Min(1),
+            })
+            .collect();
+
+        // long names are cut, the full name of the selected student is shown below the table.
+        let name_width = name_column_width(block.inner(area), &widths);
+        if let Some(name) = self
+            .selected_name()
+            .filter(|name| name.width() > name_width)
+        {
+            block = block.title_bottom(Line::from(format!(" {name} ")).left_aligned());
+        }
+
+        let header = columns
+            .iter()
+            .map(|column| match column {
+                TableColumn::Name => {
+                    Text::from(format!("Name{}", sort_indicator(SortColumn::Name)))
+                }
+                TableColumn::Points => {
+                    Text::from(format!("Points{}", sort_indicator(SortColumn::Points)))
+                        .alignment(Alignment::Center)
+                }
+                TableColumn::Grade => {
+                    Text::from(format!("Grade{}", sort_indicator(SortColumn::Grade)))
+                        .alignment(Alignment::Center)
+                }
+                TableColumn::Percentile => Text::from("Percentile").alignment(Alignment::Center),
+                TableColumn::Average => Text::from("Average").alignment(Alignment::Center),
+                _ => Text::from("Percentage").alignment(Alignment::Center),
+            })
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(THEME.table_header())
+            .height(1);
 
+        let matches = self.search_matches();
+        let editing = self.editor.as_ref().zip(self.state.selected());
         let rows = self.data.iter().enumerate().map(|(index, data)| {
-            let item = data.as_str_array();
-            item.into_iter()
-                .enumerate()
-                .map(|(idx, content)| {
-                    let text = format!("\n{content}\n");
+            let is_match = matches.contains(&index);
+            columns
+                .iter()
+                .map(|&column| {
+                    let text = match editing {
+                        Some(((edited, editor), row)) if row == index && *edited == column => {
+                            editor_text(editor, self.editor_error.as_deref())
+                        }
+                        _ if column == TableColumn::Name => {
+                            Text::from(format!("\n{}\n", truncate(&data.name, name_width)))
+                        }
+                        _ => Text::from(format!("\n{}\n", data.cell(column))),
+                    };
                     let mut align = Alignment::Left;
 
-                    if idx != 0 {
+                    if column != TableColumn::Name {
                         align = Alignment::Center
                     }
 
                     let grade_style = match data.grade {
-                        5 | 6 => Style::new().bg(Color::Red).add_modifier(Modifier::BOLD),
-                        3 | 4 => Style::new().bg(Color::Yellow).add_modifier(Modifier::BOLD),
-                        1 | 2 => Style::new().bg(Color::Green).add_modifier(Modifier::BOLD),
+                        1..=6 if !data.absent => Style::new()
+                            .bg(THEME.grade_color(data.grade))
+                            .add_modifier(Modifier::BOLD),
                         _ => Style::new().add_modifier(Modifier::BOLD),
                     };
 
-                    let mut text = Text::from(text).alignment(align);
+                    let mut text = text.alignment(align);
 
-                    if idx == 3 {
+                    if column == TableColumn::Grade {
                         text = text.patch_style(grade_style);
                     }
 
+                    if column == TableColumn::Name && is_match {
+                        text = text.patch_style(THEME.tag(true));
+                    }
+
                     Cell::from(text)
                 })
                 .collect::<Row>()
-                .style(THEME.table_row(index))
-                .height(3)
+                .style(match self.is_failing(data) {
+                    true => THEME.table_row_failing(),
+                    false => THEME.table_row(index),
+                })
+                .height(ITEM_HEIGHT as u16)
         });
 
-        let bar = " █ ";
-        let table = Table::new(
-            rows,
-            [
-                // + 1 is for padding.
-                Constraint::Min(2),
-                Constraint::Min(1),
-                Constraint::Min(1),
-                Constraint::Min(1),
-            ],
-        )
-        .block(block)
-        .header(header)
-        // .row_highlight_style(THEME.table_row_selected())
-        .cell_highlight_style(THEME.table_row_selected())
-        .highlight_spacing(ratatui::widgets::HighlightSpacing::Always)
-        .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]));
+        let bar = THEME.highlight_symbol();
+        let table = Table::new(rows, widths)
+            .block(block)
+            .header(header)
+            // .row_highlight_style(THEME.table_row_selected())
+            .cell_highlight_style(THEME.table_row_selected())
+            .highlight_spacing(ratatui::widgets::HighlightSpacing::Always)
+            .highlight_symbol(Text::from(vec!["".into(), bar.into(), "".into()]));
+
+        // borders and header are no rows.
+        self.page_size = (area.height.saturating_sub(3) as usize / ITEM_HEIGHT).max(1);
 
         StatefulWidget::render(table, area, buf, &mut self.state);
+
+        if self.data.len() > self.page_size {
+            let scrollbar_area = Rect {
+                y: area.y + 2,
+                height: area.height.saturating_sub(3),
+                ..area
+            };
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(THEME.text())
+                .render(scrollbar_area, buf, &mut self.scroll_state);
+        }
     }
 }
 
-#[derive(Debug, Default, Clone)]
+// the width of the first column like the table computes it, next to the highlight symbol.
+fn name_column_width(area: Rect, widths: &[Constraint]) -> usize {
+    let [_, columns_area] = Layout::horizontal([
+        Constraint::Length(THEME.highlight_symbol().width() as u16),
+        Constraint::Fill(0),
+    ])
+    .areas(area);
+    Layout::horizontal(widths.iter().copied())
+        .spacing(1)
+        .split(columns_area)
+        .first()
+        .map_or(0, |column| column.width as usize)
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(into = "ResultJson")]
 pub struct ExamResultTableRowData {
     name: String,
     points: f64,
     percentage: f64,
     grade: u8,
+    absent: bool,
+    percentile: Option<f64>, // rank in the class, none for absent students
+    average: Option<f64>,    // weighted over all exams, none without other exams
+    notes: String,
 }
 
 impl ExamResultTableRowData {
@@ -189,15 +740,187 @@ impl ExamResultTableRowData {
             points,
             percentage,
             grade,
+            absent: false,
+            percentile: None,
+            average: None,
+            notes: String::new(),
         }
     }
 
-    fn as_str_array(&self) -> [String; 4] {
-        [
+    pub fn with_percentile(mut self, percentile: Option<f64>) -> Self {
+        self.percentile = percentile;
+        self
+    }
+
+    pub fn with_average(mut self, average: Option<f64>) -> Self {
+        self.average = average;
+        self
+    }
+
+    pub fn with_absent(mut self, absent: bool) -> Self {
+        self.absent = absent;
+        self
+    }
+
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.notes = notes.to_string();
+        self
+    }
+
+    pub fn is_absent(&self) -> bool {
+        self.absent
+    }
+
+    pub fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    // the grade as number, none for absent students.
+    pub fn grade(&self) -> Option<u8> {
+        (!self.absent).then_some(self.grade)
+    }
+
+    // the row in csv format, as it is copied to the clipboard. Numbers are rounded like in spreadsheets.
+    pub fn to_csv(&self) -> String {
+        let number = |column| {
+            self.value(column)
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        };
+        let grade = match self.absent {
+            true => "absent".to_string(),
+            false => self.grade.to_string(),
+        };
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let record = [
             self.name.clone(),
-            self.points.to_string(),
-            self.percentage.to_string(),
-            self.grade.to_string(),
-        ]
+            number(TableColumn::Points),
+            number(TableColumn::Percentage),
+            grade,
+        ];
+        // writing into memory can't fail.
+        let _ = writer.write_record(&record);
+        let csv = String::from_utf8(writer.into_inner().unwrap_or_default()).unwrap_or_default();
+        csv.trim_end().to_string()
+    }
+
+    pub fn is_passed(&self, pass_grade: u8) -> bool {
+        !self.absent && self.grade <= pass_grade
+    }
+
+    // return the number of a table column as displayed, e.g. for spreadsheets.
+    // Absent students have no grade.
+    pub fn value(&self, column: TableColumn) -> Option<f64> {
+        let precision = precision();
+        match column {
+            TableColumn::Points => Some(round_dp(self.points, precision.points)),
+            TableColumn::Percentage => Some(round_dp(self.percentage, precision.percentage + 2)),
+            TableColumn::Percentile => self
+                .percentile
+                .map(|share| round_dp(share, precision.percentage + 2)),
+            TableColumn::Average => self.average.map(|avg| round_dp(avg, precision.average)),
+            // verbal grades are written as text.
+            TableColumn::Grade if !self.absent && grade_format() == GradeFormat::Numeric => {
+                Some(self.grade.into())
+            }
+            _ => None,
+        }
+    }
+
+    // return the displayed value of a table column.
+    pub fn cell(&self, column: TableColumn) -> String {
+        match column {
+            TableColumn::Name => self.name.clone(),
+            TableColumn::Points => format_points(self.points),
+            TableColumn::Percentage => format_share(self.percentage),
+            TableColumn::Percentile => self.percentile.map(format_percentage).unwrap_or("-".into()),
+            TableColumn::Average => self.average.map(format_average).unwrap_or("-".into()),
+            TableColumn::Grade if self.absent => "-".to_string(),
+            TableColumn::Grade => format_grade(self.grade),
+            _ => String::new(),
+        }
+    }
+}
+
+// a row as written by `--json`, absent students have no grade.
+#[derive(Serialize)]
+struct ResultJson {
+    name: String,
+    points: f64,
+    percentage: f64,
+    grade: Option<u8>,
+    absent: bool,
+    percentile: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    average: Option<f64>,
+}
+
+impl From<ExamResultTableRowData> for ResultJson {
+    fn from(row: ExamResultTableRowData) -> Self {
+        Self {
+            grade: row.grade(),
+            name: row.name,
+            points: row.points,
+            percentage: row.percentage,
+            absent: row.absent,
+            percentile: row.percentile,
+            average: row.average,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_follows_student() {
+        let row = |name: &str, points: f64| ExamResultTableRowData::new(name, points, points, 1);
+        let mut table = ExamResultTable::new();
+        table.set_data(vec![row("Anna", 80.0), row("Ben", 60.0)]);
+        assert!(table.select_name("Ben"));
+
+        // Ben moved to the first row.
+        table.set_data(vec![row("Ben", 90.0), row("Anna", 80.0)]);
+        assert_eq!(table.selected_name(), Some("Ben"));
+
+        // the row is kept if the student was deleted.
+        table.set_data(vec![row("Anna", 80.0)]);
+        assert_eq!(table.selected_name(), Some("Anna"));
+        assert!(!table.select_name("Ben"));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let row = ExamResultTableRowData::new("Müller, Anna", 46.5, 0.9302468, 1);
+        assert_eq!(row.to_csv(), "\"Müller, Anna\",46.5,0.93,1");
+        let mut row = ExamResultTableRowData::new("Ben", 0.0, 0.0, 6);
+        row.absent = true;
+        assert_eq!(row.to_csv(), "Ben,0,0,absent");
+    }
+
+    #[test]
+    fn test_parse_sort_order() {
+        let order = |column, descending| Ok(SortOrder { column, descending });
+        assert_eq!("name".parse(), order(SortColumn::Name, false));
+        assert_eq!("points:desc".parse(), order(SortColumn::Points, true));
+        assert!("grade:up".parse::<SortOrder>().is_err());
+        assert!("notes".parse::<SortOrder>().is_err());
+    }
+
+    #[test]
+    fn test_grade_filter() {
+        let row = |name: &str, grade: u8| ExamResultTableRowData::new(name, 50.0, 50.0, grade);
+        let mut table = ExamResultTable::new();
+        table.set_data(vec![row("Anna", 1), row("Ben", 3), row("Cem", 3)]);
+        table.select_name("Cem");
+
+        table.set_grade_filter(Some(3));
+        assert_eq!(table.data.len(), 2);
+        assert_eq!(table.selected_name(), Some("Cem"));
+
+        table.set_grade_filter(None);
+        assert_eq!(table.data.len(), 3);
+        assert_eq!(table.selected_name(), Some("Cem"));
     }
 }
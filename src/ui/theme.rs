@@ -1,4 +1,13 @@
-use ratatui::style::{Color, Modifier, Style, Stylize};
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+use ratatui::{
+    layout::Alignment,
+    style::{Color, Modifier, Style, Stylize},
+    widgets::{Block, BorderType, Borders, Padding},
+};
+use serde::Deserialize;
+use strum_macros::{Display, EnumIter, EnumString};
 
 use crate::model::scale::GradeScaleType;
 
@@ -16,11 +25,45 @@ pub trait AppStyle {
     fn block_title(&self) -> Style {
         Style::default()
     }
-    fn background(&self) -> Style {
-        Style::default()
+    // border of the pane which receives the keys.
+    fn border(&self, focused: bool) -> Style {
+        match focused {
+            true => Style::default().fg(self.accent_color()),
+            false => Style::default(),
+        }
+    }
+    fn chrome(&self) -> Chrome {
+        Chrome::default()
+    }
+    // a block with the borders, title alignment and padding of the theme.
+    fn frame(&self) -> Block<'static> {
+        let chrome = self.chrome();
+        let block = Block::new()
+            .title_alignment(chrome.title_alignment.into())
+            .title_style(self.block_title())
+            .style(self.block())
+            .padding(Padding::horizontal(chrome.padding));
+        match chrome.borders.border_type() {
+            Some(border_type) => block.borders(Borders::ALL).border_type(border_type),
+            None => block,
+        }
+    }
+    fn highlight_symbol(&self) -> &'static str {
+        self.chrome().highlight_symbol.symbol()
+    }
+    fn error_text(&self) -> Style {
+        Style::default().fg(Color::Red)
+    }
+    fn grade_color(&self, grade: u8) -> Color {
+        match grade {
+            5 | 6 => Color::Red,
+            3 | 4 => Color::Yellow,
+            _ => Color::Green,
+        }
     }
     fn table_header(&self) -> Style;
     fn table_row(&self, index: usize) -> Style;
+    fn table_row_failing(&self) -> Style;
     fn table_row_selected(&self) -> Style;
     fn table_col_selected(&self) -> Style;
     fn tab(&self, selected: bool) -> Style;
@@ -51,50 +94,302 @@ pub const LIGHT_GRAY: Color = Color::Rgb(80, 73, 69);
 pub const GRAY: Color = Color::Rgb(60, 56, 54);
 pub const BLACK: Color = Color::Rgb(8, 8, 8); // not really black, often #080808
 
-#[derive(Debug, Default)]
-pub struct Theme;
+pub const CREAM: Color = Color::Rgb(251, 241, 199);
+pub const SAND: Color = Color::Rgb(235, 219, 178);
+pub const DARK_SAND: Color = Color::Rgb(213, 196, 161);
 
-impl AppStyle for Theme {
-    fn scale_color(&self, scale_type: &GradeScaleType) -> Color {
+pub const DARK_RED: Color = Color::Rgb(97, 38, 38);
+pub const ROSE: Color = Color::Rgb(242, 196, 186);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderKind {
+    #[default]
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+    None,
+}
+
+impl BorderKind {
+    fn border_type(&self) -> Option<BorderType> {
+        match self {
+            BorderKind::Plain => Some(BorderType::Plain),
+            BorderKind::Rounded => Some(BorderType::Rounded),
+            BorderKind::Double => Some(BorderType::Double),
+            BorderKind::Thick => Some(BorderType::Thick),
+            BorderKind::None => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TitleAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl From<TitleAlignment> for Alignment {
+    fn from(alignment: TitleAlignment) -> Self {
+        match alignment {
+            TitleAlignment::Left => Alignment::Left,
+            TitleAlignment::Center => Alignment::Center,
+            TitleAlignment::Right => Alignment::Right,
+        }
+    }
+}
+
+/// Marker in front of the selected row of tables and lists.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HighlightSymbol {
+    #[default]
+    Block,
+    Bar,
+    Arrow,
+    None,
+}
+
+impl HighlightSymbol {
+    fn symbol(&self) -> &'static str {
+        match self {
+            HighlightSymbol::Block => " █ ",
+            HighlightSymbol::Bar => " ▌ ",
+            HighlightSymbol::Arrow => " ▶ ",
+            HighlightSymbol::None => "",
+        }
+    }
+}
+
+/// Borders and spacing of the blocks, set in the `[chrome]` table of a theme file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Chrome {
+    pub borders: BorderKind,
+    pub title_alignment: TitleAlignment,
+    pub highlight_symbol: HighlightSymbol,
+    pub padding: u16, // horizontal padding inside the borders
+}
+
+pub const DEFAULT_CHROME: Chrome = Chrome {
+    borders: BorderKind::Plain,
+    title_alignment: TitleAlignment::Left,
+    highlight_symbol: HighlightSymbol::Block,
+    padding: 0,
+};
+
+impl Default for Chrome {
+    fn default() -> Self {
+        DEFAULT_CHROME
+    }
+}
+
+/// Colors of the grading scales in the header indicator and the scale tab,
+/// set in the `[scale_colors]` table of a theme file or the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ScaleColors {
+    pub ihk: Color,
+    pub techniker: Color,
+    pub linear: Color,
+    pub custom: Color,
+}
+
+pub const DEFAULT_SCALE_COLORS: ScaleColors = ScaleColors {
+    ihk: Color::Yellow,
+    techniker: Color::Blue,
+    linear: Color::Green,
+    custom: Color::LightRed,
+};
+
+impl Default for ScaleColors {
+    fn default() -> Self {
+        DEFAULT_SCALE_COLORS
+    }
+}
+
+impl ScaleColors {
+    pub fn get(&self, scale_type: &GradeScaleType) -> Color {
         match scale_type {
-            GradeScaleType::IHK => Color::Yellow,
-            GradeScaleType::TECHNIKER => Color::Blue,
-            GradeScaleType::LINEAR => Color::Green,
-            GradeScaleType::Custom(_) => Color::LightRed,
+            GradeScaleType::IHK => self.ihk,
+            GradeScaleType::TECHNIKER => self.techniker,
+            GradeScaleType::LINEAR => self.linear,
+            GradeScaleType::Custom(_) => self.custom,
+        }
+    }
+}
+
+/// The colors and block chrome used by the theme.
+/// Custom themes can define them in a toml file, missing values are taken from the dark palette.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    pub text: Color,
+    pub text_dark: Color,
+    pub background: Color,
+    pub background_alt: Color,
+    pub background_dark: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub failing: Color, // background of students who failed
+    pub chrome: Chrome,
+    pub scale_colors: ScaleColors,
+}
+
+pub const DARK_PALETTE: Palette = Palette {
+    text: DARK_WHITE,
+    text_dark: LIGHT_GRAY,
+    background: GRAY,
+    background_alt: LIGHT_GRAY,
+    background_dark: BLACK,
+    accent: Color::Cyan,
+    highlight: Color::Magenta,
+    failing: DARK_RED,
+    chrome: DEFAULT_CHROME,
+    scale_colors: DEFAULT_SCALE_COLORS,
+};
+
+impl Default for Palette {
+    fn default() -> Self {
+        DARK_PALETTE
+    }
+}
+
+pub const LIGHT_PALETTE: Palette = Palette {
+    text: GRAY,
+    text_dark: CREAM,
+    background: SAND,
+    background_alt: DARK_SAND,
+    background_dark: CREAM,
+    accent: Color::Blue,
+    highlight: Color::Magenta,
+    failing: ROSE,
+    chrome: DEFAULT_CHROME,
+    scale_colors: DEFAULT_SCALE_COLORS,
+};
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Display, EnumString, EnumIter,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ThemeVariant {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeVariant {
+    pub fn palette(&self) -> Palette {
+        match self {
+            ThemeVariant::Dark => DARK_PALETTE,
+            ThemeVariant::Light => LIGHT_PALETTE,
+        }
+    }
+
+    // return the variant which follows this one.
+    pub fn next(&self) -> ThemeVariant {
+        match self {
+            ThemeVariant::Dark => ThemeVariant::Light,
+            ThemeVariant::Light => ThemeVariant::Dark,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ThemeState {
+    name: String,
+    palette: Palette,
+}
+
+/// The theme can be switched at runtime, so it is shared behind a lock.
+#[derive(Debug)]
+pub struct Theme {
+    state: RwLock<ThemeState>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            state: RwLock::new(ThemeState {
+                name: ThemeVariant::Dark.to_string(),
+                palette: DARK_PALETTE,
+            }),
         }
     }
+}
+
+impl Theme {
+    pub fn name(&self) -> String {
+        self.state
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .name
+            .clone()
+    }
+
+    pub fn set(&self, name: &str, palette: Palette) {
+        *self.state.write().unwrap_or_else(|e| e.into_inner()) = ThemeState {
+            name: name.to_string(),
+            palette,
+        };
+    }
+
+    fn palette(&self) -> Palette {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).palette
+    }
+}
+
+impl AppStyle for Theme {
+    fn scale_color(&self, scale_type: &GradeScaleType) -> Color {
+        self.palette().scale_colors.get(scale_type)
+    }
 
     fn accent_color(&self) -> Color {
-        Color::Cyan
+        self.palette().accent
+    }
+
+    fn chrome(&self) -> Chrome {
+        self.palette().chrome
     }
 
     fn text_color(&self, dark: bool) -> Color {
         match dark {
-            true => LIGHT_GRAY,
-            false => DARK_WHITE,
+            true => self.palette().text_dark,
+            false => self.palette().text,
         }
     }
 
     fn background_color(&self, dark: bool) -> Color {
         match dark {
-            true => BLACK,
-            false => GRAY,
+            true => self.palette().background_dark,
+            false => self.palette().background,
         }
     }
 
     fn table_header(&self) -> Style {
         Style::default()
-            .fg(DARK_WHITE)
+            .fg(self.palette().text)
             .add_modifier(Modifier::ITALIC)
     }
 
     fn table_row(&self, index: usize) -> Style {
+        let palette = self.palette();
         match index % 2 {
-            0 => Style::default().fg(DARK_WHITE).bg(GRAY),
-            _ => Style::default().fg(DARK_WHITE).bg(LIGHT_GRAY),
+            0 => Style::default().fg(palette.text).bg(palette.background),
+            _ => Style::default().fg(palette.text).bg(palette.background_alt),
         }
     }
 
+    fn table_row_failing(&self) -> Style {
+        let palette = self.palette();
+        Style::default().fg(palette.text).bg(palette.failing)
+    }
+
     fn table_row_selected(&self) -> Style {
         Style::default()
             .bg(self.accent_color())
@@ -118,14 +413,28 @@ impl AppStyle for Theme {
     }
 
     fn tag(&self, colored: bool) -> Style {
+        let palette = self.palette();
         match colored {
-            true => Style::default().bg(self.accent_color()).fg(LIGHT_GRAY),
-            false => Style::default().fg(DARK_WHITE).bg(LIGHT_GRAY),
+            true => Style::default()
+                .bg(self.accent_color())
+                .fg(palette.text_dark),
+            false => Style::default().fg(palette.text).bg(palette.background_alt),
         }
     }
 
+    fn indicator(&self, scale_type: Option<&GradeScaleType>) -> Style {
+        let bg = match scale_type {
+            Some(scale) => self.scale_color(scale),
+            None => self.palette().highlight,
+        };
+        Style::default().fg(BLACK).bg(bg)
+    }
+
     fn top_bar(&self) -> Style {
-        Style::default().bg(GRAY).fg(Color::Magenta)
+        let palette = self.palette();
+        Style::default()
+            .bg(palette.background)
+            .fg(palette.highlight)
     }
 
     fn bottom_bar(&self) -> Style {
@@ -133,8 +442,10 @@ impl AppStyle for Theme {
     }
 
     fn bar_chart(&self) -> Style {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(self.accent_color())
     }
 }
 
-pub const THEME: Theme = Theme {};
+lazy_static! {
+    pub static ref THEME: Theme = Theme::default();
+}
@@ -0,0 +1,144 @@
+use std::time::SystemTime;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, HighlightSpacing, List, ListItem, ListState, StatefulWidget, Widget},
+};
+
+use super::{
+    centered_rect,
+    theme::{AppStyle, THEME},
+};
+use crate::{action::Action, recent::RecentFile};
+
+/// Start screen if graca was started without a course.
+#[derive(Debug, Default)]
+pub struct WelcomeScreen {
+    recent_files: Vec<RecentFile>,
+    state: ListState,
+}
+
+impl WelcomeScreen {
+    pub fn new(recent_files: Vec<RecentFile>) -> Self {
+        Self {
+            recent_files,
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    pub fn handle_event(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.state.select_next(),
+            KeyCode::Enter => {
+                let index = self.state.selected().unwrap_or_default();
+                // the recent files are followed by the open and new entries.
+                return match self.recent_files.get(index) {
+                    Some(file) => Some(Action::LoadStudentList(file.path.clone())),
+                    None if index == self.recent_files.len() => Some(Action::OpenFilePicker),
+                    None => Some(Action::CloseWelcome),
+                };
+            }
+            // opens the recent file with this number directly.
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c.to_digit(10)? as usize - 1;
+                let file = self.recent_files.get(index)?;
+                return Some(Action::LoadStudentList(file.path.clone()));
+            }
+            KeyCode::Char('o') => return Some(Action::OpenFilePicker),
+            KeyCode::Char('?') | KeyCode::F(1) => return Some(Action::ShowHelp),
+            KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Esc => return Some(Action::CloseWelcome),
+            _ => {}
+        }
+        None
+    }
+}
+
+impl Widget for &mut WelcomeScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let now = SystemTime::now();
+        let items: Vec<ListItem> = self
+            .recent_files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                Line::from(vec![
+                    Span::from(match i < 9 {
+                        true => format!("{} ", i + 1),
+                        false => "  ".to_string(),
+                    })
+                    .style(THEME.tag(false)),
+                    Span::from(format!("📄 {}  ", file.path.display())),
+                    Span::from(file.opened_ago(now)).style(THEME.text()),
+                ])
+            })
+            .chain([Line::from("📂 Open file…"), Line::from("✚ New course")])
+            .enumerate()
+            .map(|(i, entry)| ListItem::new(entry).style(THEME.table_row(i)))
+            .collect();
+
+        let block = THEME
+            .frame()
+            .title(format!(
+                " 👋 Welcome to {} ",
+                env!("CARGO_PKG_NAME").to_uppercase()
+            ))
+            .title_bottom(Line::from(" Enter to open · Esc to skip ").right_aligned());
+
+        let shortcuts = Line::from(
+            [
+                ("o", "open"),
+                (":", "command"),
+                ("?", "help"),
+                ("q", "quit"),
+            ]
+            .into_iter()
+            .flat_map(|(key, description)| {
+                [
+                    Span::from(format!(" {key} ")).style(THEME.tag(true)),
+                    Span::from(format!(" {description}  ")).style(THEME.text()),
+                ]
+            })
+            .collect::<Vec<Span>>(),
+        )
+        .centered();
+
+        let height = self.recent_files.len() as u16 + 8;
+        let popup_area = centered_rect(area, Constraint::Max(70), Constraint::Length(height));
+        Clear.render(area, buf);
+        block.render(popup_area, buf);
+
+        let [label_area, list_area, shortcut_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .margin(1)
+        .areas(popup_area);
+
+        let label = match self.recent_files.is_empty() {
+            true => "No recent files.",
+            false => "Recent files",
+        };
+        Line::from(label)
+            .style(THEME.table_header())
+            .render(label_area, buf);
+        let list = List::new(items)
+            .highlight_style(THEME.table_row_selected())
+            .highlight_symbol(THEME.highlight_symbol())
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, list_area, buf, &mut self.state);
+        shortcuts.render(
+            Rect {
+                y: shortcut_area.bottom().saturating_sub(1),
+                height: 1,
+                ..shortcut_area
+            },
+            buf,
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Padding, Paragraph, Widget},
+};
+
+use super::theme::{AppStyle, THEME};
+
+/// Lists the keys which continue a pending key sequence, in the bottom right corner.
+#[derive(Debug, Default)]
+pub struct WhichKey {
+    pub prefix: String,
+    pub next_keys: Vec<(String, String)>,
+}
+
+impl Widget for &WhichKey {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let key_width = self
+            .next_keys
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or_default();
+        let lines: Vec<Line> = self
+            .next_keys
+            .iter()
+            .map(|(key, description)| {
+                Line::from(vec![
+                    Span::from(format!(" {key:>key_width$} ")).style(THEME.tag(true)),
+                    Span::from(format!(" {description}")).style(THEME.text()),
+                ])
+            })
+            .collect();
+
+        let width = lines
+            .iter()
+            .map(|line| line.width() as u16 + 4)
+            .max()
+            .unwrap_or_default()
+            .max(self.prefix.len() as u16 + 6)
+            .min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup_area = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.bottom().saturating_sub(height),
+            width,
+            height,
+        };
+
+        let block = THEME
+            .frame()
+            .title(format!(" {} … ", self.prefix))
+            .padding(Padding::horizontal(1));
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+}
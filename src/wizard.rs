@@ -0,0 +1,112 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use strum::IntoEnumIterator;
+
+use crate::cli::Args;
+use crate::config::get_config_file;
+use crate::model::scale::{parse_max_points, GradeScaleType, GradingError};
+
+/// Asks for scale, max points and course on the first start without any
+/// arguments and config, and stores the answers in `args`.
+/// The scale can be saved as `default_scale` to skip the wizard next time.
+pub fn run(args: &mut Args, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    writeln!(
+        output,
+        "Welcome to graca! No config found, let's set up this session."
+    )?;
+
+    let names: Vec<&str> = GradeScaleType::iter()
+        .filter(|scale_type| !scale_type.is_custom())
+        .map(|scale_type| scale_type.text())
+        .collect();
+    let question = format!("Grading scale [{}]", names.join("/"));
+    let scale_type = loop {
+        let answer = ask(input, output, &question, GradeScaleType::default().text())?;
+        match GradeScaleType::from_name(&answer) {
+            Some(scale_type) => break scale_type,
+            None => writeln!(output, "'{answer}' is no known scale.")?,
+        }
+    };
+    args.scale = Some(scale_type.text().to_string());
+
+    args.points = loop {
+        let answer = ask(input, output, "Max points", &args.max_points().to_string())?;
+        match parse_max_points(&answer) {
+            Ok(points) => break vec![points],
+            Err(e @ GradingError::NotANumber(_)) => writeln!(output, "{e}")?,
+            Err(_) => writeln!(output, "expected a positive number of (half) points.")?,
+        }
+    };
+
+    let course = ask(input, output, "Course file to open (empty to skip)", "")?;
+    if !course.is_empty() {
+        args.course = Some(PathBuf::from(course));
+    }
+
+    let save = ask(input, output, "Save the scale as default? [y/n]", "y")?;
+    if save.eq_ignore_ascii_case("y") {
+        match save_default_scale(scale_type) {
+            Ok(path) => writeln!(output, "Saved {}", path.display())?,
+            Err(e) => writeln!(output, "Unable to save the config: {e}")?,
+        }
+    }
+    Ok(())
+}
+
+// prints the question and returns the trimmed answer or the default on an empty line.
+fn ask(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    default: &str,
+) -> io::Result<String> {
+    match default.is_empty() {
+        true => write!(output, "{question}: ")?,
+        false => write!(output, "{question} ({default}): ")?,
+    }
+    output.flush()?;
+
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(match answer.trim() {
+        "" => default.to_string(),
+        answer => answer.to_string(),
+    })
+}
+
+fn save_default_scale(scale_type: GradeScaleType) -> color_eyre::eyre::Result<PathBuf> {
+    let path = get_config_file()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(
+        &path,
+        format!("default_scale = \"{}\"\n", scale_type.text()),
+    )?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Parser;
+
+    #[test]
+    fn test_answers() {
+        let mut args = Args::parse_from(["graca"]);
+        let mut input = io::Cursor::new("foo\nlinear\n0\n47,5\ndata/10a.csv\nn\n");
+        let mut output = Vec::new();
+        run(&mut args, &mut input, &mut output).unwrap();
+
+        assert_eq!(args.scale.as_deref(), Some("LINEAR"));
+        assert_eq!(args.points, vec![47.5]);
+        assert_eq!(args.course, Some(PathBuf::from("data/10a.csv")));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("'foo' is no known scale."));
+        assert!(output.contains("Max points (100): "));
+    }
+}